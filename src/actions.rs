@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+
+use regex::Regex;
+
+use crate::parser::{self, Statement};
+use crate::project::Project;
+use crate::scene;
+
+/// One place a script names an input action.
+struct Usage {
+    script: String,
+    line: usize,
+}
+
+/// Cross-checks the input actions the project's scripts use against those
+/// `project.godot` defines, printing the used actions nobody defined and the
+/// defined ones nobody uses. Returns how many problems there were.
+///
+/// The scripts read are those attached to nodes of the project's scenes and
+/// those of its autoloads. Godot's own `ui_*` actions always count as defined.
+pub fn report(project: &Project) -> io::Result<usize> {
+    let defined = defined(project)?;
+
+    let mut scripts: Vec<String> = project.settings("autoload")?.into_iter().map(|(_, path)| path.trim_start_matches('*').to_string()).collect();
+    for file in project.files(&["tscn"]) {
+        let root = scene::parse(BufReader::new(File::open(&file)?), Some(project))?;
+        for (_, node) in root.paths() {
+            if let Some(script) = node.script().filter(|s| !scripts.contains(&s.path)) {
+                scripts.push(script.path.clone());
+            }
+        }
+    }
+
+    // Input.is_action_pressed("jump"), event.is_action(&"jump"), Input.get_action_strength("jump")
+    let call_re = Regex::new(r#"\b(?:is_action(?:_just)?(?:_pressed|_released)?|get_action_(?:raw_)?strength|action_press|action_release)\(\s*[&^]?"(?P<action>[^"]+)""#).unwrap();
+    // Input.get_axis("left", "right") and Input.get_vector(...) name several
+    let axis_re = Regex::new(r#"\bget_(?:axis|vector)\((?P<args>[^)]*)\)"#).unwrap();
+    let string_re = Regex::new(r#"[&^]?"(?P<action>[^"]+)""#).unwrap();
+    // an InputEventAction built by hand
+    let event_re = Regex::new(r#"\.action\s*=\s*[&^]?"(?P<action>[^"]+)""#).unwrap();
+
+    let mut used: BTreeMap<String, Vec<Usage>> = BTreeMap::new();
+    for script in scripts.iter().filter(|s| s.ends_with(".gd")) {
+        let Ok(source) = fs::read_to_string(project.resolve(script)) else {
+            continue;
+        };
+        for (i, line) in source.lines().enumerate() {
+            let code = line.split('#').next().unwrap_or_default();
+            let mut actions: Vec<&str> = call_re.captures_iter(code).chain(event_re.captures_iter(code)).map(|c| c.name("action").unwrap().as_str()).collect();
+            for caps in axis_re.captures_iter(code) {
+                actions.extend(string_re.captures_iter(caps.name("args").unwrap().as_str()).map(|c| c.name("action").unwrap().as_str()));
+            }
+            for action in actions {
+                used.entry(action.to_string()).or_default().push(Usage { script: script.clone(), line: i + 1 });
+            }
+        }
+    }
+
+    let undefined: Vec<(&String, &Vec<Usage>)> = used.iter().filter(|(action, _)| !defined.contains(action) && !action.starts_with("ui_")).collect();
+    let unused: Vec<&String> = defined.iter().filter(|action| !used.contains_key(*action)).collect();
+    if !undefined.is_empty() {
+        println!("undefined actions:");
+        for (action, usages) in undefined.iter() {
+            let places: Vec<String> = usages.iter().map(|u| format!("{}:{}", u.script, u.line)).collect();
+            println!("  {}: {}", action, places.join(", "));
+        }
+    }
+    if !unused.is_empty() {
+        println!("unused actions:");
+        for action in unused.iter() {
+            println!("  {}", action);
+        }
+    }
+    println!(
+        "{} actions defined, {} used in {} scripts; {} undefined, {} unused",
+        defined.len(),
+        used.len(),
+        scripts.len(),
+        undefined.len(),
+        unused.len()
+    );
+    Ok(undefined.len() + unused.len())
+}
+
+/// The actions of the `[input]` section of `project.godot`, whose values run
+/// over several lines.
+fn defined(project: &Project) -> io::Result<Vec<String>> {
+    let path = project.root.join("project.godot");
+    let text = fs::read_to_string(&path)?;
+    let statements = parser::parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))?;
+    let mut actions = Vec::new();
+    let mut inside = false;
+    for statement in statements.iter() {
+        match statement {
+            Statement::Section { tag, .. } => inside = tag == "input",
+            Statement::Assignment { key, .. } if inside => actions.push(key.clone()),
+            Statement::Assignment { .. } => (),
+        }
+    }
+    Ok(actions)
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde_json::Value;
+
+/// Notes about nodes kept outside the scene, such as the team that owns them,
+/// what is left to do or what they cost at runtime, read from a JSON object
+/// mapping node paths to a note or to an object of labelled notes:
+///
+/// ```json
+/// { ".": "boss arena", "Boss": { "owner": "combat", "cost": "2.1 ms" } }
+/// ```
+#[derive(Default)]
+pub struct Annotations {
+    nodes: HashMap<String, Vec<(String, String)>>,
+}
+
+/// Where a scene's annotations are looked for when none are named: beside it,
+/// as `level.tscn.annotations.json`.
+pub fn sidecar(scene: &Path) -> PathBuf {
+    let mut name = scene.as_os_str().to_owned();
+    name.push(".annotations.json");
+    PathBuf::from(name)
+}
+
+impl Annotations {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), msg));
+        let value: Value = serde_json::from_str(&fs::read_to_string(path)?).map_err(|err| invalid(err.to_string()))?;
+        let Value::Object(entries) = value else {
+            return Err(invalid("expected an object mapping node paths to annotations".to_string()));
+        };
+        let mut nodes = HashMap::new();
+        for (node, value) in entries {
+            let notes = match value {
+                Value::Object(labelled) => labelled.into_iter().map(|(label, value)| (label, text(&value))).collect(),
+                value => vec![(String::new(), text(&value))],
+            };
+            nodes.insert(node, notes);
+        }
+        Ok(Self { nodes })
+    }
+
+    /// Keeps only the annotations of the subtree at `path`, with their paths
+    /// made relative to it, for when that node is shown as the root.
+    pub fn rebase(self, path: &str) -> Self {
+        let prefix = format!("{}/", path);
+        let nodes = self
+            .nodes
+            .into_iter()
+            .filter_map(|(node, notes)| match node.strip_prefix(&prefix) {
+                Some(rest) => Some((rest.to_string(), notes)),
+                None if node == path => Some((".".to_string(), notes)),
+                None => None,
+            })
+            .collect();
+        Self { nodes }
+    }
+
+    /// The annotations of the node at `path`, as `owner: combat, cost: 2.1 ms`.
+    pub fn label(&self, path: &str) -> Option<String> {
+        let notes = self.nodes.get(path)?;
+        let parts: Vec<String> = notes
+            .iter()
+            .map(|(label, note)| match label.is_empty() {
+                true => note.clone(),
+                false => format!("{}: {}", label, note),
+            })
+            .collect();
+        Some(parts.join(", "))
+    }
+}
+
+/// A note as written, without the quotes around strings.
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        value => value.to_string(),
+    }
+}
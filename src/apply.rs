@@ -0,0 +1,638 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::project::Project;
+use crate::scene::{self, Node};
+
+/// A list of edits to make across a project's scenes, read from YAML or JSON.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Plan {
+    // each operation is written as a map with one key, its kind
+    #[serde(with = "serde_yaml::with::singleton_map_recursive")]
+    pub operations: Vec<Operation>,
+}
+
+/// One edit of a plan. Scenes are named by res:// path or by a path relative to
+/// the current directory, and nodes by a path from the scene root, resolved as
+/// Godot resolves node paths, so `%Unique` and `..` work too.
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum Operation {
+    /// Renames a node, updating its children, connections, editable paths and
+    /// the NodePath properties that lead to it or through it.
+    Rename { scene: String, node: String, to: String },
+    /// Points the ext_resources loading one file at another, in one scene or all of them.
+    Repath {
+        from: String,
+        to: String,
+        #[serde(default)]
+        scene: Option<String>,
+    },
+    /// Removes a node and its descendants, with the connections to and from
+    /// them and their editable paths.
+    Delete { scene: String, node: String },
+    /// Sets a property of a node, adding it when the node doesn't have it yet.
+    Set { scene: String, node: String, property: String, value: String },
+    /// Connects a node's signal to a method of another node, unless it already is.
+    Connect { scene: String, signal: String, from: String, to: String, method: String },
+    /// Removes the connections of a node's signal, or only those to `to` or `method`.
+    Disconnect {
+        scene: String,
+        signal: String,
+        from: String,
+        #[serde(default)]
+        to: Option<String>,
+        #[serde(default)]
+        method: Option<String>,
+    },
+    /// Points the connections of a node's signal, or only those to `to` or
+    /// `method`, at another node or method.
+    Retarget {
+        scene: String,
+        signal: String,
+        from: String,
+        #[serde(default)]
+        to: Option<String>,
+        #[serde(default)]
+        method: Option<String>,
+        #[serde(default)]
+        new_to: Option<String>,
+        #[serde(default)]
+        new_method: Option<String>,
+    },
+}
+
+impl Plan {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let text = fs::read_to_string(path)?;
+        // YAML is a superset of JSON, so this reads either
+        serde_yaml::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))
+    }
+}
+
+/// A scene file's lines, edited in memory until the whole plan has gone through.
+struct Edited {
+    /// The scene as it was read, put back should writing the others fail.
+    original: String,
+    lines: Vec<String>,
+    /// The line ending the file was written with, kept when it is written back.
+    newline: &'static str,
+    changed: bool,
+}
+
+/// A plan carried out in memory: a line describing each edit, and the scenes
+/// as they read afterwards, for `write` to put in place.
+pub struct Applied {
+    pub edits: Vec<String>,
+    scenes: IndexMap<PathBuf, Edited>,
+}
+
+/// Carries out a plan, printing each edit. Every operation is tried before any
+/// file is written, so a plan that fails partway leaves the project untouched;
+/// with `dry_run`, nothing is written at all. Returns how many edits were made.
+pub fn run(plan: &Plan, project: Option<&Project>, dry_run: bool) -> io::Result<usize> {
+    let applied = edit(plan, project)?;
+    for edit in applied.edits.iter() {
+        println!("{}", edit);
+    }
+    if !dry_run {
+        applied.write()?;
+    }
+    Ok(applied.edits.len())
+}
+
+/// Carries out every operation of a plan on the scenes in memory, failing on
+/// the first that can't be made.
+pub fn edit(plan: &Plan, project: Option<&Project>) -> io::Result<Applied> {
+    let resolve = |scene: &str| -> PathBuf {
+        match project {
+            Some(project) => project.resolve(scene),
+            None => PathBuf::from(scene),
+        }
+    };
+    let mut scenes: IndexMap<PathBuf, Edited> = IndexMap::new();
+
+    let mut edits = Vec::new();
+    for op in plan.operations.iter() {
+        match op {
+            Operation::Rename { scene, node, to } => {
+                let path = open(&mut scenes, resolve(scene))?;
+                let count = rename(&mut scenes[&path].lines, node, to).map_err(|err| in_scene(scene, err))?;
+                edits.push(format!("{}: renamed {} to {} ({} lines)", scene, node, to, count));
+                scenes[&path].changed = true;
+            }
+            Operation::Repath { from, to, scene } => {
+                // a file path names the same resource as its res:// path
+                let from = match project {
+                    Some(project) if !from.starts_with("res://") && !from.starts_with("uid://") => {
+                        fs::canonicalize(from).ok().and_then(|file| project.res_path(&file)).unwrap_or(from.clone())
+                    }
+                    _ => from.clone(),
+                };
+                let files = match (scene, project) {
+                    (Some(scene), _) => vec![resolve(scene)],
+                    (None, Some(project)) => project.files(&["tscn"]),
+                    (None, None) => return Err(io::Error::other(format!("repath of {} names no scene, and there is no project to search", from))),
+                };
+                for file in files {
+                    let path = open(&mut scenes, file)?;
+                    let count = repath(&mut scenes[&path].lines, &from, to);
+                    if count > 0 {
+                        let name = project.and_then(|p| p.res_path(&path)).unwrap_or_else(|| path.display().to_string());
+                        edits.push(format!("{}: {} -> {}", name, from, to));
+                        scenes[&path].changed = true;
+                    }
+                }
+            }
+            Operation::Set { scene, node, property, value } => {
+                let path = open(&mut scenes, resolve(scene))?;
+                set(&mut scenes[&path].lines, node, property, value).map_err(|err| in_scene(scene, err))?;
+                edits.push(format!("{}: {}: {} = {}", scene, node, property, value));
+                scenes[&path].changed = true;
+            }
+            Operation::Connect { scene, signal, from, to, method } => {
+                let path = open(&mut scenes, resolve(scene))?;
+                if connect(&mut scenes[&path].lines, signal, from, to, method).map_err(|err| in_scene(scene, err))? {
+                    edits.push(format!("{}: connected {}:{}() => {}:{}()", scene, from, signal, to, method));
+                    scenes[&path].changed = true;
+                }
+            }
+            Operation::Disconnect { scene, signal, from, to, method } => {
+                let path = open(&mut scenes, resolve(scene))?;
+                let (from, to) = (canonical(&scenes[&path].lines, from), to.as_ref().map(|to| canonical(&scenes[&path].lines, to)));
+                let wanted = Wanted { signal, from: &from, to: to.as_deref(), method: method.as_deref() };
+                let lines = &mut scenes[&path].lines;
+                let before = lines.len();
+                lines.retain(|line| !wanted.matches(line));
+                let count = before - lines.len();
+                if count == 0 {
+                    return Err(in_scene(scene, format!("no connection of {}:{}() to remove", from, signal)));
+                }
+                while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                    lines.pop();
+                }
+                edits.push(format!("{}: disconnected {}:{}() ({} connections)", scene, from, signal, count));
+                scenes[&path].changed = true;
+            }
+            Operation::Retarget { scene, signal, from, to, method, new_to, new_method } => {
+                let path = open(&mut scenes, resolve(scene))?;
+                let (from, to) = (canonical(&scenes[&path].lines, from), to.as_ref().map(|to| canonical(&scenes[&path].lines, to)));
+                let wanted = Wanted { signal, from: &from, to: to.as_deref(), method: method.as_deref() };
+                let count = retarget(&mut scenes[&path].lines, &wanted, new_to.as_deref(), new_method.as_deref()).map_err(|err| in_scene(scene, err))?;
+                edits.push(format!("{}: retargeted {}:{}() ({} connections)", scene, from, signal, count));
+                scenes[&path].changed = true;
+            }
+            Operation::Delete { scene, node } => {
+                let path = open(&mut scenes, resolve(scene))?;
+                let count = delete(&mut scenes[&path].lines, node).map_err(|err| in_scene(scene, err))?;
+                edits.push(format!("{}: deleted {} ({} nodes)", scene, node, count));
+                scenes[&path].changed = true;
+            }
+        }
+    }
+    Ok(Applied { edits, scenes })
+}
+
+impl Applied {
+    /// Writes the scenes that changed, each beside itself first and then all
+    /// moved into place, so that a failure leaves none half written. Should
+    /// one fail, the scenes already moved into place are put back as they
+    /// were read, and the files written beside them are removed.
+    pub fn write(&self) -> io::Result<()> {
+        let changed: Vec<(&PathBuf, &Edited)> = self.scenes.iter().filter(|(_, edited)| edited.changed).collect();
+        let mut moved = 0;
+        let result = changed
+            .iter()
+            .try_for_each(|(path, edited)| fs::write(path.with_extension("tscn.partial"), edited.lines.join(edited.newline) + edited.newline))
+            .and_then(|_| {
+                changed.iter().try_for_each(|(path, _)| {
+                    fs::rename(path.with_extension("tscn.partial"), path)?;
+                    moved += 1;
+                    Ok(())
+                })
+            });
+        if result.is_err() {
+            for (path, edited) in &changed[..moved] {
+                let _ = fs::write(path, &edited.original);
+            }
+            for (path, _) in &changed[moved..] {
+                let _ = fs::remove_file(path.with_extension("tscn.partial"));
+            }
+        }
+        result
+    }
+}
+
+/// Reads a scene into `scenes` unless an earlier operation already has.
+fn open(scenes: &mut IndexMap<PathBuf, Edited>, path: PathBuf) -> io::Result<PathBuf> {
+    if !scenes.contains_key(&path) {
+        let text = fs::read_to_string(&path).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+        let newline = if text.contains("\r\n") { "\r\n" } else { "\n" };
+        let lines = text.lines().map(|line| line.to_string()).collect();
+        scenes.insert(path.clone(), Edited { original: text, lines, newline, changed: false });
+    }
+    Ok(path)
+}
+
+fn in_scene(scene: &str, err: String) -> io::Error {
+    io::Error::other(format!("{}: {}", scene, err))
+}
+
+/// The scene's node tree as it stands after the edits so far.
+fn tree(lines: &[String]) -> Result<Node, String> {
+    scene::parse(lines.join("\n").as_bytes(), None).map_err(|err| err.to_string())
+}
+
+/// The path of the node a node path from the root leads to, as `Node::paths`
+/// spells them and the headers of the scene name nodes.
+fn find(lines: &[String], path: &str) -> Result<String, String> {
+    tree(lines)?.resolve(".", path).ok_or_else(|| format!("no node at {}", path))
+}
+
+/// A node path from the root as the headers would spell it, or as it is when
+/// it leads nowhere, as the paths of connections left behind by a removed
+/// node do.
+fn canonical(lines: &[String], path: &str) -> String {
+    tree(lines).ok().and_then(|root| root.resolve(".", path)).unwrap_or(path.to_string())
+}
+
+/// The path of the node a `[node ...]` header declares, as `Node::paths` spells it.
+fn header_path(caps: &regex::Captures) -> String {
+    match caps.name("parent").map(|p| p.as_str()) {
+        None => ".".to_string(),
+        Some(".") => caps["name"].to_string(),
+        Some(parent) => format!("{}/{}", parent, &caps["name"]),
+    }
+}
+
+fn node_re() -> Regex {
+    Regex::new(r#"^\[node name="(?P<name>[^"]+)"(?:.*?\bparent="(?P<parent>[^"]+)")?.*\]$"#).unwrap()
+}
+
+/// Renames the node at `node`, and rewrites the paths that pass through it.
+/// Returns how many lines changed.
+fn rename(lines: &mut [String], node: &str, to: &str) -> Result<usize, String> {
+    if to.is_empty() || to.contains(['/', ':', '.', '@', '%', '"']) {
+        return Err(format!("{} is not a valid node name", to));
+    }
+    let root = tree(lines)?;
+    let node = &root.resolve(".", node).ok_or_else(|| format!("no node at {}", node))?;
+    let node_re = node_re();
+    let header = lines
+        .iter()
+        .position(|line| node_re.captures(line).is_some_and(|caps| header_path(&caps) == *node))
+        .ok_or_else(|| format!("no node at {}", node))?;
+    let caps = node_re.captures(&lines[header]).unwrap();
+    let old_name = caps["name"].to_string();
+    let parent = caps.name("parent").map(|p| p.as_str().to_string());
+    let sibling = match parent.as_deref() {
+        None => None,
+        Some(".") => Some(to.to_string()),
+        Some(parent) => Some(format!("{}/{}", parent, to)),
+    };
+    if sibling.as_ref().is_some_and(|path| lines.iter().any(|line| node_re.captures(line).is_some_and(|caps| header_path(&caps) == *path))) {
+        return Err(format!("{} already has a child named {}", parent.unwrap(), to));
+    }
+    let rename_header = |lines: &mut [String]| lines[header] = lines[header].replacen(&format!("name=\"{}\"", old_name), &format!("name=\"{}\"", to), 1);
+    let mut count = 1;
+    // the root's children name it ".", so only other nodes' paths need rewriting
+    let Some(new_path) = sibling else {
+        rename_header(lines);
+        return Ok(count);
+    };
+    let path_attr = Regex::new(r#"\b(?P<attr>parent|from|to|path)="(?P<path>[^"]+)""#).unwrap();
+    let node_path = Regex::new(r#"\bNodePath\("(?P<path>[^"]*)"\)"#).unwrap();
+    let bases = sub_resource_bases(lines, &root);
+    // the node the paths in the lines are relative to, before any renaming
+    let mut owner: Option<String> = None;
+    for line in lines.iter_mut() {
+        let rewritten = match line.starts_with('[') {
+            true => {
+                owner = match node_re.captures(line) {
+                    Some(caps) => Some(header_path(&caps)),
+                    None if line.starts_with("[sub_resource ") => header_attr(line, "id").and_then(|id| bases.get(&id).cloned()),
+                    None => None,
+                };
+                if !(line.starts_with("[node ") || line.starts_with("[connection ") || line.starts_with("[editable ")) {
+                    continue;
+                }
+                path_attr.replace_all(line, |caps: &regex::Captures| {
+                    let path = &caps["path"];
+                    let moved = match path.strip_prefix(node.as_str()) {
+                        Some("") => new_path.clone(),
+                        Some(rest) if rest.starts_with('/') => format!("{}{}", new_path, rest),
+                        _ => return caps[0].to_string(),
+                    };
+                    format!("{}=\"{}\"", &caps["attr"], moved)
+                })
+            }
+            false => {
+                let Some(owner) = &owner else {
+                    continue;
+                };
+                node_path.replace_all(line, |caps: &regex::Captures| format!("NodePath(\"{}\")", renamed_path(&root, owner, &caps["path"], node, to)))
+            }
+        };
+        if rewritten != line.as_str() {
+            *line = rewritten.to_string();
+            count += 1;
+        }
+    }
+    rename_header(lines);
+    Ok(count)
+}
+
+/// What refers to a sub_resource first: a node, by its path, or another
+/// sub_resource, by its id.
+#[derive(Clone)]
+enum User {
+    Node(String),
+    SubResource(String),
+}
+
+/// The node each sub_resource's node paths are relative to, by id: the node
+/// that uses it, directly or through other sub_resources, or for the
+/// animations in an AnimationPlayer's libraries, the node its `root_node`
+/// leads to. Sub_resources no node uses are left out.
+fn sub_resource_bases(lines: &[String], root: &Node) -> HashMap<String, String> {
+    let node_re = node_re();
+    let reference = Regex::new(r#"\bSubResource\("(?P<id>[^"]+)"\)"#).unwrap();
+    let node_path = Regex::new(r#"\bNodePath\("(?P<path>[^"]*)"\)"#).unwrap();
+    let mut users: HashMap<String, User> = HashMap::new();
+    let mut user = None;
+    for line in lines {
+        if line.starts_with('[') {
+            user = match node_re.captures(line) {
+                Some(caps) => Some(User::Node(header_path(&caps))),
+                None if line.starts_with("[sub_resource ") => header_attr(line, "id").map(User::SubResource),
+                None => None,
+            };
+            continue;
+        }
+        let Some(user) = &user else {
+            continue;
+        };
+        for caps in reference.captures_iter(line) {
+            users.entry(caps["id"].to_string()).or_insert_with(|| user.clone());
+        }
+    }
+    let mut bases = HashMap::new();
+    for id in users.keys() {
+        let mut user = &users[id];
+        // a chain this long must loop back on itself
+        for _ in 0..32 {
+            match user {
+                User::SubResource(id) => match users.get(id) {
+                    Some(next) => user = next,
+                    None => break,
+                },
+                User::Node(path) => {
+                    let Some(node) = root.find(path) else {
+                        break;
+                    };
+                    let base = match node.parameters.iter().find(|param| param.key == "root_node") {
+                        Some(param) => node_path.captures(&param.val).and_then(|caps| root.resolve(path, &caps["path"])),
+                        None if matches!(node._type.as_str(), "AnimationPlayer" | "AnimationMixer" | "AnimationTree") => root.resolve(path, ".."),
+                        None => Some(path.clone()),
+                    };
+                    if let Some(base) = base {
+                        bases.insert(id.clone(), base);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+    bases
+}
+
+/// A node path written in the node at `owner` with the segment that steps into
+/// the node at `node` naming it `to`, so that it still leads where it did once
+/// that node is renamed. Absolute paths lead outside the scene and are left be.
+fn renamed_path(root: &Node, owner: &str, path: &str, node: &str, to: &str) -> String {
+    let (nodes, property) = match path.split_once(':') {
+        Some((nodes, property)) => (nodes, Some(property)),
+        None => (path, None),
+    };
+    if nodes.starts_with('/') {
+        return path.to_string();
+    }
+    let mut at = owner.to_string();
+    let mut segments = Vec::new();
+    let mut renamed = false;
+    for name in nodes.split('/') {
+        at = match name {
+            "" | "." => at,
+            ".." => match at.rsplit_once('/') {
+                Some((parent, _)) => parent.to_string(),
+                None if at != "." => ".".to_string(),
+                None => return path.to_string(),
+            },
+            name if name.starts_with('%') => match root.resolve(".", name) {
+                Some(unique) => unique,
+                None => return path.to_string(),
+            },
+            name if at == "." => name.to_string(),
+            name => format!("{}/{}", at, name),
+        };
+        let steps_in = at == node && !matches!(name, "" | "." | "..");
+        renamed |= steps_in;
+        segments.push(match (steps_in, name.starts_with('%')) {
+            (true, true) => format!("%{}", to),
+            (true, false) => to.to_string(),
+            (false, _) => name.to_string(),
+        });
+    }
+    match (renamed, property) {
+        (false, _) => path.to_string(),
+        (true, Some(property)) => format!("{}:{}", segments.join("/"), property),
+        (true, None) => segments.join("/"),
+    }
+}
+
+/// Removes the node at `node` and its descendants, each with the lines of its
+/// section, along with the connections to or from any of them and their
+/// `[editable]` paths. Returns how many nodes went.
+fn delete(lines: &mut Vec<String>, node: &str) -> Result<usize, String> {
+    let node = find(lines, node)?;
+    if node == "." {
+        return Err("the root can't be deleted".to_string());
+    }
+    let under = |path: Option<String>| path.is_some_and(|path| path == node || path.starts_with(&format!("{}/", node)));
+    let node_re = node_re();
+    let mut count = 0;
+    let mut dropping = false;
+    lines.retain(|line| {
+        if line.starts_with('[') {
+            dropping = match node_re.captures(line) {
+                Some(caps) => {
+                    let gone = under(Some(header_path(&caps)));
+                    count += usize::from(gone);
+                    gone
+                }
+                None if line.starts_with("[connection ") => under(header_attr(line, "from")) || under(header_attr(line, "to")),
+                None if line.starts_with("[editable ") => under(header_attr(line, "path")),
+                None => false,
+            };
+        }
+        !dropping
+    });
+    while lines.last().is_some_and(|line| line.trim().is_empty()) {
+        lines.pop();
+    }
+    Ok(count)
+}
+
+/// Points the ext_resources at `from`, a res:// path or the uid:// of one, to
+/// `to`, returning how many there were.
+fn repath(lines: &mut [String], from: &str, to: &str) -> usize {
+    let mut count = 0;
+    for line in lines.iter_mut().filter(|line| line.starts_with("[ext_resource ")) {
+        let Some(old) = header_attr(line, "path") else {
+            continue;
+        };
+        if old == from || header_attr(line, "uid").as_deref() == Some(from) {
+            *line = line.replacen(&format!("path=\"{}\"", old), &format!("path=\"{}\"", to), 1);
+            count += 1;
+        }
+    }
+    count
+}
+
+/// Sets a property of the node at `node`, replacing its value if it has one and
+/// adding it after the node's other properties otherwise.
+fn set(lines: &mut Vec<String>, node: &str, property: &str, value: &str) -> Result<(), String> {
+    let node = &find(lines, node)?;
+    let node_re = node_re();
+    let header = lines
+        .iter()
+        .position(|line| node_re.captures(line).is_some_and(|caps| header_path(&caps) == *node))
+        .ok_or_else(|| format!("no node at {}", node))?;
+    let end = (header + 1..lines.len()).find(|&i| lines[i].starts_with('[')).unwrap_or(lines.len());
+    let prefix = format!("{} = ", property);
+    let line = format!("{}{}", prefix, value);
+    let mut i = header + 1;
+    while i < end {
+        if lines[i].starts_with(&prefix) {
+            // a value may run over several lines, like a multi-line dictionary
+            let last = value_end(lines, i);
+            lines.splice(i..=last, [line]);
+            return Ok(());
+        }
+        i += 1;
+    }
+    // after the last non-blank line of the section
+    let at = (header + 1..end).rev().find(|&i| !lines[i].trim().is_empty()).map_or(header + 1, |i| value_end(lines, i) + 1);
+    lines.insert(at, line);
+    Ok(())
+}
+
+/// Which connections an operation acts on: those of a signal of a node, and
+/// optionally only those to a given node or method.
+struct Wanted<'a> {
+    signal: &'a str,
+    from: &'a str,
+    to: Option<&'a str>,
+    method: Option<&'a str>,
+}
+
+impl Wanted<'_> {
+    fn matches(&self, line: &str) -> bool {
+        if !line.starts_with("[connection ") {
+            return false;
+        }
+        let attr = |key: &str| header_attr(line, key);
+        attr("signal").as_deref() == Some(self.signal)
+            && attr("from").as_deref() == Some(self.from)
+            && self.to.is_none_or(|to| attr("to").as_deref() == Some(to))
+            && self.method.is_none_or(|method| attr("method").as_deref() == Some(method))
+    }
+}
+
+/// The value of a section header's `key="..."`, found without a regex as it is
+/// called for header after header of a scene.
+fn header_attr(line: &str, key: &str) -> Option<String> {
+    let prefix = format!("{}=\"", key);
+    let (at, _) = line.match_indices(&prefix).find(|(at, _)| !line[..*at].ends_with(|c: char| c.is_alphanumeric() || c == '_'))?;
+    let value = &line[at + prefix.len()..];
+    value.find('"').map(|end| value[..end].to_string())
+}
+
+/// Adds a connection after the scene's others, returning false when it was
+/// already there.
+fn connect(lines: &mut Vec<String>, signal: &str, from: &str, to: &str, method: &str) -> Result<bool, String> {
+    let (from, to) = (&find(lines, from)?, &find(lines, to)?);
+    let wanted = Wanted { signal, from, to: Some(to), method: Some(method) };
+    if lines.iter().any(|line| wanted.matches(line)) {
+        return Ok(false);
+    }
+    let line = format!("[connection signal=\"{}\" from=\"{}\" to=\"{}\" method=\"{}\"]", signal, from, to, method);
+    match lines.iter().rposition(|line| line.starts_with("[connection ")) {
+        Some(last) => lines.insert(last + 1, line),
+        None => {
+            while lines.last().is_some_and(|line| line.trim().is_empty()) {
+                lines.pop();
+            }
+            lines.push(String::new());
+            lines.push(line);
+        }
+    }
+    Ok(true)
+}
+
+/// Points the wanted connections at another node or method, returning how many
+/// there were.
+fn retarget(lines: &mut [String], wanted: &Wanted, new_to: Option<&str>, new_method: Option<&str>) -> Result<usize, String> {
+    if new_to.is_none() && new_method.is_none() {
+        return Err("retargeting needs a new node or method".to_string());
+    }
+    let new_to = match new_to {
+        Some(to) => Some(find(lines, to)?),
+        None => None,
+    };
+    let new_to = new_to.as_deref();
+    let mut count = 0;
+    for line in lines.iter_mut().filter(|line| wanted.matches(line)) {
+        for (key, value) in [("to", new_to), ("method", new_method)] {
+            if let (Some(value), Some(old)) = (value, header_attr(line, key)) {
+                *line = line.replacen(&format!("{}=\"{}\"", key, old), &format!("{}=\"{}\"", key, value), 1);
+            }
+        }
+        count += 1;
+    }
+    match count {
+        0 => Err(format!("no connection of {}:{}() to retarget", wanted.from, wanted.signal)),
+        count => Ok(count),
+    }
+}
+
+/// The last line of the value starting on line `start`: the line its brackets
+/// are balanced by.
+fn value_end(lines: &[String], start: usize) -> usize {
+    let mut depth = 0i32;
+    let mut in_string = false;
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let mut escaped = false;
+        for c in line.chars() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_string => escaped = true,
+                '"' => in_string = !in_string,
+                '(' | '[' | '{' if !in_string => depth += 1,
+                ')' | ']' | '}' if !in_string => depth -= 1,
+                _ => (),
+            }
+        }
+        if depth <= 0 && !in_string {
+            return i;
+        }
+    }
+    lines.len() - 1
+}
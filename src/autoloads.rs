@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+
+use regex::Regex;
+
+use crate::project::Project;
+use crate::scene;
+
+/// One place a scene touches an autoload signal: the scene, the node whose
+/// script does it, the script itself, and whether it connects or emits.
+struct Usage {
+    scene: String,
+    node: String,
+    script: String,
+    emits: bool,
+}
+
+/// Prints every signal of every autoload singleton together with the scenes
+/// whose attached scripts connect to or emit it.
+pub fn report(project: &Project) -> io::Result<()> {
+    let autoloads = project.settings("autoload")?;
+    if autoloads.is_empty() {
+        println!("no autoloads are configured in {}", project.root.join("project.godot").display());
+        return Ok(());
+    }
+
+    let signal_re = Regex::new(r"(?m)^\s*signal\s+(?P<name>\w+)").unwrap();
+    let mut usages: BTreeMap<&str, BTreeMap<String, Vec<Usage>>> = BTreeMap::new();
+    let mut patterns = Vec::new();
+    for (name, path) in autoloads.iter() {
+        let signals = usages.entry(name.as_str()).or_default();
+        let path = path.trim_start_matches('*');
+        if let Ok(source) = fs::read_to_string(project.resolve(path)) {
+            for caps in signal_re.captures_iter(&source) {
+                signals.entry(caps["name"].to_string()).or_default();
+            }
+        }
+        let name = regex::escape(name);
+        patterns.push((
+            // Events.died.connect(...) / Events.died.emit(...)
+            Regex::new(&format!(r"\b{}\.(?P<signal>\w+)\.(?P<call>connect|emit)\(", name)).unwrap(),
+            // Events.connect("died", ...) / Events.emit_signal("died")
+            Regex::new(&format!(r#"\b{}\.(?P<call>connect|emit_signal)\(\s*&?"(?P<signal>\w+)""#, name)).unwrap(),
+        ));
+    }
+
+    let mut scripts: HashMap<String, String> = HashMap::new();
+    for file in project.files(&["tscn"]) {
+        let root = scene::parse(BufReader::new(File::open(&file)?), Some(project))?;
+        let scene_path = project.res_path(&file).unwrap_or_else(|| file.display().to_string());
+        for (node_path, node) in root.paths() {
+            let Some(script) = node.script() else {
+                continue;
+            };
+            let source = scripts
+                .entry(script.path.clone())
+                .or_insert_with(|| fs::read_to_string(project.resolve(&script.path)).unwrap_or_default());
+            for ((name, _), (member_re, string_re)) in autoloads.iter().zip(patterns.iter()) {
+                for caps in member_re.captures_iter(source).chain(string_re.captures_iter(source)) {
+                    usages.get_mut(name.as_str()).unwrap().entry(caps["signal"].to_string()).or_default().push(Usage {
+                        scene: scene_path.clone(),
+                        node: node_path.clone(),
+                        script: script.path.clone(),
+                        emits: caps["call"].starts_with("emit"),
+                    });
+                }
+            }
+        }
+    }
+
+    for (name, path) in autoloads.iter() {
+        println!("{} ({})", name, path.trim_start_matches('*'));
+        let signals = &usages[name.as_str()];
+        let mut index = signals.len();
+        for (signal, found) in signals.iter() {
+            index -= 1;
+            let (branch, prefix) = if index == 0 { ("└──", "    ") } else { ("├──", "│   ") };
+            println!("{} {}", branch, signal);
+            if found.is_empty() {
+                println!("{}└── unused", prefix);
+            }
+            let mut sub_index = found.len();
+            for usage in found.iter() {
+                sub_index -= 1;
+                println!(
+                    "{}{} {} in {} at {} ({})",
+                    prefix,
+                    if sub_index == 0 { "└──" } else { "├──" },
+                    if usage.emits { "emitted" } else { "connected" },
+                    usage.scene,
+                    usage.node,
+                    usage.script,
+                );
+            }
+        }
+    }
+    Ok(())
+}
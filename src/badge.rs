@@ -0,0 +1,91 @@
+use serde_json::{json, Value};
+
+use crate::lint::Finding;
+use crate::report;
+use crate::scene::Node;
+
+/// Complexity scores at which a scene counts as medium and as high.
+const MEDIUM: usize = 100;
+const HIGH: usize = 300;
+
+/// A scene's health at a glance: its size, how it fares against the lint rules,
+/// and how complex it is.
+pub struct Health {
+    pub scene: String,
+    pub nodes: usize,
+    pub connections: usize,
+    pub depth: usize,
+    /// How many lint rules ran, and what they found.
+    pub rules: usize,
+    pub findings: Vec<Finding>,
+}
+
+impl Health {
+    pub fn new(scene: String, root: &Node, rules: usize, findings: Vec<Finding>) -> Self {
+        let nodes = root.paths();
+        Self {
+            scene,
+            connections: nodes.iter().map(|(_, node)| node.connections.len()).sum(),
+            nodes: nodes.len(),
+            depth: report::depth(root),
+            rules,
+            findings,
+        }
+    }
+
+    /// Nodes, plus two for each connection and five for each level of nesting.
+    pub fn complexity(&self) -> usize {
+        self.nodes + 2 * self.connections + 5 * self.depth
+    }
+
+    pub fn grade(&self) -> &'static str {
+        match self.complexity() {
+            score if score >= HIGH => "high",
+            score if score >= MEDIUM => "medium",
+            _ => "low",
+        }
+    }
+
+    fn lint_status(&self) -> String {
+        match (self.rules, self.findings.len()) {
+            (0, _) => "no rules".to_string(),
+            (_, 0) => "passing".to_string(),
+            (_, 1) => "1 problem".to_string(),
+            (_, problems) => format!("{} problems", problems),
+        }
+    }
+
+    /// The summary as a shields.io endpoint badge.
+    pub fn shields(&self) -> Value {
+        let color = match (self.findings.is_empty(), self.grade()) {
+            (false, _) => "red",
+            (true, "high") => "yellow",
+            (true, _) => "brightgreen",
+        };
+        json!({
+            "schemaVersion": 1,
+            "label": self.scene.rsplit('/').next().unwrap_or(&self.scene),
+            "message": format!("{} nodes | lint {} | complexity {}", self.nodes, self.lint_status(), self.grade()),
+            "color": color,
+        })
+    }
+
+    /// The summary with every measure spelled out.
+    pub fn summary(&self) -> Value {
+        json!({
+            "scene": self.scene,
+            "nodes": self.nodes,
+            "connections": self.connections,
+            "depth": self.depth,
+            "lint": {
+                "rules": self.rules,
+                "problems": self.findings.len(),
+                "status": self.lint_status(),
+            },
+            "complexity": {
+                "score": self.complexity(),
+                "grade": self.grade(),
+            },
+        })
+    }
+}
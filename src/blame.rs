@@ -0,0 +1,57 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::git::{self, Revision};
+use crate::scene;
+use crate::units;
+
+/// Walks the git history of a scene, parsing each revision, and prints the
+/// commit that added a node and the commits that last changed each of its
+/// current property values. Values not yet committed are marked as such.
+///
+/// The node's history stops at the last revision it was missing from, so a
+/// node that was deleted and added back counts as added the second time.
+pub fn report(file: &Path, node_path: &str) -> io::Result<()> {
+    let current = scene::parse(io::BufReader::new(fs::File::open(file)?), None)?;
+    let Some(node) = current.find(node_path) else {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("{} has no node {}", file.display(), node_path)));
+    };
+    let revisions = git::history(file)?;
+
+    // newest first: each property keeps the oldest revision of the run of
+    // revisions, back from now, that have its current value
+    let mut added: Option<&Revision> = None;
+    let mut changed: Vec<(Option<&Revision>, bool)> = vec![(None, false); node.parameters.len()];
+    for revision in revisions.iter() {
+        let text = revision.contents(git::dir(file))?;
+        let Some(old) = scene::parse(text.as_bytes(), None).ok().and_then(|root| root.find(node_path).cloned()) else {
+            break;
+        };
+        added = Some(revision);
+        for (param, (since, done)) in node.parameters.iter().zip(changed.iter_mut()) {
+            if *done {
+                continue;
+            }
+            match old.parameters.iter().find(|p| p.key == param.key) {
+                Some(p) if p.val == param.val => *since = Some(revision),
+                _ => *done = true,
+            }
+        }
+    }
+
+    let describe = |revision: Option<&Revision>| match revision {
+        Some(r) => format!("{}  {}  {}  {}", r.short(), r.date, r.author, r.subject),
+        None => "not committed yet".to_string(),
+    };
+    let label = match node._type.is_empty() {
+        true => node_path.to_string(),
+        false => format!("{} ({})", node_path, node._type),
+    };
+    println!("{}", label);
+    println!("  added: {}", describe(added));
+    for (param, (since, _)) in node.parameters.iter().zip(changed.iter()) {
+        println!("  {} = {}: {}", param.key, units::one_line(&param.val), describe(*since));
+    }
+    Ok(())
+}
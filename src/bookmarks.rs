@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::project::Project;
+
+/// Named node paths saved per scene, kept in `.gdtree/bookmarks.toml` under the
+/// project root so that gdtree can rewrite it without touching the hand-edited
+/// config.
+#[derive(Debug, Default, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Bookmarks {
+    /// Bookmark names and node paths, keyed by the scene's res:// path.
+    scenes: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+fn file(project: &Project) -> PathBuf {
+    project.root.join(".gdtree/bookmarks.toml")
+}
+
+/// The key a scene's bookmarks are filed under: its res:// path.
+pub fn scene_key(project: &Project, scene: &Path) -> String {
+    let scene = fs::canonicalize(scene).unwrap_or(scene.to_path_buf());
+    project.res_path(&scene).unwrap_or_else(|| scene.display().to_string())
+}
+
+impl Bookmarks {
+    /// Loads the project's bookmarks; a project without any gets none.
+    pub fn load(project: &Project) -> io::Result<Self> {
+        let path = file(project);
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        toml::from_str(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))
+    }
+
+    pub fn save(&self, project: &Project) -> io::Result<()> {
+        let path = file(project);
+        fs::create_dir_all(path.parent().unwrap())?;
+        let text = toml::to_string(self).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+        fs::write(path, text)
+    }
+
+    /// The node path a scene's bookmark points at.
+    pub fn get(&self, scene: &str, name: &str) -> Option<&str> {
+        self.scenes.get(scene)?.get(name).map(|path| path.as_str())
+    }
+
+    /// Saves a bookmark, replacing any of the same name.
+    pub fn add(&mut self, scene: &str, name: &str, path: &str) {
+        self.scenes.entry(scene.to_string()).or_default().insert(name.to_string(), path.to_string());
+    }
+
+    /// Deletes a bookmark, returning whether there was one.
+    pub fn remove(&mut self, scene: &str, name: &str) -> bool {
+        let Some(marks) = self.scenes.get_mut(scene) else {
+            return false;
+        };
+        let removed = marks.remove(name).is_some();
+        if marks.is_empty() {
+            self.scenes.remove(scene);
+        }
+        removed
+    }
+
+    /// Lists the bookmarks as (scene, name, node path), optionally of one scene only.
+    pub fn list(&self, scene: Option<&str>) -> Vec<(&str, &str, &str)> {
+        self.scenes
+            .iter()
+            .filter(|(key, _)| scene.is_none_or(|s| s == key.as_str()))
+            .flat_map(|(key, marks)| marks.iter().map(move |(name, path)| (key.as_str(), name.as_str(), path.as_str())))
+            .collect()
+    }
+}
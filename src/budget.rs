@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::report;
+use crate::scene::Scene;
+use crate::units;
+
+/// Limits on how big a scene may grow, checked by `gdtree lint`. Unset limits
+/// aren't checked.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Budget {
+    pub max_nodes: Option<usize>,
+    pub max_depth: Option<usize>,
+    /// In bytes.
+    pub max_file_size: Option<u64>,
+    /// How many nodes may be instances of other scenes.
+    pub max_instances: Option<usize>,
+}
+
+impl Budget {
+    pub fn is_empty(&self) -> bool {
+        self.max_nodes.is_none() && self.max_depth.is_none() && self.max_file_size.is_none() && self.max_instances.is_none()
+    }
+
+    /// This budget with the limits `other` sets taking the place of its own.
+    fn overridden_by(&self, other: &Budget) -> Budget {
+        Budget {
+            max_nodes: other.max_nodes.or(self.max_nodes),
+            max_depth: other.max_depth.or(self.max_depth),
+            max_file_size: other.max_file_size.or(self.max_file_size),
+            max_instances: other.max_instances.or(self.max_instances),
+        }
+    }
+
+    /// Describes each limit the scene is over.
+    pub fn check(&self, scene: &Scene) -> Vec<String> {
+        let nodes = scene.root.paths();
+        let instances = nodes.iter().filter(|(_, node)| node.instance.is_some()).count();
+        let depth = report::depth(&scene.root);
+        let mut over = Vec::new();
+        for (measure, value, limit) in [
+            ("nodes", nodes.len(), self.max_nodes),
+            ("levels deep", depth, self.max_depth),
+            ("instanced scenes", instances, self.max_instances),
+        ] {
+            if let Some(limit) = limit.filter(|limit| value > *limit) {
+                over.push(format!("{} {}, over the budget of {}", value, measure, limit));
+            }
+        }
+        if let Some(limit) = self.max_file_size.filter(|limit| scene.size > *limit) {
+            over.push(format!("{} file, over the budget of {}", units::bytes(scene.size), units::bytes(limit)));
+        }
+        over
+    }
+}
+
+/// The project's default budget, and the budgets of scenes allowed more or less.
+#[derive(Debug, Default, Clone)]
+pub struct Budgets {
+    pub default: Budget,
+    /// Keyed by res:// path.
+    pub scenes: BTreeMap<String, Budget>,
+}
+
+impl Budgets {
+    pub fn is_empty(&self) -> bool {
+        self.default.is_empty() && self.scenes.values().all(|budget| budget.is_empty())
+    }
+
+    /// The budget a scene is held to.
+    pub fn for_scene(&self, scene: &str) -> Budget {
+        match self.scenes.get(scene) {
+            Some(budget) => self.default.overridden_by(budget),
+            None => self.default.clone(),
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+
+use crate::parser::{self, Statement};
+use crate::project::Project;
+use crate::scene;
+
+/// Cross-checks the `bus` of the project's AudioStreamPlayer, AudioStreamPlayer2D
+/// and AudioStreamPlayer3D nodes against the buses of its default bus layout,
+/// printing the buses used but never defined and those defined but never used.
+/// Returns how many problems there were.
+///
+/// Players that leave `bus` unset play on Master, which every layout has.
+pub fn report(project: &Project) -> io::Result<usize> {
+    let (layout, defined) = defined(project)?;
+
+    let mut used: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let mut players = 0;
+    for file in project.files(&["tscn"]) {
+        let root = scene::parse(BufReader::new(File::open(&file)?), Some(project))?;
+        let name = project.res_path(&file).unwrap_or_else(|| file.display().to_string());
+        for (path, node) in root.paths() {
+            if !node._type.starts_with("AudioStreamPlayer") {
+                continue;
+            }
+            players += 1;
+            let bus = match node.parameters.iter().find(|p| p.key == "bus") {
+                Some(param) => param.val.trim_start_matches(['&', '^']).trim_matches('"').to_string(),
+                None => "Master".to_string(),
+            };
+            used.entry(bus).or_default().push(format!("{}:{}", name, path));
+        }
+    }
+
+    let undefined: Vec<(&String, &Vec<String>)> = used.iter().filter(|(bus, _)| !defined.contains(bus)).collect();
+    let unused: Vec<&String> = defined.iter().filter(|bus| *bus != "Master" && !used.contains_key(*bus)).collect();
+    if !undefined.is_empty() {
+        println!("unknown buses:");
+        for (bus, nodes) in undefined.iter() {
+            println!("  {}: {}", bus, nodes.join(", "));
+        }
+    }
+    if !unused.is_empty() {
+        println!("unused buses:");
+        for bus in unused.iter() {
+            println!("  {}", bus);
+        }
+    }
+    println!(
+        "{} buses in {}, {} used by {} players; {} unknown, {} unused",
+        defined.len(),
+        layout,
+        used.len(),
+        players,
+        undefined.len(),
+        unused.len()
+    );
+    Ok(undefined.len() + unused.len())
+}
+
+/// The bus layout `project.godot` names, or Godot's default when it names
+/// none, and the names of its buses. A project that never saved a layout has
+/// only Master.
+fn defined(project: &Project) -> io::Result<(String, Vec<String>)> {
+    // audio/default_bus_layout in Godot 3, audio/buses/default_bus_layout in Godot 4
+    let layout = project
+        .settings("audio")?
+        .into_iter()
+        .find(|(key, _)| key == "default_bus_layout" || key == "buses/default_bus_layout")
+        .map_or("res://default_bus_layout.tres".to_string(), |(_, path)| path);
+    let path = project.resolve(&layout);
+    let Ok(text) = fs::read_to_string(&path) else {
+        return Ok((layout, vec!["Master".to_string()]));
+    };
+    let statements = parser::parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))?;
+    let mut buses = Vec::new();
+    for statement in statements.iter() {
+        // bus/1/name = &"SFX"
+        if let Statement::Assignment { key, value } = statement {
+            if key.starts_with("bus/") && key.ends_with("/name") {
+                buses.extend(value.as_str().map(|name| name.to_string()));
+            }
+        }
+    }
+    if !buses.iter().any(|bus| bus == "Master") {
+        buses.insert(0, "Master".to_string());
+    }
+    Ok((layout, buses))
+}
@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::parser::{self, Statement, Value};
+use crate::{project, scene};
+
+/// The sections Godot writes into scenes and resources.
+const SECTIONS: &[&str] = &["gd_scene", "gd_resource", "ext_resource", "sub_resource", "node", "resource", "connection", "editable"];
+
+/// The attributes a section cannot do without.
+const REQUIRED: &[(&str, &[&str])] = &[
+    ("ext_resource", &["id", "path", "type"]),
+    ("sub_resource", &["id", "type"]),
+    ("node", &["name"]),
+    ("connection", &["signal", "from", "to", "method"]),
+    ("editable", &["path"]),
+];
+
+/// A problem found in a scene, at a line of its file.
+struct Problem {
+    line: usize,
+    message: String,
+}
+
+/// Checks that the scenes at `path`, a file or a directory, are well formed:
+/// that they parse, that each section has the attributes it needs, that every
+/// ExtResource and SubResource refers to one declared above it, that each
+/// node's parent exists and that no two siblings share a name. Prints a line
+/// per problem, as `file:line: message`, and returns how many were found. A
+/// file that can't be read counts as a problem, and the others are still
+/// checked.
+pub fn run(path: &Path) -> usize {
+    let files = match path.is_dir() {
+        true => project::list_files(path, &["tscn", "tres"]),
+        false => vec![path.to_path_buf()],
+    };
+    let mut total = 0;
+    let mut failed = 0;
+    for file in files.iter() {
+        let problems = match fs::read_to_string(file) {
+            Ok(text) => check(&text),
+            Err(err) => {
+                println!("{}: could not be read: {}", file.display(), err);
+                total += 1;
+                failed += 1;
+                continue;
+            }
+        };
+        for problem in problems.iter() {
+            println!("{}:{}: {}", file.display(), problem.line, problem.message);
+        }
+        total += problems.len();
+        failed += usize::from(!problems.is_empty());
+    }
+    println!("{} problems in {} of {} files", total, failed, files.len());
+    total
+}
+
+fn check(text: &str) -> Vec<Problem> {
+    let statements = match parser::parse(text) {
+        Ok(statements) => statements,
+        Err(err) => return vec![Problem { line: err.line, message: format!("malformed: {}", err.message) }],
+    };
+    let line = |offset: usize| text[..offset].matches('\n').count() + 1;
+    let mut problems = Vec::new();
+    let mut ext_ids = HashSet::new();
+    let mut sub_ids = HashSet::new();
+    // the paths of the nodes so far, as parents name them
+    let mut nodes = HashSet::new();
+    let mut root: Option<String> = None;
+    // nodes that bring in a scene, whose nodes this one may add to or change
+    let mut instanced = HashSet::new();
+    for statement in statements.iter() {
+        let values: Vec<&Value> = match statement {
+            Statement::Section { attrs, .. } => attrs.iter().map(|(_, v)| v).collect(),
+            Statement::Assignment { value, .. } => vec![value],
+        };
+        // a sub_resource may not refer to itself, so references are checked
+        // before the section's own id is declared
+        for value in values.iter().flat_map(|v| v.references()) {
+            let (kind, id) = value.reference().unwrap();
+            let declared = match kind {
+                "ExtResource" => &ext_ids,
+                _ => &sub_ids,
+            };
+            if !declared.contains(id) {
+                problems.push(Problem { line: line(value.start), message: format!("{}(\"{}\") refers to no {} declared above it", kind, id, section_of(kind)) });
+            }
+        }
+        let Statement::Section { tag, start, .. } = statement else {
+            continue;
+        };
+        let at = line(*start);
+        let attr = |key: &str| statement.attr(key).and_then(|v| v.as_str());
+        if !SECTIONS.contains(&tag.as_str()) {
+            problems.push(Problem { line: at, message: format!("malformed: unknown section [{}]", tag) });
+            continue;
+        }
+        let required = REQUIRED.iter().find(|(t, _)| t == tag).map_or(&[][..], |(_, keys)| *keys);
+        let missing: Vec<&str> = required.iter().copied().filter(|key| attr(key).is_none()).collect();
+        if !missing.is_empty() {
+            problems.push(Problem { line: at, message: format!("malformed: [{}] lacks {}", tag, missing.join(", ")) });
+            continue;
+        }
+        match tag.as_str() {
+            "ext_resource" | "sub_resource" => {
+                let (ids, kind) = match tag.as_str() {
+                    "ext_resource" => (&mut ext_ids, "ExtResource"),
+                    _ => (&mut sub_ids, "SubResource"),
+                };
+                let id = attr("id").unwrap().to_string();
+                if !ids.insert(id.clone()) {
+                    problems.push(Problem { line: at, message: format!("{} id \"{}\" is declared twice", kind, id) });
+                }
+            }
+            "node" => {
+                let name = attr("name").unwrap();
+                let path = match attr("parent") {
+                    None => match &root {
+                        Some(root) => {
+                            problems.push(Problem { line: at, message: format!("node {} has no parent, but {} is already the root", name, root) });
+                            continue;
+                        }
+                        None => {
+                            root = Some(name.to_string());
+                            ".".to_string()
+                        }
+                    },
+                    Some(parent) if parent != "." && !nodes.contains(parent) && !scene::from_instance(parent, &instanced) => {
+                        problems.push(Problem { line: at, message: format!("node {} has parent {}, which is not declared above it", name, parent) });
+                        continue;
+                    }
+                    Some(".") => name.to_string(),
+                    Some(parent) => format!("{}/{}", parent, name),
+                };
+                if statement.attr("instance").is_some() {
+                    instanced.insert(path.clone());
+                }
+                if !nodes.insert(path.clone()) {
+                    problems.push(Problem { line: at, message: format!("node {} is declared twice", path) });
+                }
+            }
+            _ => (),
+        }
+    }
+    problems
+}
+
+fn section_of(kind: &str) -> &'static str {
+    match kind {
+        "ExtResource" => "ext_resource",
+        _ => "sub_resource",
+    }
+}
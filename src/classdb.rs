@@ -0,0 +1,227 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use flate2::read::GzDecoder;
+use regex::Regex;
+
+/// Where `classdb fetch` downloads engine sources from, by release tag.
+pub const SOURCE_URL: &str = "https://codeload.github.com/godotengine/godot/tar.gz/refs/tags/{tag}";
+
+/// What the class reference says about one class.
+#[derive(Debug, Default)]
+pub struct Class {
+    pub brief: Option<String>,
+    pub inherits: Option<String>,
+    /// Member properties with their engine defaults, when documented.
+    pub members: HashMap<String, Option<String>>,
+}
+
+/// Godot's class reference, read from a directory of `<Class>.xml` files: either the
+/// engine's `doc/classes` or a bundle cached by `gdtree classdb fetch`.
+pub struct ClassDb {
+    dir: PathBuf,
+    classes: RefCell<HashMap<String, Option<Rc<Class>>>>,
+}
+
+impl ClassDb {
+    pub fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            classes: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Opens the cached bundle for a Godot version, if it has been fetched.
+    pub fn cached(version: &str) -> Option<Self> {
+        let dir = cache_dir()?.join(version);
+        dir.is_dir().then(|| Self::new(dir))
+    }
+
+    pub fn class(&self, name: &str) -> Option<Rc<Class>> {
+        self.classes
+            .borrow_mut()
+            .entry(name.to_string())
+            .or_insert_with(|| {
+                let xml = fs::read_to_string(self.dir.join(format!("{}.xml", name))).ok()?;
+                Some(Rc::new(parse_class(&xml)))
+            })
+            .clone()
+    }
+
+    /// The one-line description of a class.
+    pub fn brief(&self, name: &str) -> Option<String> {
+        self.class(name)?.brief.clone()
+    }
+
+    /// The class followed by the classes it inherits from, nearest first.
+    pub fn ancestry(&self, name: &str) -> Vec<String> {
+        let mut chain = vec![name.to_string()];
+        while let Some(parent) = self.class(chain.last().unwrap()).and_then(|c| c.inherits.clone()) {
+            if chain.contains(&parent) {
+                break;
+            }
+            chain.push(parent);
+        }
+        chain
+    }
+
+    /// Looks a property up through the class hierarchy: `None` if no class in
+    /// it has the property, otherwise its documented default, if any.
+    pub fn member(&self, class: &str, property: &str) -> Option<Option<String>> {
+        self.ancestry(class)
+            .iter()
+            .find_map(|name| self.class(name)?.members.get(property).cloned())
+    }
+}
+
+fn parse_class(xml: &str) -> Class {
+    let class_re = Regex::new(r#"<class ([^>]*)>"#).unwrap();
+    let brief_re = Regex::new(r"(?s)<brief_description>(?P<text>.*?)</brief_description>").unwrap();
+    let member_re = Regex::new(r#"<member ([^>]*)>"#).unwrap();
+    let mut class = Class {
+        brief: brief_re.captures(xml).map(|caps| plain_text(&caps["text"])).filter(|t| !t.is_empty()),
+        ..Default::default()
+    };
+    if let Some(caps) = class_re.captures(xml) {
+        class.inherits = attribute(&caps[1], "inherits");
+    }
+    for caps in member_re.captures_iter(xml) {
+        if let Some(name) = attribute(&caps[1], "name") {
+            class.members.insert(name, attribute(&caps[1], "default"));
+        }
+    }
+    class
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let re = Regex::new(&format!(r#"\b{}="(?P<val>[^"]*)""#, name)).unwrap();
+    re.captures(tag).map(|caps| unescape(&caps["val"]))
+}
+
+/// Reduces a description's BBCode markup to plain text on one line.
+fn plain_text(text: &str) -> String {
+    let tag_re = Regex::new(r"\[/?(?:[a-z_]+(?:=[^\]]*)?|(?:[a-z]+ )?(?P<ref>[A-Za-z0-9_@.]+))\]").unwrap();
+    let text = tag_re.replace_all(text, |caps: &regex::Captures| {
+        caps.name("ref").map_or("", |r| r.as_str()).to_string()
+    });
+    unescape(&text.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The directory holding one fetched class reference bundle per Godot version.
+pub fn cache_dir() -> Option<PathBuf> {
+    let base = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))?;
+    Some(base.join("gdtree").join("classdb"))
+}
+
+/// Downloads the engine source release for `version` (or reads it from `source`,
+/// a URL or local `.tar.gz`), keeps its class reference XML files in the cache
+/// and returns how many classes were stored.
+pub fn fetch(version: &str, source: Option<&str>) -> io::Result<usize> {
+    let tag = match version.contains('-') {
+        true => version.to_string(),
+        false => format!("{}-stable", version),
+    };
+    let source = source.map_or_else(|| SOURCE_URL.replace("{tag}", &tag), |s| s.to_string());
+    let archive: Box<dyn Read> = match source.starts_with("http://") || source.starts_with("https://") {
+        true => Box::new(
+            ureq::get(&source)
+                .call()
+                .map_err(|err| io::Error::other(err.to_string()))?
+                .into_reader(),
+        ),
+        false => Box::new(File::open(&source)?),
+    };
+
+    let dir = cache_dir()
+        .ok_or_else(|| io::Error::other("no cache directory; set XDG_CACHE_HOME"))?
+        .join(version);
+    // unpack next to the cache and swap it in, so a failed download leaves the old bundle alone
+    let partial = dir.with_extension("partial");
+    if partial.exists() {
+        fs::remove_dir_all(&partial)?;
+    }
+    fs::create_dir_all(&partial)?;
+    let mut count = 0;
+    let mut tar = tar::Archive::new(GzDecoder::new(archive));
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.to_path_buf();
+        let in_docs = path.parent().is_some_and(|dir| {
+            let dir = dir.to_string_lossy();
+            dir.ends_with("/doc/classes") || (dir.contains("/modules/") && dir.ends_with("/doc_classes"))
+        });
+        if in_docs && path.extension().is_some_and(|e| e == "xml") {
+            entry.unpack(partial.join(path.file_name().unwrap()))?;
+            count += 1;
+        }
+    }
+    if count == 0 {
+        fs::remove_dir_all(&partial)?;
+        return Err(io::Error::other(format!("{} has no class reference files", source)));
+    }
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    fs::rename(&partial, &dir)?;
+    Ok(count)
+}
+
+/// Lists the Godot versions with a cached class reference.
+pub fn versions() -> io::Result<Vec<String>> {
+    let Some(dir) = cache_dir().filter(|d| d.is_dir()) else {
+        return Ok(Vec::new());
+    };
+    let mut versions = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if entry.path().is_dir() && !name.ends_with(".partial") {
+            versions.push(name);
+        }
+    }
+    versions.sort();
+    Ok(versions)
+}
+
+/// Deletes the cached class reference of a Godot version.
+pub fn remove(version: &str) -> io::Result<()> {
+    match cache_dir() {
+        Some(dir) => fs::remove_dir_all(dir.join(version)),
+        None => Err(io::Error::new(io::ErrorKind::NotFound, "no cache directory")),
+    }
+}
+
+/// Compares a scene value with a documented default, ignoring spacing and how
+/// numbers are written: `Vector2( 1, 0 )` equals `Vector2(1.0, 0)`.
+pub fn same_value(default: &str, value: &str) -> bool {
+    normalize(default) == normalize(value)
+}
+
+fn normalize(value: &str) -> String {
+    let number_re = Regex::new(r"-?\d+(?:\.\d*)?(?:e[-+]?\d+)?").unwrap();
+    let value: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    number_re
+        .replace_all(&value, |caps: &regex::Captures| match caps[0].parse::<f64>() {
+            Ok(n) => format!("{}", n),
+            Err(_) => caps[0].to_string(),
+        })
+        .to_string()
+}
@@ -0,0 +1,70 @@
+use std::env;
+use std::io::{self, IsTerminal};
+
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ColorChoice {
+    /// Color when printing to a terminal and NO_COLOR is unset
+    Auto,
+    /// Color even when piped
+    Always,
+    /// Never color
+    Never,
+}
+
+/// The colors of the parts of a tree line, or none at all.
+#[derive(Clone, Copy)]
+pub struct Palette {
+    enabled: bool,
+}
+
+impl Palette {
+    pub fn new(choice: ColorChoice) -> Self {
+        let enabled = match choice {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            // https://no-color.org asks for any non-empty value to turn color off
+            ColorChoice::Auto => {
+                io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none_or(|v| v.is_empty()) && env::var("TERM").ok().as_deref() != Some("dumb")
+            }
+        };
+        Self { enabled }
+    }
+
+    pub fn off() -> Self {
+        Self { enabled: false }
+    }
+
+    /// A node's name, in bold blue.
+    pub fn name(&self, text: &str) -> String {
+        self.paint("1;34", text)
+    }
+
+    /// A node's type, in cyan.
+    pub fn type_name(&self, text: &str) -> String {
+        self.paint("36", text)
+    }
+
+    /// A resource path, in green.
+    pub fn path(&self, text: &str) -> String {
+        self.paint("32", text)
+    }
+
+    /// A signal connection, in yellow.
+    pub fn connection(&self, text: &str) -> String {
+        self.paint("33", text)
+    }
+
+    /// An annotation, in magenta.
+    pub fn note(&self, text: &str) -> String {
+        self.paint("35", text)
+    }
+
+    fn paint(&self, code: &str, text: &str) -> String {
+        match self.enabled && !text.is_empty() {
+            true => format!("\x1b[{}m{}\x1b[0m", code, text),
+            false => text.to_string(),
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use clap::ValueEnum;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum CommentStyle {
+    /// `# ` before each line
+    Gdscript,
+    /// `// ` before each line
+    Rust,
+    /// One `/* ... */` block
+    C,
+}
+
+/// Wraps rendered text in comment syntax, ready to paste at the top of a source file.
+pub fn wrap(text: &str, style: CommentStyle) -> String {
+    let mut out = String::new();
+    match style {
+        CommentStyle::Gdscript | CommentStyle::Rust => {
+            let marker = match style {
+                CommentStyle::Gdscript => "#",
+                _ => "//",
+            };
+            for line in text.lines() {
+                out += &format!("{} {}\n", marker, line);
+            }
+        }
+        CommentStyle::C => {
+            out += "/*\n";
+            for line in text.lines() {
+                // a value can't be allowed to end the comment early
+                out += &format!(" * {}\n", line.replace("*/", "* /"));
+            }
+            out += " */\n";
+        }
+    }
+    out
+}
@@ -0,0 +1,56 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use crate::budget::{Budget, Budgets};
+use crate::project::Project;
+
+/// Per-project settings, read from `.gdtree/config.toml` under the project root.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Directory of Godot class reference XML files (the engine's `doc/classes`).
+    pub docs: Option<PathBuf>,
+    /// Godot version whose class reference, cached by `gdtree classdb fetch`, is used when `docs` isn't set.
+    pub godot: Option<String>,
+    /// Property renames for `gdtree migrate-properties`, on top of the built-in ones:
+    /// `old = "new"`, or `"Class.old" = "new"` for one class only.
+    pub renamed_properties: BTreeMap<String, String>,
+    /// Print trees without node properties, as `--no-properties` does, unless `--properties` is given.
+    pub structure_only: bool,
+    /// Size limits `gdtree lint` holds every scene to.
+    pub budget: Budget,
+    /// Limits for particular scenes, keyed by res:// path, replacing those of `budget`.
+    pub scene_budgets: BTreeMap<String, Budget>,
+    /// The owners of the scenes matching each res:// glob, in place of CODEOWNERS:
+    /// `"res://levels/*" = ["@level-design"]`.
+    pub owners: BTreeMap<String, Vec<String>>,
+}
+
+impl Config {
+    /// Loads the project's config; a project without one gets the defaults.
+    pub fn load(project: Option<&Project>) -> io::Result<Self> {
+        let Some(project) = project else {
+            return Ok(Self::default());
+        };
+        let path = project.root.join(".gdtree/config.toml");
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+        let mut config: Self = toml::from_str(&text)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), err)))?;
+        // relative paths are relative to the project
+        config.docs = config.docs.map(|docs| project.root.join(docs));
+        Ok(config)
+    }
+
+    /// The scene budgets, for `gdtree lint`.
+    pub fn budgets(&self) -> Budgets {
+        Budgets { default: self.budget.clone(), scenes: self.scene_budgets.clone() }
+    }
+}
@@ -0,0 +1,178 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::diff;
+use crate::parser::{self, Statement};
+use crate::project::Project;
+use crate::scene;
+
+/// A stretch of the file git couldn't merge, with the lines of each side.
+struct Hunk<'a> {
+    /// The lines of the `<<<<<<<` and `>>>>>>>` markers, from 1.
+    start: usize,
+    end: usize,
+    /// What the markers call the sides, such as `HEAD` and a branch name.
+    ours_label: &'a str,
+    theirs_label: &'a str,
+    ours: Vec<&'a str>,
+    theirs: Vec<&'a str>,
+}
+
+/// The file as lines outside the conflicts, and conflicts by their index.
+enum Piece<'a> {
+    Line(&'a str),
+    Hunk(usize),
+}
+
+/// Explains each conflict git left in a scene: where in the scene it falls,
+/// and what the two sides make of the nodes, properties and connections there.
+/// Each side is read as the whole scene with that conflict settled its way
+/// (and any other conflicts settled as ours), and the two are compared as
+/// `gdtree diff` would, from ours to theirs. Returns how many conflicts there
+/// were.
+pub fn explain(file: &Path, project: Option<&Project>) -> io::Result<usize> {
+    let text = fs::read_to_string(file)?;
+    let (pieces, hunks) = split(&text).map_err(|msg| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", file.display(), msg)))?;
+    if hunks.is_empty() {
+        println!("no conflicts in {}", file.display());
+        return Ok(0);
+    }
+    for (i, hunk) in hunks.iter().enumerate() {
+        println!("conflict {} of {}, lines {}-{}, {}:", i + 1, hunks.len(), hunk.start, hunk.end, context(&pieces, i));
+        let ours = settle(&pieces, &hunks, i, false);
+        let theirs = settle(&pieces, &hunks, i, true);
+        let parse = |text: &str| scene::parse(text.as_bytes(), project);
+        let changes = match (parse(&ours), parse(&theirs)) {
+            (Ok(ours), Ok(theirs)) => diff::changes(&ours, &theirs),
+            (Err(err), _) | (_, Err(err)) => {
+                println!("  one side doesn't parse ({}), so here they are as written:", err);
+                Vec::new()
+            }
+        };
+        match changes.is_empty() {
+            true => {
+                // headers, resources and the like, which diff doesn't compare
+                if is_load_steps(hunk) {
+                    println!("  only load_steps differs; Godot counts it again on saving, so either side will do");
+                    continue;
+                }
+                for line in hunk.ours.iter() {
+                    println!("  {} | {}", hunk.ours_label, line);
+                }
+                for line in hunk.theirs.iter() {
+                    println!("  {} | {}", hunk.theirs_label, line);
+                }
+            }
+            false => {
+                println!("  from {} to {}:", side("ours", hunk.ours_label), side("theirs", hunk.theirs_label));
+                for change in changes.iter() {
+                    println!("  {}", change);
+                }
+            }
+        }
+    }
+    Ok(hunks.len())
+}
+
+/// Splits the file at its conflict markers. The common ancestor's lines that
+/// `merge.conflictStyle = diff3` adds are left out.
+fn split(text: &str) -> Result<(Vec<Piece<'_>>, Vec<Hunk<'_>>), String> {
+    let mut pieces = Vec::new();
+    let mut hunks: Vec<Hunk> = Vec::new();
+    // which part of a conflict the lines belong to: 0 outside, then ours, the
+    // ancestor's and theirs
+    let mut part = 0;
+    for (n, line) in text.lines().enumerate() {
+        let marker = |m: &str| line.strip_prefix(m).map(|label| label.trim());
+        match (part, marker("<<<<<<<"), marker("|||||||"), marker("======="), marker(">>>>>>>")) {
+            (0, Some(label), ..) => {
+                pieces.push(Piece::Hunk(hunks.len()));
+                hunks.push(Hunk { start: n + 1, end: n + 1, ours_label: label, theirs_label: "", ours: Vec::new(), theirs: Vec::new() });
+                part = 1;
+            }
+            (1, _, Some(_), ..) => part = 2,
+            (1 | 2, _, _, Some(_), _) => part = 3,
+            (3, .., Some(label)) => {
+                let hunk = hunks.last_mut().unwrap();
+                (hunk.theirs_label, hunk.end) = (label, n + 1);
+                part = 0;
+            }
+            (0, ..) => pieces.push(Piece::Line(line)),
+            (1, ..) => hunks.last_mut().unwrap().ours.push(line),
+            (2, ..) => (),
+            _ => hunks.last_mut().unwrap().theirs.push(line),
+        }
+    }
+    match (part, hunks.last()) {
+        (0, _) => Ok((pieces, hunks)),
+        (_, Some(hunk)) => Err(format!("the conflict starting on line {} is never closed", hunk.start)),
+        (_, None) => unreachable!(),
+    }
+}
+
+/// The scene with conflict `at` settled as `theirs` says and the others as ours.
+fn settle(pieces: &[Piece], hunks: &[Hunk], at: usize, theirs: bool) -> String {
+    let mut text = String::new();
+    for piece in pieces.iter() {
+        let lines = match piece {
+            Piece::Line(line) => std::slice::from_ref(line),
+            Piece::Hunk(i) if *i == at && theirs => &hunks[*i].theirs[..],
+            Piece::Hunk(i) => &hunks[*i].ours[..],
+        };
+        for line in lines.iter() {
+            text += line;
+            text.push('\n');
+        }
+    }
+    text
+}
+
+/// Where conflict `at` falls: in the section whose header last came before it.
+fn context(pieces: &[Piece], at: usize) -> String {
+    let header = pieces
+        .iter()
+        .take_while(|piece| !matches!(piece, Piece::Hunk(i) if *i == at))
+        .filter_map(|piece| match piece {
+            Piece::Line(line) if line.starts_with('[') => Some(*line),
+            _ => None,
+        })
+        .last();
+    let Some(header) = header else {
+        return "at the top of the file".to_string();
+    };
+    let statements = parser::parse(header).unwrap_or_default();
+    let Some(statement @ Statement::Section { tag, .. }) = statements.first() else {
+        return format!("after {}", header);
+    };
+    let attr = |key: &str| statement.attr(key).and_then(|v| v.as_str()).unwrap_or_default();
+    match tag.as_str() {
+        "node" => match attr("parent") {
+            "" => format!("in the root node {}", attr("name")),
+            "." => format!("in node {}", attr("name")),
+            parent => format!("in node {}/{}", parent, attr("name")),
+        },
+        "sub_resource" => format!("in sub_resource {} ({})", attr("id"), attr("type")),
+        _ => format!("after {}", header),
+    }
+}
+
+/// A side of a conflict with the name its marker gives it, as `HEAD (ours)`.
+fn side(name: &str, label: &str) -> String {
+    match label.is_empty() || label == name {
+        true => name.to_string(),
+        false => format!("{} ({})", label, name),
+    }
+}
+
+/// Whether the sides differ only in the load_steps of the file's header, the
+/// most common conflict of all, since any added resource changes it.
+fn is_load_steps(hunk: &Hunk) -> bool {
+    let strip = |lines: &[&str]| -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.split_whitespace().filter(|word| !word.starts_with("load_steps=")).collect::<Vec<_>>().join(" "))
+            .collect()
+    };
+    hunk.ours.iter().chain(hunk.theirs.iter()).any(|line| line.contains("load_steps=")) && strip(&hunk.ours) == strip(&hunk.theirs)
+}
@@ -0,0 +1,39 @@
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use crate::scene::Node;
+
+/// Prints which nodes have a script attached and which don't, grouped by node type.
+pub fn report(root: &Node, out: &mut impl Write) -> io::Result<()> {
+    let mut groups: BTreeMap<String, Vec<(String, bool)>> = BTreeMap::new();
+    for (path, node) in root.paths() {
+        let group = match (&node.instance, node._type.as_str()) {
+            (Some(res), "") => format!("instance of {}", res.path),
+            (_, "") => "unknown type".to_string(),
+            (_, t) => t.to_string(),
+        };
+        let has_script = node.parameters.iter().any(|p| p.key == "script");
+        let label = if path == "." { node.name.clone() } else { path };
+        groups.entry(group).or_default().push((label, has_script));
+    }
+
+    let total: usize = groups.values().map(|nodes| nodes.len()).sum();
+    let scripted = groups.values().flatten().filter(|(_, s)| *s).count();
+    writeln!(out, "{}: {} of {} nodes have scripts ({})", root.name, scripted, total, percent(scripted, total))?;
+    for (group, nodes) in groups.iter() {
+        let count = nodes.iter().filter(|(_, s)| *s).count();
+        writeln!(out)?;
+        writeln!(out, "{} ({}/{}, {})", group, count, nodes.len(), percent(count, nodes.len()))?;
+        for (path, has_script) in nodes.iter() {
+            writeln!(out, "  {} {}", if *has_script { "+" } else { "-" }, path)?;
+        }
+    }
+    Ok(())
+}
+
+fn percent(part: usize, total: usize) -> String {
+    match total {
+        0 => "0.0%".to_string(),
+        _ => format!("{:.1}%", part as f64 * 100.0 / total as f64),
+    }
+}
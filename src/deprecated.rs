@@ -0,0 +1,170 @@
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::project::{self, Project};
+use crate::scene;
+
+/// What became of a Godot 3 node class in Godot 4.
+pub enum Change {
+    Renamed(&'static str),
+    Removed(&'static str),
+}
+
+/// Godot 3 node classes that were renamed or dropped in Godot 4.
+pub const CLASSES: &[(&str, Change)] = &[
+    ("ARVRAnchor", Change::Renamed("XRAnchor3D")),
+    ("ARVRCamera", Change::Renamed("XRCamera3D")),
+    ("ARVRController", Change::Renamed("XRController3D")),
+    ("ARVROrigin", Change::Renamed("XROrigin3D")),
+    ("AnimatedSprite", Change::Renamed("AnimatedSprite2D")),
+    ("AnimationTreePlayer", Change::Removed("use AnimationTree")),
+    ("Area", Change::Renamed("Area3D")),
+    ("BakedLightmap", Change::Renamed("LightmapGI")),
+    ("BoneAttachment", Change::Renamed("BoneAttachment3D")),
+    ("CPUParticles", Change::Renamed("CPUParticles3D")),
+    ("Camera", Change::Renamed("Camera3D")),
+    ("ClippedCamera", Change::Removed("use SpringArm3D with a Camera3D child")),
+    ("CollisionObject", Change::Renamed("CollisionObject3D")),
+    ("CollisionPolygon", Change::Renamed("CollisionPolygon3D")),
+    ("CollisionShape", Change::Renamed("CollisionShape3D")),
+    ("ConeTwistJoint", Change::Renamed("ConeTwistJoint3D")),
+    ("DirectionalLight", Change::Renamed("DirectionalLight3D")),
+    ("GIProbe", Change::Renamed("VoxelGI")),
+    ("Generic6DOFJoint", Change::Renamed("Generic6DOFJoint3D")),
+    ("HingeJoint", Change::Renamed("HingeJoint3D")),
+    ("ImmediateGeometry", Change::Removed("use MeshInstance3D with an ImmediateMesh")),
+    ("InterpolatedCamera", Change::Removed("interpolate a Camera3D from a script")),
+    ("Joint", Change::Renamed("Joint3D")),
+    ("KinematicBody", Change::Renamed("CharacterBody3D")),
+    ("KinematicBody2D", Change::Renamed("CharacterBody2D")),
+    ("Light2D", Change::Renamed("PointLight2D")),
+    ("Listener", Change::Renamed("AudioListener3D")),
+    ("Listener2D", Change::Renamed("AudioListener2D")),
+    ("MeshInstance", Change::Renamed("MeshInstance3D")),
+    ("MultiMeshInstance", Change::Renamed("MultiMeshInstance3D")),
+    ("Navigation", Change::Removed("use NavigationServer3D directly")),
+    ("Navigation2D", Change::Removed("use NavigationServer2D directly")),
+    ("NavigationMeshInstance", Change::Renamed("NavigationRegion3D")),
+    ("NavigationPolygonInstance", Change::Renamed("NavigationRegion2D")),
+    ("Occluder", Change::Renamed("OccluderInstance3D")),
+    ("Particles", Change::Renamed("GPUParticles3D")),
+    ("Particles2D", Change::Renamed("GPUParticles2D")),
+    ("Path", Change::Renamed("Path3D")),
+    ("PathFollow", Change::Renamed("PathFollow3D")),
+    ("PhysicalBone", Change::Renamed("PhysicalBone3D")),
+    ("PinJoint", Change::Renamed("PinJoint3D")),
+    ("PopupDialog", Change::Renamed("Popup")),
+    ("Portal", Change::Removed("portals and rooms have no Godot 4 equivalent")),
+    ("Position2D", Change::Renamed("Marker2D")),
+    ("Position3D", Change::Renamed("Marker3D")),
+    ("ProximityGroup", Change::Removed("track proximity from a script")),
+    ("RayCast", Change::Renamed("RayCast3D")),
+    ("RemoteTransform", Change::Renamed("RemoteTransform3D")),
+    ("RigidBody", Change::Renamed("RigidBody3D")),
+    ("Room", Change::Removed("portals and rooms have no Godot 4 equivalent")),
+    ("RoomGroup", Change::Removed("portals and rooms have no Godot 4 equivalent")),
+    ("RoomManager", Change::Removed("portals and rooms have no Godot 4 equivalent")),
+    ("Skeleton", Change::Renamed("Skeleton3D")),
+    ("SliderJoint", Change::Renamed("SliderJoint3D")),
+    ("SoftBody", Change::Renamed("SoftBody3D")),
+    ("Spatial", Change::Renamed("Node3D")),
+    ("SpotLight", Change::Renamed("SpotLight3D")),
+    ("SpringArm", Change::Renamed("SpringArm3D")),
+    ("Sprite", Change::Renamed("Sprite2D")),
+    ("StaticBody", Change::Renamed("StaticBody3D")),
+    ("TextureProgress", Change::Renamed("TextureProgressBar")),
+    ("ToolButton", Change::Renamed("Button")),
+    ("Tween", Change::Removed("use create_tween() from a script")),
+    ("VehicleBody", Change::Renamed("VehicleBody3D")),
+    ("VehicleWheel", Change::Renamed("VehicleWheel3D")),
+    ("VideoPlayer", Change::Renamed("VideoStreamPlayer")),
+    ("Viewport", Change::Renamed("SubViewport")),
+    ("ViewportContainer", Change::Renamed("SubViewportContainer")),
+    ("VisibilityEnabler", Change::Renamed("VisibleOnScreenEnabler3D")),
+    ("VisibilityEnabler2D", Change::Renamed("VisibleOnScreenEnabler2D")),
+    ("VisibilityNotifier", Change::Renamed("VisibleOnScreenNotifier3D")),
+    ("VisibilityNotifier2D", Change::Renamed("VisibleOnScreenNotifier2D")),
+    ("WindowDialog", Change::Renamed("Window")),
+    ("YSort", Change::Removed("use a Node2D with y_sort_enabled")),
+];
+
+/// Node properties renamed between Godot 3 and 4, as (class, old name, new name).
+/// An empty class applies to every class, and an old name ending in `/` renames
+/// a whole group of properties. Renames that also changed the value's meaning,
+/// such as `rect_rotation` going from degrees to radians, are left out.
+pub const PROPERTIES: &[(&str, &str, &str)] = &[
+    ("", "custom_colors/", "theme_override_colors/"),
+    ("", "custom_constants/", "theme_override_constants/"),
+    ("", "custom_fonts/", "theme_override_fonts/"),
+    ("", "custom_icons/", "theme_override_icons/"),
+    ("", "custom_styles/", "theme_override_styles/"),
+    ("", "hint_tooltip", "tooltip_text"),
+    ("", "margin_bottom", "offset_bottom"),
+    ("", "margin_left", "offset_left"),
+    ("", "margin_right", "offset_right"),
+    ("", "margin_top", "offset_top"),
+    ("", "rect_clip_content", "clip_contents"),
+    ("", "rect_min_size", "custom_minimum_size"),
+    ("", "rect_pivot_offset", "pivot_offset"),
+    ("", "rect_position", "position"),
+    ("", "rect_scale", "scale"),
+    ("", "rect_size", "size"),
+    ("", "translation", "position"),
+    ("AnimatedSprite", "frames", "sprite_frames"),
+    ("AnimationPlayer", "playback_speed", "speed_scale"),
+    ("AudioStreamPlayer3D", "unit_db", "volume_db"),
+    ("Button", "align", "alignment"),
+    ("Camera2D", "current", "enabled"),
+    ("Camera2D", "smoothing_enabled", "position_smoothing_enabled"),
+    ("Camera2D", "smoothing_speed", "position_smoothing_speed"),
+    ("Label", "align", "horizontal_alignment"),
+    ("Label", "percent_visible", "visible_ratio"),
+    ("Label", "valign", "vertical_alignment"),
+    ("LineEdit", "align", "alignment"),
+    ("RayCast", "cast_to", "target_position"),
+    ("RayCast2D", "cast_to", "target_position"),
+    ("RichTextLabel", "bbcode_text", "text"),
+    ("RichTextLabel", "percent_visible", "visible_ratio"),
+    ("WindowDialog", "window_title", "title"),
+];
+
+/// Looks up what happened to a node class in Godot 4, if it didn't survive unchanged.
+pub fn change(class: &str) -> Option<&'static Change> {
+    CLASSES
+        .binary_search_by(|(old, _)| old.cmp(&class))
+        .ok()
+        .map(|i| &CLASSES[i].1)
+}
+
+/// Lists the nodes using renamed or removed classes in the scenes at `path`,
+/// returning how many were found.
+pub fn report(path: &Path, project: Option<&Project>) -> io::Result<usize> {
+    let files = match path.is_dir() {
+        true => project::list_files(path, &["tscn"]),
+        false => vec![path.to_path_buf()],
+    };
+    let (mut found, mut scenes) = (0, 0);
+    for file in files.iter() {
+        let root = scene::parse(BufReader::new(File::open(file)?), project)?;
+        let mut lines = Vec::new();
+        for (node_path, node) in root.paths() {
+            match change(&node._type) {
+                Some(Change::Renamed(new)) => lines.push(format!("{} ({}): renamed to {}", node_path, node._type, new)),
+                Some(Change::Removed(hint)) => lines.push(format!("{} ({}): removed, {}", node_path, node._type, hint)),
+                None => continue,
+            }
+        }
+        if !lines.is_empty() {
+            println!("{}", project.and_then(|p| p.res_path(file)).unwrap_or_else(|| file.display().to_string()));
+            for line in lines.iter() {
+                println!("  {}", line);
+            }
+            found += lines.len();
+            scenes += 1;
+        }
+    }
+    println!("{} deprecated nodes in {} of {} scenes", found, scenes, files.len());
+    Ok(found)
+}
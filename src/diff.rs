@@ -0,0 +1,224 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::scene::{Connection, Node};
+
+/// One semantic difference between two versions of a scene.
+pub enum Change {
+    NodeAdded { path: String, _type: String },
+    NodeRemoved { path: String, _type: String },
+    /// A node now under another parent, along with everything beneath it.
+    NodeMoved { from: String, to: String, _type: String },
+    TypeChanged { path: String, old: String, new: String },
+    PropertyAdded { path: String, key: String, val: String },
+    PropertyRemoved { path: String, key: String, val: String },
+    PropertyChanged { path: String, key: String, old: String, new: String },
+    ConnectionAdded(Connection),
+    ConnectionRemoved(Connection),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let typed = |path: &str, _type: &str| match _type.is_empty() {
+            true => path.to_string(),
+            false => format!("{} ({})", path, _type),
+        };
+        let connection = |c: &Connection| format!("connection: {}:{}() => {}:{}(){}", c.from, c.signal, c.to, c.method, c.tags());
+        match self {
+            Change::NodeAdded { path, _type } => write!(f, "+ {}", typed(path, _type)),
+            Change::NodeRemoved { path, _type } => write!(f, "- {}", typed(path, _type)),
+            Change::NodeMoved { from, to, _type } => write!(f, "> {} → {}", typed(from, _type), to),
+            Change::TypeChanged { path, old, new } => write!(f, "~ {}: type {} → {}", path, old, new),
+            Change::PropertyAdded { path, key, val } => write!(f, "+ {}: {}: {}", path, key, val),
+            Change::PropertyRemoved { path, key, val } => write!(f, "- {}: {}: {}", path, key, val),
+            Change::PropertyChanged { path, key, old, new } => write!(f, "~ {}: {}: {} → {}", path, key, old, new),
+            Change::ConnectionAdded(c) => write!(f, "+ {}", connection(c)),
+            Change::ConnectionRemoved(c) => write!(f, "- {}", connection(c)),
+        }
+    }
+}
+
+/// Compares two versions of a scene node by node, matching nodes by their path,
+/// or for a node gone from one path and new at another, by its name and type.
+pub fn changes(old: &Node, new: &Node) -> Vec<Change> {
+    let old_nodes: IndexMap<String, &Node> = old.paths().into_iter().collect();
+    let new_nodes: IndexMap<String, &Node> = new.paths().into_iter().collect();
+    let moves = moves(&old_nodes, &new_nodes);
+    let mut changes = Vec::new();
+
+    for (path, node) in old_nodes.iter() {
+        if !new_nodes.contains_key(path) && !moves.contains_key(path) {
+            changes.push(Change::NodeRemoved { path: path.clone(), _type: node._type.clone() });
+        }
+    }
+    for (from, to) in moves.iter() {
+        // the children of a moved node move with it
+        let carried = moves.iter().any(|(parent_from, parent_to)| {
+            from.strip_prefix(parent_from.as_str()).is_some_and(|rest| rest.starts_with('/') && *to == format!("{}{}", parent_to, rest))
+        });
+        if !carried {
+            changes.push(Change::NodeMoved { from: from.clone(), to: to.clone(), _type: new_nodes[to]._type.clone() });
+        }
+    }
+    let moved_from: IndexMap<&String, &String> = moves.iter().map(|(from, to)| (to, from)).collect();
+    for (path, node) in new_nodes.iter() {
+        let before = match (old_nodes.get(path), moved_from.get(path)) {
+            (Some(before), _) => before,
+            (None, Some(from)) => &old_nodes[*from],
+            (None, None) => {
+                changes.push(Change::NodeAdded { path: path.clone(), _type: node._type.clone() });
+                continue;
+            }
+        };
+        if before._type != node._type {
+            changes.push(Change::TypeChanged { path: path.clone(), old: before._type.clone(), new: node._type.clone() });
+        }
+        let (before, after) = (properties(before), properties(node));
+        for (key, val) in before.iter() {
+            if !after.contains_key(key) {
+                changes.push(Change::PropertyRemoved { path: path.clone(), key: key.clone(), val: val.clone() });
+            }
+        }
+        for (key, val) in after.iter() {
+            match before.get(key) {
+                None => changes.push(Change::PropertyAdded { path: path.clone(), key: key.clone(), val: val.clone() }),
+                Some(old) if old != val => changes.push(Change::PropertyChanged {
+                    path: path.clone(),
+                    key: key.clone(),
+                    old: old.clone(),
+                    new: val.clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+    }
+
+    let same = |a: &Connection, b: &Connection| {
+        a.signal == b.signal && a.from == b.from && a.to == b.to && a.method == b.method && a.flags == b.flags
+    };
+    let old_connections: Vec<&Connection> = old_nodes.values().flat_map(|n| n.connections.iter()).collect();
+    let new_connections: Vec<&Connection> = new_nodes.values().flat_map(|n| n.connections.iter()).collect();
+    for conn in old_connections.iter() {
+        if !new_connections.iter().any(|c| same(c, conn)) {
+            changes.push(Change::ConnectionRemoved((*conn).clone()));
+        }
+    }
+    for conn in new_connections.iter() {
+        if !old_connections.iter().any(|c| same(c, conn)) {
+            changes.push(Change::ConnectionAdded((*conn).clone()));
+        }
+    }
+    changes
+}
+
+/// Pairs the paths of nodes that are gone with those of new nodes with the same
+/// name and type, taking them to have been moved, old path to new.
+fn moves(old_nodes: &IndexMap<String, &Node>, new_nodes: &IndexMap<String, &Node>) -> IndexMap<String, String> {
+    let mut added: Vec<&String> = new_nodes.keys().filter(|path| !old_nodes.contains_key(*path)).collect();
+    let mut moves = IndexMap::new();
+    for (path, node) in old_nodes.iter().filter(|(path, _)| !new_nodes.contains_key(*path)) {
+        let found = added.iter().position(|to| {
+            let after = new_nodes[*to];
+            after.name == node.name && after._type == node._type && after.instance.as_ref().map(|r| &r.path) == node.instance.as_ref().map(|r| &r.path)
+        });
+        if let Some(at) = found {
+            moves.insert(path.clone(), added.remove(at).clone());
+        }
+    }
+    moves
+}
+
+/// A node's properties with those of its sub_resources flattened in as
+/// `key/sub_key`, so edits inside a sub_resource show up too.
+fn properties(node: &Node) -> IndexMap<String, String> {
+    let mut properties = IndexMap::new();
+    for param in node.parameters.iter() {
+        properties.insert(param.key.clone(), param.val.clone());
+        for sub in param.sub_params.iter() {
+            properties.insert(format!("{}/{}", param.key, sub.key), sub.val.clone());
+        }
+    }
+    properties
+}
+
+/// Sums changes up in one sentence, such as "Added 3 nodes under HUD, changed
+/// 5 properties on Player, removed 1 connection", for changelogs and pull
+/// request descriptions.
+pub fn summary(changes: &[Change]) -> String {
+    let added: Vec<&str> = changes.iter().filter_map(|c| match c { Change::NodeAdded { path, .. } => Some(path.as_str()), _ => None }).collect();
+    let removed: Vec<&str> = changes.iter().filter_map(|c| match c { Change::NodeRemoved { path, .. } => Some(path.as_str()), _ => None }).collect();
+    let mut clauses = Vec::new();
+    for (verb, paths) in [("added", &added), ("removed", &removed)] {
+        for (parent, count) in by_parent(paths) {
+            clauses.push(format!("{} {} under {}", verb, plural(count, "node", "nodes"), parent));
+        }
+    }
+    let moved = changes.iter().filter(|c| matches!(c, Change::NodeMoved { .. })).count();
+    if moved > 0 {
+        clauses.push(format!("moved {}", plural(moved, "node", "nodes")));
+    }
+
+    let retyped: Vec<&str> = changes.iter().filter_map(|c| match c { Change::TypeChanged { path, .. } => Some(path.as_str()), _ => None }).collect();
+    if !retyped.is_empty() {
+        clauses.push(format!("changed the type of {}", retyped.join(", ")));
+    }
+    let mut properties: IndexMap<&str, usize> = IndexMap::new();
+    for change in changes.iter() {
+        if let Change::PropertyAdded { path, .. } | Change::PropertyRemoved { path, .. } | Change::PropertyChanged { path, .. } = change {
+            *properties.entry(path.as_str()).or_default() += 1;
+        }
+    }
+    for (path, count) in properties {
+        clauses.push(format!("changed {} on {}", plural(count, "property", "properties"), name(path)));
+    }
+
+    let connections_added = changes.iter().filter(|c| matches!(c, Change::ConnectionAdded(_))).count();
+    let connections_removed = changes.iter().filter(|c| matches!(c, Change::ConnectionRemoved(_))).count();
+    for (verb, count) in [("added", connections_added), ("removed", connections_removed)] {
+        if count > 0 {
+            clauses.push(format!("{} {}", verb, plural(count, "connection", "connections")));
+        }
+    }
+
+    let sentence = match clauses.is_empty() {
+        true => "no changes".to_string(),
+        false => clauses.join(", "),
+    };
+    let mut chars = sentence.chars();
+    chars.next().unwrap().to_uppercase().chain(chars).collect()
+}
+
+/// Counts added or removed nodes by the nearest ancestor that was there all
+/// along, so a whole new subtree counts toward the node it was added under.
+fn by_parent(paths: &[&str]) -> IndexMap<String, usize> {
+    let mut counts = IndexMap::new();
+    for path in paths.iter() {
+        let mut parent = *path;
+        loop {
+            parent = match parent.rsplit_once('/') {
+                Some((up, _)) => up,
+                None => ".",
+            };
+            if parent == "." || !paths.contains(&parent) {
+                break;
+            }
+        }
+        *counts.entry(name(parent)).or_default() += 1;
+    }
+    counts
+}
+
+fn name(path: &str) -> String {
+    match path {
+        "." => "the root".to_string(),
+        path => path.to_string(),
+    }
+}
+
+fn plural(count: usize, one: &str, many: &str) -> String {
+    match count {
+        1 => format!("1 {}", one),
+        count => format!("{} {}", count, many),
+    }
+}
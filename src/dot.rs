@@ -0,0 +1,90 @@
+use indexmap::IndexMap;
+
+use crate::scene::Node;
+
+/// Draws a scene as a Graphviz digraph: solid edges from each node to its
+/// children, and dashed ones, labelled with the signal and method, for the
+/// connections with all the `flags` bits.
+pub fn render(root: &Node, flags: u32) -> String {
+    let mut out = format!("digraph {} {{\n", quote(&root.name));
+    out += "    node [shape=box];\n";
+    let nodes = root.paths();
+    for (path, node) in nodes.iter() {
+        let label = match (&node.instance, node._type.as_str()) {
+            (Some(res), "") => format!("{}\\n{}", node.name, res.path),
+            (_, "") => node.name.clone(),
+            (_, _type) => format!("{}\\n({})", node.name, _type),
+        };
+        out += &format!("    {} [label={}];\n", quote(path), quote(&label));
+    }
+    for (path, node) in nodes.iter() {
+        for name in node.children.keys() {
+            let child = match path.as_str() {
+                "." => name.clone(),
+                path => format!("{}/{}", path, name),
+            };
+            out += &format!("    {} -> {};\n", quote(path), quote(&child));
+        }
+    }
+    for (_, node) in nodes.iter() {
+        for conn in node.connections.iter().filter(|c| c.flags & flags == flags) {
+            // connections name the root by its name as their target
+            let to = match conn.to == root.name {
+                true => ".",
+                false => conn.to.as_str(),
+            };
+            let label = format!("{} → {}(){}", conn.signal, conn.method, conn.tags());
+            out += &format!("    {} -> {} [style=dashed, color=blue, fontcolor=blue, label={}];\n", quote(&conn.from), quote(to), quote(&label));
+        }
+    }
+    out += "}\n";
+    out
+}
+
+/// Draws a project's dependency graph: an edge from each scene to each resource
+/// it loads, with the files of each directory grouped into a cluster.
+pub fn dependencies(scenes: &[(String, Vec<String>)]) -> String {
+    let mut files: Vec<&str> = Vec::new();
+    for (scene, resources) in scenes.iter() {
+        for file in std::iter::once(scene).chain(resources.iter()) {
+            if !files.contains(&file.as_str()) {
+                files.push(file);
+            }
+        }
+    }
+    let mut dirs: IndexMap<&str, Vec<&str>> = IndexMap::new();
+    for file in files.iter() {
+        let dir = file.rsplit_once('/').map_or("", |(dir, _)| dir);
+        dirs.entry(dir).or_default().push(file);
+    }
+    dirs.sort_keys();
+
+    let mut out = String::from("digraph dependencies {\n    node [shape=box];\n");
+    for (i, (dir, files)) in dirs.iter().enumerate() {
+        let label = |file: &str| file.rsplit('/').next().unwrap_or(file).to_string();
+        // files at the top of the project stay ungrouped
+        if dir.is_empty() || *dir == "res:/" {
+            for file in files.iter() {
+                out += &format!("    {} [label={}];\n", quote(file), quote(&label(file)));
+            }
+            continue;
+        }
+        out += &format!("    subgraph cluster_{} {{\n        label={};\n", i, quote(&format!("{}/", dir)));
+        for file in files.iter() {
+            out += &format!("        {} [label={}];\n", quote(file), quote(&label(file)));
+        }
+        out += "    }\n";
+    }
+    for (scene, resources) in scenes.iter() {
+        for res in resources.iter() {
+            out += &format!("    {} -> {};\n", quote(scene), quote(res));
+        }
+    }
+    out += "}\n";
+    out
+}
+
+/// Quotes an identifier or label for DOT; `\n` escapes already in it are kept.
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('"', "\\\""))
+}
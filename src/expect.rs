@@ -0,0 +1,59 @@
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use crate::project::Project;
+use crate::scene::{self, Node};
+
+const HEADER: &str = "# gdtree expect ";
+
+/// Lists a scene's node paths with their types, under a header naming the
+/// scene, as `verify` reads them back. Lines can be deleted to leave only the
+/// paths a test cares about.
+pub fn write(scene: &str, root: &Node) -> String {
+    let mut out = format!("{}{}\n", HEADER, scene);
+    for (path, node) in root.paths() {
+        match node._type.is_empty() {
+            true => out += &format!("{}\n", path),
+            false => out += &format!("{} ({})\n", path, node._type),
+        }
+    }
+    out
+}
+
+/// Checks that the scene an expectations file names still has each node path it
+/// lists, of the type it gives, printing those that don't. Returns how many
+/// were missing or changed.
+pub fn verify(file: &Path, project: Option<&Project>) -> io::Result<usize> {
+    let text = fs::read_to_string(file).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", file.display(), err)))?;
+    let Some(scene) = text.lines().next().and_then(|line| line.strip_prefix(HEADER)) else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}: the first line should be \"{}<scene>\"; write it with gdtree expect", file.display(), HEADER),
+        ));
+    };
+    let scene_file = match project {
+        Some(project) if scene.starts_with("res://") => project.resolve(scene),
+        _ => PathBuf::from(scene),
+    };
+    let reader = File::open(&scene_file).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", scene_file.display(), err)))?;
+    let root = scene::parse(BufReader::new(reader), project)?;
+
+    let mut problems = 0;
+    for line in text.lines().skip(1).map(|line| line.trim()).filter(|line| !line.is_empty() && !line.starts_with('#')) {
+        let (path, _type) = match line.strip_suffix(')').and_then(|rest| rest.rsplit_once(" (")) {
+            Some((path, _type)) => (path, Some(_type)),
+            None => (line, None),
+        };
+        let problem = match (root.find(path), _type) {
+            (None, _) => format!("missing {}", line),
+            (Some(node), Some(_type)) if node._type != _type => format!("{} is now a {}, not a {}", path, node._type, _type),
+            (Some(_), _) => continue,
+        };
+        println!("{}: {}", scene, problem);
+        problems += 1;
+    }
+    Ok(problems)
+}
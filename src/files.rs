@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::media;
+use crate::units;
+
+/// Describes a referenced file for `--check-files`: missing, or its size and mtime,
+/// followed by what its header says for media files.
+/// With `human_sizes` the size is given in KiB, MiB and so on.
+pub fn describe(path: &Path, human_sizes: bool) -> String {
+    let size = |len: u64| match human_sizes {
+        true => units::bytes(len),
+        false => format!("{} bytes", len),
+    };
+    let description = match fs::metadata(path) {
+        Ok(meta) => match meta.modified() {
+            Ok(modified) => format!("{}, modified {}", size(meta.len()), timestamp(modified)),
+            Err(_) => size(meta.len()),
+        },
+        Err(_) => return "missing".to_string(),
+    };
+    match media::describe(path) {
+        Some(media) => format!("{}, {}", description, media),
+        None => description,
+    }
+}
+
+/// Formats a time as `YYYY-MM-DD HH:MM:SS UTC`.
+pub fn timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let (days, rem) = (secs / 86400, secs % 86400);
+    // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year, month, day, rem / 3600, rem % 3600 / 60, rem % 60
+    )
+}
@@ -0,0 +1,64 @@
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+/// A commit that touched a file, with the file's path as of that commit.
+pub struct Revision {
+    pub hash: String,
+    pub author: String,
+    pub date: String,
+    pub subject: String,
+    /// Relative to the top of the repository, as git show wants it.
+    pub path: String,
+}
+
+impl Revision {
+    /// The first eight characters of the hash.
+    pub fn short(&self) -> &str {
+        &self.hash[..self.hash.len().min(8)]
+    }
+
+    /// The file as this commit left it.
+    pub fn contents(&self, dir: &Path) -> io::Result<String> {
+        run(dir, &["show", &format!("{}:{}", self.hash, self.path)])
+    }
+}
+
+/// The directory to run git in for a file: the one the file is in.
+pub fn dir(file: &Path) -> &Path {
+    match file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    }
+}
+
+/// The commits that touched the file, newest first, following renames.
+pub fn history(file: &Path) -> io::Result<Vec<Revision>> {
+    let name = file.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let log = run(dir(file), &["log", "--follow", "--name-only", "--format=%x00%H%x09%an%x09%ad%x09%s", "--date=short", "--", &name])?;
+    let mut revisions = Vec::new();
+    for entry in log.split('\0').filter(|e| !e.trim().is_empty()) {
+        let mut lines = entry.lines().filter(|l| !l.is_empty());
+        let (Some(header), Some(path)) = (lines.next(), lines.next()) else {
+            continue;
+        };
+        let fields: Vec<&str> = header.splitn(4, '\t').collect();
+        if let [hash, author, date, subject] = fields[..] {
+            revisions.push(Revision { hash: hash.to_string(), author: author.to_string(), date: date.to_string(), subject: subject.to_string(), path: path.to_string() });
+        }
+    }
+    Ok(revisions)
+}
+
+fn run(dir: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| io::Error::new(err.kind(), format!("could not run git: {}", err)))?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("git {} failed: {}", args[0], String::from_utf8_lossy(&output.stderr).trim())));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
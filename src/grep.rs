@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use crate::parser::{self, Statement};
+use crate::project::{self, Project};
+
+/// A property whose value matched.
+struct Match<'a> {
+    offset: usize,
+    /// The node path, or the sub_resource, the property belongs to.
+    owner: String,
+    key: &'a str,
+    raw: &'a str,
+    /// The path of the ext_resource the value matched through, if it did.
+    via: Option<&'a str>,
+}
+
+/// Searches the property values of the scenes and resources at `path` for a
+/// literal string, printing the file, line, owning node (or sub_resource) and
+/// property of each match. A file that can't be read or parsed is reported on
+/// stderr and skipped. Returns how many matches there were and how many files
+/// were skipped.
+///
+/// A value matches when its text as written contains the string, or when it
+/// refers to an ext_resource whose path does, so searching for a file finds
+/// the properties that load it.
+pub fn run(path: &Path, needle: &str, project: Option<&Project>) -> (usize, usize) {
+    let files = match path.is_dir() {
+        true => project::list_files(path, &["tscn", "tres"]),
+        false => vec![path.to_path_buf()],
+    };
+    let mut found = 0;
+    let mut matched_files = 0;
+    let mut failed = 0;
+    for file in files.iter() {
+        let name = project
+            .and_then(|p| p.res_path(&fs::canonicalize(file).unwrap_or(file.clone())))
+            .unwrap_or_else(|| file.display().to_string());
+        let text = match fs::read_to_string(file) {
+            Ok(text) => text,
+            Err(err) => {
+                eprintln!("gdtree: {}: {}", name, err);
+                failed += 1;
+                continue;
+            }
+        };
+        let statements = match parser::parse(&text) {
+            Ok(statements) => statements,
+            Err(err) => {
+                eprintln!("gdtree: {}: {}", name, err);
+                failed += 1;
+                continue;
+            }
+        };
+        let matches = search(&text, &statements, needle);
+        for m in matches.iter() {
+            let line = text[..m.offset].matches('\n').count() + 1;
+            match m.via {
+                Some(path) => println!("{}:{}: {}: {} = {} ({})", name, line, m.owner, m.key, m.raw, path),
+                None => println!("{}:{}: {}: {} = {}", name, line, m.owner, m.key, m.raw),
+            }
+        }
+        found += matches.len();
+        matched_files += usize::from(!matches.is_empty());
+    }
+    println!("{} matches in {} of {} files", found, matched_files, files.len());
+    if failed > 0 {
+        eprintln!("gdtree: {} of {} files could not be searched", failed, files.len());
+    }
+    (found, failed)
+}
+
+/// The properties whose values match `needle`.
+fn search<'a>(text: &'a str, statements: &'a [Statement], needle: &str) -> Vec<Match<'a>> {
+    let mut ext_paths: HashMap<&str, &str> = HashMap::new();
+    let mut matches = Vec::new();
+    let mut owner = String::new();
+    for statement in statements.iter() {
+        match statement {
+            Statement::Section { tag, .. } => {
+                let attr = |key: &str| statement.attr(key).and_then(|v| v.as_str()).unwrap_or_default();
+                owner = match tag.as_str() {
+                    "node" => match (statement.attr("parent").and_then(|v| v.as_str()), attr("name")) {
+                        (None, name) => name.to_string(),
+                        (Some("."), name) => name.to_string(),
+                        (Some(parent), name) => format!("{}/{}", parent, name),
+                    },
+                    "sub_resource" => format!("sub_resource {} ({})", attr("id"), attr("type")),
+                    "ext_resource" => {
+                        ext_paths.insert(attr("id"), attr("path"));
+                        String::new()
+                    }
+                    tag => tag.to_string(),
+                };
+            }
+            Statement::Assignment { key, value } => {
+                let raw = value.raw(text);
+                if raw.contains(needle) {
+                    matches.push(Match { offset: value.start, owner: owner.clone(), key, raw, via: None });
+                    continue;
+                }
+                let via = value.references().into_iter().find_map(|r| match r.reference() {
+                    Some(("ExtResource", id)) => ext_paths.get(id).copied().filter(|path| path.contains(needle)),
+                    _ => None,
+                });
+                if via.is_some() {
+                    matches.push(Match { offset: value.start, owner: owner.clone(), key, raw, via });
+                }
+            }
+        }
+    }
+    matches
+}
@@ -0,0 +1,73 @@
+use std::io;
+use std::path::Path;
+
+use crate::git::{self, Revision};
+use crate::report;
+use crate::scene::{self, Scene};
+use crate::units;
+
+/// A scene's measures as one commit left it.
+struct Sample<'a> {
+    revision: &'a Revision,
+    /// None when that revision failed to parse.
+    scene: Option<Scene>,
+    size: usize,
+}
+
+/// Prints a timeline of a scene's size and complexity across its git history,
+/// oldest first: the node count, depth, properties, sub_resources, connections
+/// and bytes at up to `samples` commits spread evenly over it, always
+/// including the first and the latest. The largest growth in nodes between two
+/// samples is pointed out, to show when a scene started to balloon.
+pub fn report(file: &Path, samples: usize) -> io::Result<()> {
+    let dir = git::dir(file);
+    let mut revisions = git::history(file)?;
+    if revisions.is_empty() {
+        return Err(io::Error::new(io::ErrorKind::NotFound, format!("{} has no git history", file.display())));
+    }
+    revisions.reverse();
+    let mut picked: Vec<usize> = match revisions.len() <= samples {
+        true => (0..revisions.len()).collect(),
+        false => (0..samples).map(|i| i * (revisions.len() - 1) / (samples - 1).max(1)).collect(),
+    };
+    picked.dedup();
+    let mut timeline = Vec::new();
+    for i in picked {
+        let revision = &revisions[i];
+        let text = revision.contents(dir)?;
+        timeline.push(Sample { revision, scene: scene::parse_scene(text.as_bytes(), None).ok(), size: text.len() });
+    }
+
+    println!("{:10}  {:8}  {:>6}  {:>5}  {:>6}  {:>4}  {:>5}  {:>10}  subject", "date", "commit", "nodes", "depth", "props", "sub", "conns", "size");
+    for sample in timeline.iter() {
+        let r = sample.revision;
+        match &sample.scene {
+            Some(scene) => {
+                let nodes = scene.root.paths();
+                println!(
+                    "{:10}  {:8}  {:>6}  {:>5}  {:>6}  {:>4}  {:>5}  {:>10}  {}",
+                    r.date,
+                    r.short(),
+                    nodes.len(),
+                    report::depth(&scene.root),
+                    nodes.iter().map(|(_, node)| node.parameters.len()).sum::<usize>(),
+                    scene.sub_resources.len(),
+                    nodes.iter().map(|(_, node)| node.connections.len()).sum::<usize>(),
+                    units::bytes(sample.size as u64),
+                    r.subject
+                );
+            }
+            None => println!("{:10}  {:8}  {:>6}  {:>5}  {:>6}  {:>4}  {:>5}  {:>10}  {} (failed to parse)", r.date, r.short(), "-", "-", "-", "-", "-", units::bytes(sample.size as u64), r.subject),
+        }
+    }
+
+    let counts: Vec<(&Revision, usize)> = timeline.iter().filter_map(|s| s.scene.as_ref().map(|scene| (s.revision, scene.root.paths().len()))).collect();
+    let jump = counts.windows(2).map(|pair| (pair[1].0, pair[1].1 as i64 - pair[0].1 as i64)).max_by_key(|(_, growth)| *growth);
+    if let Some((revision, growth)) = jump.filter(|(_, growth)| *growth > 0) {
+        println!("largest growth: +{} nodes by {} {} ({})", growth, revision.short(), revision.date, revision.subject);
+    }
+    if timeline.len() < revisions.len() {
+        println!("{} of {} commits sampled", timeline.len(), revisions.len());
+    }
+    Ok(())
+}
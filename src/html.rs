@@ -0,0 +1,101 @@
+use crate::annotations::Annotations;
+use crate::scene::Node;
+
+const STYLE: &str = "
+body { font-family: sans-serif; margin: 2em; }
+#search { font-size: 1em; padding: 0.3em; width: 20em; margin-bottom: 1em; }
+ul.tree, ul.tree ul { list-style: none; padding-left: 1.2em; margin: 0; }
+ul.tree { padding-left: 0; }
+summary, .leaf { cursor: default; padding: 0.1em 0; }
+.leaf { padding-left: 1.1em; }
+.type { color: #777; }
+.note { color: #a0309a; font-size: 0.9em; }
+.match > summary .name, .match > .leaf .name { background: #ffe680; }
+.hidden { display: none; }
+table { border-collapse: collapse; }
+th, td { text-align: left; padding: 0.2em 0.8em; border-bottom: 1px solid #ddd; }
+td.number { text-align: right; }
+nav { margin-bottom: 1em; }
+";
+
+const SCRIPT: &str = "
+const search = document.getElementById('search');
+search.addEventListener('input', () => {
+  const text = search.value.trim().toLowerCase();
+  // work from the leaves up, so a parent knows whether a child matched
+  const items = Array.from(document.querySelectorAll('ul.tree li')).reverse();
+  for (const li of items) {
+    const name = li.dataset.name.toLowerCase();
+    const matched = text !== '' && name.includes(text);
+    const childShown = Array.from(li.querySelectorAll(':scope > details > ul > li')).some(c => !c.classList.contains('hidden'));
+    li.classList.toggle('match', matched);
+    li.classList.toggle('hidden', text !== '' && !matched && !childShown);
+    const details = li.querySelector(':scope > details');
+    if (details && text !== '') details.open = childShown;
+  }
+});
+";
+
+/// Writes a scene as a standalone HTML page: a tree whose nodes fold away, a box
+/// to search node names with, and each node's properties and connections in its
+/// tooltip. Annotations follow the nodes they are for.
+pub fn render(root: &Node, flags: u32, annotations: &Annotations) -> String {
+    page(&root.name, &tree(root, flags, annotations))
+}
+
+/// Wraps a body in a complete page with the stylesheet the tree uses.
+pub fn page(title: &str, body: &str) -> String {
+    let mut out = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    out += &format!("<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n", escape(title), STYLE);
+    out += body;
+    out += "</body>\n</html>\n";
+    out
+}
+
+/// The search box, the foldable tree and the script that makes them work.
+pub fn tree(root: &Node, flags: u32, annotations: &Annotations) -> String {
+    let mut out = String::from("<input id=\"search\" type=\"search\" placeholder=\"Search node names\">\n<ul class=\"tree\">\n");
+    item(root, ".", flags, annotations, &mut out);
+    out += &format!("</ul>\n<script>{}</script>\n", SCRIPT);
+    out
+}
+
+fn item(node: &Node, path: &str, flags: u32, annotations: &Annotations, out: &mut String) {
+    let mut tooltip: Vec<String> = Vec::new();
+    if let Some(res) = &node.instance {
+        tooltip.push(format!("instance of {}", res.path));
+    }
+    tooltip.extend(node.parameters.iter().map(|param| format!("{}: {}", param.key, param.val)));
+    tooltip.extend(
+        node.connections
+            .iter()
+            .filter(|c| c.flags & flags == flags)
+            .map(|c| format!("{}() => {}:{}(){}", c.signal, c.to, c.method, c.tags())),
+    );
+    let mut label = match node._type.is_empty() {
+        true => format!("<span class=\"name\">{}</span>", escape(&node.name)),
+        false => format!("<span class=\"name\">{}</span> <span class=\"type\">({})</span>", escape(&node.name), escape(&node._type)),
+    };
+    if let Some(note) = annotations.label(path) {
+        label += &format!(" <span class=\"note\">[{}]</span>", escape(&note));
+    }
+    let title = escape(&tooltip.join("\n")).replace('\n', "&#10;");
+    *out += &format!("<li data-name=\"{}\">", escape(&node.name));
+    if node.children.is_empty() {
+        *out += &format!("<div class=\"leaf\" title=\"{}\">{}</div></li>\n", title, label);
+        return;
+    }
+    *out += &format!("<details open><summary title=\"{}\">{}</summary>\n<ul>\n", title, label);
+    for (name, child) in node.children.iter() {
+        let child_path = match path {
+            "." => name.clone(),
+            _ => format!("{}/{}", path, name),
+        };
+        item(child, &child_path, flags, annotations, out);
+    }
+    *out += "</ul></details></li>\n";
+}
+
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
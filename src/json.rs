@@ -0,0 +1,84 @@
+use serde_json::{json, Value};
+
+use crate::scene::{Connection, ExtResource, Node, Parameter, Scene};
+
+/// The newest layout of the scene document.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// JSON Schemas describing each layout, oldest first.
+pub const SCHEMAS: &[&str] = &[include_str!("../schema/scene-v1.schema.json"), include_str!("../schema/scene-v2.schema.json")];
+
+/// Converts a parsed scene into the JSON document handed to plugins and printed
+/// by --format json, laid out as `version` of the format describes it.
+///
+/// Version 2 adds the scene's ext_resources and sub_resources, each node's
+/// parent, and connection flags.
+pub fn scene(scene: &Scene, version: u32) -> Value {
+    let root = node(&scene.root, ".", version);
+    match version {
+        1 => json!({ "format_version": version, "root": root }),
+        _ => json!({
+            "format_version": version,
+            "root": root,
+            "ext_resources": scene.ext_resources.iter().map(|res| json!({ "id": res.id, "path": res.path, "type": res._type })).collect::<Vec<Value>>(),
+            "sub_resources": scene.sub_resources.iter().map(|res| json!({
+                "id": res.id,
+                "type": res._type,
+                "properties": properties(&res.parameters),
+            })).collect::<Vec<Value>>(),
+        }),
+    }
+}
+
+fn node(node: &Node, path: &str, version: u32) -> Value {
+    let children: Vec<Value> = node
+        .children
+        .iter()
+        .map(|(name, child)| match path {
+            "." => self::node(child, name, version),
+            _ => self::node(child, &format!("{}/{}", path, name), version),
+        })
+        .collect();
+    let mut value = json!({
+        "name": node.name,
+        "type": node._type,
+        "path": path,
+        "instance": node.instance.as_ref().map(resource),
+        "properties": node.parameters.iter().map(|param| json!({
+            "key": param.key,
+            "value": param.val,
+            "resource": param.resource.as_ref().map(resource),
+            "sub_resource": param.sub_type.as_ref().map(|t| sub_resource(t, &param.sub_params)),
+        })).collect::<Vec<Value>>(),
+        "connections": node.connections.iter().map(|conn| connection(conn, version)).collect::<Vec<Value>>(),
+        "children": children,
+    });
+    if version >= 2 {
+        // after the path, which it is the start of
+        value.as_object_mut().unwrap().shift_insert(3, "parent".to_string(), json!(node.parent));
+    }
+    value
+}
+
+fn resource(res: &ExtResource) -> Value {
+    json!({ "path": res.path, "type": res._type })
+}
+
+pub fn sub_resource(_type: &str, params: &[Parameter]) -> Value {
+    json!({
+        "type": _type,
+        "properties": properties(params),
+    })
+}
+
+fn properties(params: &[Parameter]) -> Vec<Value> {
+    params.iter().map(|p| json!({ "key": p.key, "value": p.val })).collect()
+}
+
+fn connection(conn: &Connection, version: u32) -> Value {
+    let mut value = json!({ "signal": conn.signal, "from": conn.from, "to": conn.to, "method": conn.method });
+    if version >= 2 {
+        value["flags"] = json!(conn.flags);
+    }
+    value
+}
@@ -0,0 +1,137 @@
+use std::fmt;
+
+/// What a token of the Godot text resource format is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// One of `[ ] ( ) { } , : =`
+    Punct(char),
+    /// A quoted string, possibly prefixed with `&` (a StringName) or `^` (a
+    /// NodePath). It may run over several lines.
+    String,
+    /// Any other run of characters: names, keys such as `tracks/0/path`, numbers,
+    /// `true`, and the names of constructors like `Vector2`.
+    Word,
+}
+
+/// A token and the bytes of the text it covers.
+#[derive(Clone, Copy, Debug)]
+pub struct Token {
+    pub kind: Kind,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// A syntax error, with the line and column it was found at (both from 1).
+#[derive(Debug)]
+pub struct Error {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(text: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = &text[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// Splits text into tokens, skipping whitespace and `;` comments.
+pub struct Lexer<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text, pos: 0 }
+    }
+
+    fn string(&mut self, start: usize) -> Result<Token, Error> {
+        let mut escaped = false;
+        // skip the opening quote, and the & or ^ before it
+        let body = self.text[self.pos..].find('"').unwrap() + self.pos + 1;
+        for (i, c) in self.text[body..].char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => {
+                    self.pos = body + i + 1;
+                    return Ok(Token { kind: Kind::String, start, end: self.pos });
+                }
+                _ => (),
+            }
+        }
+        Err(Error::new(self.text, start, "string is never closed"))
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Result<Token, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let rest = &self.text[self.pos..];
+            let c = rest.chars().next()?;
+            if c.is_whitespace() {
+                self.pos += c.len_utf8();
+            } else if c == ';' {
+                self.pos += rest.find('\n').unwrap_or(rest.len());
+            } else {
+                break;
+            }
+        }
+        let start = self.pos;
+        let rest = &self.text[start..];
+        let c = rest.chars().next().unwrap();
+        if c == '"' || rest.starts_with("&\"") || rest.starts_with("^\"") {
+            return Some(self.string(start));
+        }
+        if "[](){},:=".contains(c) {
+            self.pos += 1;
+            return Some(Ok(Token { kind: Kind::Punct(c), start, end: self.pos }));
+        }
+        let len = rest.find(|c: char| c.is_whitespace() || "[](){},:=\";".contains(c)).unwrap_or(rest.len());
+        self.pos += len;
+        Some(Ok(Token { kind: Kind::Word, start, end: self.pos }))
+    }
+}
+
+/// Reads the contents of a string token, undoing Godot's escapes.
+pub fn unescape(token: &str) -> String {
+    let inner = token.trim_start_matches(['&', '^']);
+    let inner = &inner[1..inner.len() - 1];
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some('u') => {
+                let hex: String = chars.by_ref().take(4).collect();
+                out.extend(u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32));
+            }
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+    out
+}
@@ -0,0 +1,32 @@
+//! Godot scene parsing, for tools that would rather embed gdtree's parser than
+//! run the binary.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use std::io::BufReader;
+//!
+//! let scene = gdtree::parse_scene(BufReader::new(File::open("main.tscn")?))?;
+//! for (path, node) in scene.root.paths() {
+//!     println!("{} ({})", path, node._type);
+//! }
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+use std::io;
+use std::io::BufRead;
+
+pub mod deprecated;
+pub mod lexer;
+pub mod parser;
+pub mod project;
+pub mod scene;
+pub mod version;
+
+pub use project::Project;
+pub use scene::{Connection, ExtResource, Node, Scene, SubResource};
+
+/// Parses a `.tscn` scene or `.tres` resource. `uid://` references are left as
+/// they are; use `scene::parse_scene` with a `Project` to resolve them.
+pub fn parse_scene(reader: impl BufRead) -> io::Result<Scene> {
+    scene::parse_scene(reader, None)
+}
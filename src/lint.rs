@@ -0,0 +1,280 @@
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::rc::Rc;
+use std::time::Instant;
+
+use rhai::{Array, Dynamic, Engine, Map, Scope, AST};
+
+use crate::budget::Budgets;
+use crate::config::Config;
+use crate::json;
+use crate::owners::Owners;
+use crate::plugin::Plugin;
+use crate::project::{self, Project};
+use crate::scene::{self, Node, Scene};
+use crate::report;
+use crate::timings::Timings;
+use crate::ui;
+
+/// A rule violation reported by a lint script.
+pub struct Finding {
+    pub node: String,
+    pub message: String,
+    pub rule: String,
+}
+
+/// The rhai rules of a directory, compiled, along with the plugins that lint.
+pub struct Linter<'a> {
+    engine: Engine,
+    rules: Vec<(String, AST)>,
+    findings: Rc<RefCell<Vec<(String, String)>>>,
+    /// The scene being checked, for rules that resolve node paths.
+    root: Rc<RefCell<Node>>,
+    plugins: &'a [Plugin],
+    budgets: Budgets,
+}
+
+impl<'a> Linter<'a> {
+    /// Compiles every `*.rhai` rule in `rules_dir`.
+    pub fn new(rules_dir: &Path, plugins: &'a [Plugin], budgets: Budgets) -> io::Result<Self> {
+        let findings: Rc<RefCell<Vec<(String, String)>>> = Rc::new(RefCell::new(Vec::new()));
+        let mut engine = Engine::new();
+        let sink = findings.clone();
+        engine.register_fn("report", move |node: &str, message: &str| {
+            sink.borrow_mut().push((node.to_string(), message.to_string()));
+        });
+        let root = Rc::new(RefCell::new(Node::new("")));
+        let scene = root.clone();
+        engine.register_fn("resolve", move |from: &str, path: &str| scene.borrow().resolve(from, path).unwrap_or_default());
+
+        let mut rules: Vec<(String, AST)> = Vec::new();
+        for file in project::list_files(rules_dir, &["rhai"]) {
+            let name = file.file_stem().unwrap_or_default().to_string_lossy().to_string();
+            let ast = engine
+                .compile(fs::read_to_string(&file)?)
+                .map_err(|err| io::Error::other(format!("{}: {}", file.display(), err)))?;
+            rules.push((name, ast));
+        }
+        Ok(Self { engine, rules, findings, root, plugins, budgets })
+    }
+
+    /// How many rules there are, counting each plugin with a lint hook as one,
+    /// the scene budgets as another, and the built-in UI and connection checks as a third.
+    pub fn rule_count(&self) -> usize {
+        self.rules.len() + self.plugins.iter().filter(|p| p.lints()).count() + usize::from(!self.budgets.is_empty()) + 1
+    }
+
+    /// Runs the rules against one scene.
+    pub fn check(&self, scene: &Scene, scene_path: &str, format_version: u32) -> io::Result<Vec<Finding>> {
+        let mut scene_findings = self.check_scripts(scene, scene_path)?;
+        scene_findings.extend(self.check_plugins(scene, format_version));
+        scene_findings.extend(self.check_builtin(scene, scene_path));
+        Ok(scene_findings)
+    }
+
+    /// Runs the rhai rules against one scene.
+    fn check_scripts(&self, scene: &Scene, scene_path: &str) -> io::Result<Vec<Finding>> {
+        if self.rules.is_empty() {
+            return Ok(Vec::new());
+        }
+        let (nodes, connections) = scene_values(&scene.root);
+        *self.root.borrow_mut() = scene.root.clone();
+        let mut scene_findings = Vec::new();
+        for (rule, ast) in self.rules.iter() {
+            let mut scope = Scope::new();
+            scope.push_constant("scene", scene_path.to_string());
+            scope.push_constant("nodes", nodes.clone());
+            scope.push_constant("connections", connections.clone());
+            self.engine
+                .run_ast_with_scope(&mut scope, ast)
+                .map_err(|err| io::Error::other(format!("rule {} failed on {}: {}", rule, scene_path, err)))?;
+            scene_findings.extend(self.findings.borrow_mut().drain(..).map(|(node, message)| Finding {
+                node,
+                message,
+                rule: rule.clone(),
+            }));
+        }
+        Ok(scene_findings)
+    }
+
+    /// Runs the lint hooks of the plugins against one scene.
+    fn check_plugins(&self, scene: &Scene, format_version: u32) -> Vec<Finding> {
+        let mut scene_findings = Vec::new();
+        if self.plugins.iter().any(|p| p.lints()) {
+            let document = json::scene(scene, format_version);
+            for plugin in self.plugins.iter() {
+                for (node, message) in plugin.lint(&document).unwrap_or_default() {
+                    scene_findings.push(Finding { node, message, rule: plugin.name.clone() });
+                }
+            }
+        }
+        scene_findings
+    }
+
+    /// Checks one scene against its budget and the built-in UI and connection checks.
+    fn check_builtin(&self, scene: &Scene, scene_path: &str) -> Vec<Finding> {
+        let mut scene_findings = Vec::new();
+        for message in self.budgets.for_scene(scene_path).check(scene) {
+            scene_findings.push(Finding { node: ".".to_string(), message, rule: "budget".to_string() });
+        }
+        for (node, message) in ui::check(&scene.root) {
+            scene_findings.push(Finding { node, message, rule: "ui".to_string() });
+        }
+        for (path, node) in scene.root.paths() {
+            for conn in node.connections.iter() {
+                if let Some(missing) = &conn.missing {
+                    let message = format!("connection of {} to {}() leads to {}, which is not in the scene", conn.signal, conn.method, missing);
+                    scene_findings.push(Finding { node: path.clone(), message, rule: "connections".to_string() });
+                }
+                if let Some(script) = &conn.missing_method {
+                    let message = format!("connection of {} calls {}() on {}, but {} has no such function", conn.signal, conn.method, conn.to, script);
+                    scene_findings.push(Finding { node: path.clone(), message, rule: "connections".to_string() });
+                }
+            }
+        }
+        scene_findings
+    }
+}
+
+/// Runs every `*.rhai` rule in `rules_dir`, the lint hook of every plugin, the
+/// scene budgets and the built-in UI and connection checks against the scenes at `path`, printing the findings and returning how many
+/// there were.
+///
+/// Each rule sees the scene as `nodes`, an array of maps with `name`, `type`,
+/// `path`, `parent`, `ancestor_types`, `instance`, `script`, `properties`,
+/// `connections` and `dropped`, plus a flat `connections` array and the `scene`
+/// path, and calls `report(node_path, message)` for anything it objects to.
+/// `resolve(from, path)` resolves a node path from the node at `from` as Godot
+/// would, giving the path of the node it leads to, or "" when there is none.
+pub fn run(path: &Path, rules_dir: &Path, project: Option<&Project>, plugins: &[Plugin], config: &Config, format_version: u32, timings: &mut Timings) -> io::Result<usize> {
+    let budgets = config.budgets();
+    let owners = Owners::load(project, &config.owners)?;
+    let linter = Linter::new(rules_dir, plugins, budgets.clone())?;
+    timings.lap("compile rules");
+
+    let files = match path.is_dir() {
+        true => project::list_files(path, &["tscn"]),
+        false => vec![path.to_path_buf()],
+    };
+    // scenes are parsed and checked on rayon's threads, each with its own copy
+    // of the rules since rhai's engine stays on the thread it was made on;
+    // plugins aren't known to be thread-safe, so they run here
+    let mut total = 0;
+    let mut by_owner: Vec<(String, usize)> = Vec::new();
+    let mut failed = None;
+    report::stream(
+        &files,
+        || Linter::new(rules_dir, &[], budgets.clone()),
+        |worker, file| {
+            // budgets are keyed by res:// path, which needs the absolute path to work out
+            let scene_path = project
+                .and_then(|p| p.res_path(&fs::canonicalize(file).unwrap_or(file.clone())))
+                .unwrap_or_else(|| file.display().to_string());
+            let start = Instant::now();
+            let scene = File::open(file).and_then(|f| scene::parse_scene(BufReader::new(f), project))?;
+            let parsed = start.elapsed();
+            let worker = worker.as_ref().map_err(|err| io::Error::new(err.kind(), err.to_string()))?;
+            let start = Instant::now();
+            let scripts = worker.check_scripts(&scene, &scene_path)?;
+            let builtin = worker.check_builtin(&scene, &scene_path);
+            Ok((scene_path, scene, scripts, builtin, parsed, start.elapsed()))
+        },
+        |file, checked: io::Result<_>| {
+            if failed.is_some() {
+                return;
+            }
+            let (scene_path, scene, scripts, builtin, parsed, linted) = match checked {
+                Ok(checked) => checked,
+                Err(err) => {
+                    failed = Some(err);
+                    return;
+                }
+            };
+            let start = Instant::now();
+            let mut scene_findings = scripts;
+            scene_findings.extend(linter.check_plugins(&scene, format_version));
+            scene_findings.extend(builtin);
+            timings.record(format!("parse {}", file.display()), parsed);
+            timings.record(format!("lint {}", scene_path), linted + start.elapsed());
+            // the owners go last, where CI can pick them out to notify
+            let scene_owners = owners.of(&scene_path);
+            let routing = match scene_owners.is_empty() {
+                true => String::new(),
+                false => format!(" {}", scene_owners.join(" ")),
+            };
+            for finding in scene_findings.iter() {
+                println!("{}: {}: {} [{}]{}", scene_path, finding.node, finding.message, finding.rule, routing);
+            }
+            if !scene_findings.is_empty() {
+                for owner in scene_owners.iter().map(String::as_str).chain(scene_owners.is_empty().then_some("unowned")) {
+                    match by_owner.iter_mut().find(|(o, _)| *o == owner) {
+                        Some((_, count)) => *count += scene_findings.len(),
+                        None => by_owner.push((owner.to_string(), scene_findings.len())),
+                    }
+                }
+            }
+            total += scene_findings.len();
+        },
+    );
+    if let Some(err) = failed {
+        return Err(err);
+    }
+    if !owners.is_empty() && !by_owner.is_empty() {
+        let counts: Vec<String> = by_owner.iter().map(|(owner, count)| format!("{} {}", owner, count)).collect();
+        println!("problems by owner: {}", counts.join(", "));
+    }
+    println!("{} problems in {} scenes checked by {} rules", total, files.len(), linter.rule_count());
+    Ok(total)
+}
+
+/// Converts a scene into the `nodes` and `connections` arrays rules work on.
+fn scene_values(root: &Node) -> (Array, Array) {
+    let mut nodes = Array::new();
+    let mut connections = Array::new();
+    for (path, node) in root.paths() {
+        let parent = match path.rsplit_once('/') {
+            Some((parent, _)) => parent.to_string(),
+            None if path == "." => String::new(),
+            None => ".".to_string(),
+        };
+        let mut ancestor_types = Array::new();
+        let mut ancestor = root;
+        if path != "." {
+            ancestor_types.push(root._type.clone().into());
+            for name in parent.split('/').filter(|n| *n != "." && !n.is_empty()) {
+                ancestor = &ancestor.children[name];
+                ancestor_types.push(ancestor._type.clone().into());
+            }
+        }
+        let mut properties = Map::new();
+        for param in node.parameters.iter() {
+            properties.insert(param.key.as_str().into(), param.val.clone().into());
+        }
+        let mut node_connections = Array::new();
+        for conn in node.connections.iter() {
+            let mut map = Map::new();
+            map.insert("signal".into(), conn.signal.clone().into());
+            map.insert("from".into(), conn.from.clone().into());
+            map.insert("to".into(), conn.to.clone().into());
+            map.insert("method".into(), conn.method.clone().into());
+            map.insert("flags".into(), (conn.flags as i64).into());
+            node_connections.push(map.clone().into());
+            connections.push(map.into());
+        }
+        let mut map = Map::new();
+        map.insert("name".into(), node.name.clone().into());
+        map.insert("type".into(), node._type.clone().into());
+        map.insert("path".into(), path.clone().into());
+        map.insert("parent".into(), parent.into());
+        map.insert("ancestor_types".into(), ancestor_types.into());
+        map.insert("instance".into(), node.instance.as_ref().map_or(String::new(), |r| r.path.clone()).into());
+        map.insert("script".into(), node.script().map_or(String::new(), |r| r.path.clone()).into());
+        map.insert("properties".into(), properties.into());
+        map.insert("connections".into(), node_connections.into());
+        map.insert("dropped".into(), node.dropped.into());
+        nodes.push(Dynamic::from_map(map));
+    }
+    (nodes, connections)
+}
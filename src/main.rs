@@ -1,294 +1,1721 @@
-use clap::Parser;
-use indexmap::IndexMap;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use regex::Regex;
+use std::borrow::Cow;
 use std::io;
 use std::io::prelude::*;
 use std::io::BufReader;
+use std::io::IsTerminal;
+use std::fs;
 use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::SystemTime;
+
+use gdtree::{deprecated, parser, project, scene, version};
+
+mod actions;
+mod annotations;
+mod apply;
+mod autoloads;
+mod badge;
+mod blame;
+mod budget;
+mod buses;
+mod check;
+mod bookmarks;
+mod classdb;
+mod color;
+mod comment;
+mod config;
+mod conflicts;
+mod coverage;
+mod diff;
+mod dot;
+mod expect;
+mod files;
+mod git;
+mod grep;
+mod history;
+mod html;
+mod json;
+mod lint;
+mod matrix;
+mod media;
+mod mermaid;
+mod migrate;
+mod orphans;
+mod owners;
+mod pager;
+mod plugin;
+mod preview;
+mod report;
+mod serve;
+mod site;
+mod snippet;
+mod template;
+mod timings;
+mod tui;
+mod ui;
+mod units;
+mod viewports;
+mod watch;
+mod world;
+mod zorder;
+
+use project::Project;
+use bookmarks::Bookmarks;
+use config::Config;
+use annotations::Annotations;
+use classdb::ClassDb;
+use color::Palette;
+use owners::Owners;
+use pager::Pager;
+use plugin::Plugin;
+use timings::Timings;
+use scene::{Connection, ExtResource, Node, NodeParameter, Parameter, Scene};
+use version::GodotVersion;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Cli {
-    file: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Scene or resource files to print, or directories to search for them; - reads a scene from stdin
+    #[arg(required = true, value_name = "FILE")]
+    files: Vec<String>,
+    /// Godot project directory that res:// paths are relative to
+    /// (defaults to the nearest parent directory containing project.godot)
+    #[arg(long, visible_alias = "project", value_name = "DIR", global = true)]
+    project_root: Option<PathBuf>,
+    /// Load a plugin library providing output formats, resource decoders or lint rules
+    #[arg(long = "plugin", value_name = "LIB", global = true)]
+    plugins: Vec<PathBuf>,
+    /// Output format: tree, comment (the tree as a source comment), matrix (signal connections
+    /// between nodes as a grid), json (the parsed scene, as gdtree schema describes it), dot
+    /// (a Graphviz digraph of the nodes and connections), mermaid (the same as a Mermaid
+    /// flowchart), html (a page with a foldable, searchable tree), or one provided by a plugin
+    #[arg(long, default_value = "tree")]
+    format: String,
+    /// Comment syntax for --format comment
+    #[arg(long, value_name = "STYLE", default_value = "gdscript")]
+    comment_style: comment::CommentStyle,
+    /// Color node names, types, resource paths and connections in the tree: auto, always or never
+    #[arg(long, value_name = "WHEN", default_value = "auto")]
+    color: color::ColorChoice,
+    /// Layout of --format matrix
+    #[arg(long, value_name = "STYLE", default_value = "text")]
+    matrix_style: matrix::MatrixStyle,
+    /// Report on stderr how long parsing, resolving, filtering and rendering took, per scene for project-wide commands
+    #[arg(long, global = true)]
+    timings: bool,
+    /// Parse the scenes of project-wide commands on this many threads, instead of one per CPU
+    #[arg(long, value_name = "N", global = true, value_parser = clap::value_parser!(u32).range(1..))]
+    threads: Option<u32>,
+    /// Layout version of JSON documents, for consumers that expect an older one
+    #[arg(long, value_name = "N", global = true, default_value_t = json::FORMAT_VERSION)]
+    #[arg(value_parser = clap::value_parser!(u32).range(1..=json::FORMAT_VERSION as i64))]
+    format_version: u32,
+    /// Show res:// paths as filesystem paths under the project root
+    #[arg(long)]
+    absolute_paths: bool,
+    /// Note whether each referenced resource exists, with its size and modification time
+    #[arg(long)]
+    check_files: bool,
+    /// Show radian properties in degrees too, and file sizes in KiB, MiB and so on
+    #[arg(long)]
+    units: bool,
+    /// Report which nodes have scripts attached instead of printing the tree
+    #[arg(long)]
+    script_coverage: bool,
+    /// Follow each node type with its one-line description from the Godot docs
+    #[arg(long)]
+    describe: bool,
+    /// Follow each node type with the classes it inherits from
+    #[arg(long)]
+    inheritance: bool,
+    /// Mark properties set to their class default, or unknown to the class
+    #[arg(long)]
+    check_properties: bool,
+    /// Print the plain tree the same way whatever the project and config, for git's textconv; a scene that fails to parse is printed as it is
+    #[arg(long, conflicts_with_all = [
+        "absolute_paths", "check_files", "units", "weights", "tool_scripts", "describe", "inheritance", "check_properties",
+        "no_properties", "annotations", "depth", "filter", "regex", "expand_instances", "bookmark", "watch", "format", "project_root",
+    ])]
+    git_textconv: bool,
+    /// Print only the signal connections, as a flat list sorted by the emitting node, instead of the tree
+    #[arg(long)]
+    connections_only: bool,
+    /// List the 2D nodes in the order they are drawn, by canvas layer and effective z index, instead of the tree
+    #[arg(long)]
+    draw_order: bool,
+    /// List the 3D nodes with their world positions, composing each transform with its ancestors', instead of the tree
+    #[arg(long)]
+    world_transforms: bool,
+    /// Tabulate the cameras, viewports and canvas layers with their settings instead of the tree
+    #[arg(long)]
+    viewports: bool,
+    /// Report which Godot version wrote the scene, and the evidence for it, instead of the tree
+    #[arg(long)]
+    detect_version: bool,
+    /// Show only the connections that have all of these flags
+    #[arg(long, value_name = "FLAGS", value_delimiter = ',')]
+    connection_flags: Vec<ConnectionFlag>,
+    /// Keep running and print the tree again each time the scene is saved
+    #[arg(long)]
+    watch: bool,
+    /// With --watch, print only what changed since the last save instead of the whole tree
+    #[arg(long, requires = "watch")]
+    diff: bool,
+    /// With --diff, sum the changes up in a sentence instead of listing them
+    #[arg(long, requires = "diff")]
+    summary: bool,
+    /// Follow each node with the bytes of the file its properties and sub_resources take up
+    #[arg(long)]
+    weights: bool,
+    /// With --weights, list the N heaviest nodes instead of the tree
+    #[arg(long, value_name = "N", requires = "weights")]
+    top: Option<usize>,
+    /// Read each node's attached script and mark the tool scripts, which run inside the editor
+    #[arg(long)]
+    tool_scripts: bool,
+    /// Print only the nodes whose name matches this pattern, where * matches any run of characters and ? any one, and their ancestors
+    #[arg(long, value_name = "PATTERN", conflicts_with = "regex")]
+    filter: Option<String>,
+    /// Print only the nodes whose name matches this regular expression, and their ancestors
+    #[arg(long, value_name = "REGEX", value_parser = Regex::new)]
+    regex: Option<Regex>,
+    /// Print each node's properties, even when the project's config makes --no-properties the default
+    #[arg(long, overrides_with = "no_properties")]
+    properties: bool,
+    /// Print only the nodes, instances and connections, leaving out the properties
+    #[arg(long, overrides_with = "properties")]
+    no_properties: bool,
+    /// Read only the [node] headers, skipping properties, resources and connections, for the hierarchy of many scenes as fast as it can be had
+    #[arg(long, conflicts_with_all = [
+        "properties", "git_textconv", "expand_instances", "check_files", "weights", "tool_scripts", "check_properties", "script_coverage",
+        "draw_order", "world_transforms", "viewports", "connections_only", "top",
+    ])]
+    structure_only: bool,
+    /// Draw the tree with |-- and `-- instead of box-drawing characters, for terminals and logs that mangle them
+    #[arg(long)]
+    ascii: bool,
+    /// Print straight to the terminal even when the tree is taller than it, instead of through $PAGER
+    #[arg(long)]
+    no_pager: bool,
+    /// Show the notes a JSON file keeps for node paths beside the nodes, in the tree and --format html
+    /// (defaults to the scene's sidecar, such as level.tscn.annotations.json, when there is one)
+    #[arg(long, value_name = "FILE")]
+    annotations: Option<PathBuf>,
+    /// Print the tree only down to this many levels below the root, like tree -L
+    #[arg(short = 'L', long, value_name = "N")]
+    depth: Option<usize>,
+    /// Open the scenes that nodes instance and show their nodes beneath them, as Godot builds the tree at runtime
+    #[arg(long, visible_alias = "recursive")]
+    expand_instances: bool,
+    /// Print only the subtree under a node bookmarked with gdtree bookmark add
+    #[arg(long, value_name = "NAME")]
+    bookmark: Option<String>,
+    /// Print a line fetching each node from the root's script instead of the tree
+    #[arg(long, value_name = "LANGUAGE")]
+    emit_snippet: Option<snippet::Language>,
+    /// Write the tree, or the --format export, to this file instead of standard output
+    #[arg(short, long, value_name = "FILE", conflicts_with_all = [
+        "watch", "git_textconv", "detect_version", "script_coverage", "draw_order", "world_transforms", "viewports", "connections_only", "top", "emit_snippet",
+    ])]
+    output: Option<PathBuf>,
 }
 
-#[derive(Debug, Clone)]
-struct ExtResource {
-    path: String,
-    _type: String,
+#[derive(Subcommand)]
+enum Command {
+    /// Show which scenes connect to or emit the signals of autoload singletons
+    Autoloads {
+        /// Project directory, or any path inside it
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// List the input actions scripts use but project.godot doesn't define, and those it defines but no script uses
+    InputActions {
+        /// Project directory, or any path inside it
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Cross-check the buses audio players use with those of the project's bus layout
+    AudioBuses {
+        /// Project directory, or any path inside it
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// List nodes whose classes were renamed or removed in Godot 4
+    Deprecated {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Check that scenes are well formed: sections, ExtResource and SubResource ids, parents and sibling names, exiting 1 on any problem
+    Check {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// List node properties renamed in Godot 4, or rename them with --write
+    MigrateProperties {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Rename the properties in the scene files instead of only listing them
+        #[arg(long)]
+        write: bool,
+    },
+    /// Check scenes against the Rhai rules in the project's .gdtree/rules directory
+    Lint {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Directory of *.rhai rules to run instead of the project's
+        #[arg(long, value_name = "DIR")]
+        rules: Option<PathBuf>,
+    },
+    /// Make the renames, repaths, deletions and property changes listed in a YAML or JSON plan, all or none of them
+    Apply {
+        /// Plan file, with an `operations` list of `rename`, `repath`, `set`, `delete` and connection entries
+        plan: PathBuf,
+        /// Print the edits without writing any scene
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Write a scene from a template, filling in its {{name}} placeholders
+    New {
+        /// Scene template, a .tscn with {{name}} placeholders in node names, paths or values
+        #[arg(long, value_name = "FILE")]
+        template: PathBuf,
+        /// Value of a placeholder; repeat for each
+        #[arg(long = "var", value_name = "NAME=VALUE", value_parser = template::parse_var)]
+        vars: Vec<(String, String)>,
+        /// File to write instead of standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Add a signal connection to a scene, or remove or retarget its existing ones
+    Reconnect {
+        /// Scene file
+        file: PathBuf,
+        /// Signal of the connection
+        #[arg(long)]
+        signal: String,
+        /// Path of the node emitting the signal, `.` for the root
+        #[arg(long, value_name = "NODE")]
+        from: String,
+        /// Path of the node receiving it; required to add a connection, and narrows the others
+        #[arg(long, value_name = "NODE")]
+        to: Option<String>,
+        /// Method it calls; required to add a connection, and narrows the others
+        #[arg(long)]
+        method: Option<String>,
+        /// Remove the connections instead of adding one
+        #[arg(long, conflicts_with_all = ["new_to", "new_method"])]
+        remove: bool,
+        /// Point the connections at this node instead of adding one
+        #[arg(long, value_name = "NODE")]
+        new_to: Option<String>,
+        /// Make the connections call this method instead of adding one
+        #[arg(long, value_name = "METHOD")]
+        new_method: Option<String>,
+        /// Print the edit without writing the scene
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Print a JSON summary of a scene's node count, lint status and complexity as a shields.io endpoint badge
+    Badge {
+        /// Scene file
+        file: PathBuf,
+        /// Directory of *.rhai rules to run instead of the project's
+        #[arg(long, value_name = "DIR")]
+        rules: Option<PathBuf>,
+        /// Print every measure instead of the badge
+        #[arg(long)]
+        summary: bool,
+    },
+    /// Write a scene's node paths and types to a file that verify-expect checks the scene against
+    Expect {
+        /// Scene file
+        file: PathBuf,
+        /// File to write instead of standard output
+        #[arg(short, long, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Check that scenes still have the node paths listed in files written by gdtree expect
+    VerifyExpect {
+        /// Expectations files, each naming the scene it was written from
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Compare two scene files node by node: nodes added, removed, moved or retyped, and properties and connections changed
+    Diff {
+        /// Scene before the changes
+        old: PathBuf,
+        /// Scene after the changes
+        new: PathBuf,
+        /// Sum the changes up in a sentence instead of listing them
+        #[arg(long)]
+        summary: bool,
+    },
+    /// Explain the merge conflicts git left in a scene: the node each falls in and how the two sides differ, property by property
+    Conflicts {
+        /// Scene file with conflict markers
+        file: PathBuf,
+    },
+    /// Show the commits that added a node and last changed each of its properties, from the scene's git history
+    Blame {
+        /// Scene file, in a git repository
+        file: PathBuf,
+        /// Node path relative to the root, such as Player/Sprite2D
+        node: String,
+    },
+    /// Keep the project's parsed scenes in memory and hand them out over a socket in .gdtree, for editor integrations and scripts to share
+    Serve {
+        /// Project directory, or any path inside it
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Browse a scene in the terminal: fold the tree, see a node's properties and connections, search names and open instances
+    Tui {
+        /// Scene file
+        file: PathBuf,
+    },
+    /// Chart a scene's node count, depth and size across its git history, to show when it grew
+    History {
+        /// Scene file, in a git repository
+        file: PathBuf,
+        /// Show at most this many commits, spread evenly from the first to the latest
+        #[arg(long, value_name = "N", default_value_t = 20, value_parser = clap::value_parser!(u32).range(2..))]
+        samples: u32,
+    },
+    /// Tabulate the node count, file size, tree depth, incoming references, resources and connections of each scene
+    Stats {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Print the measures, with node counts per type, as a JSON array
+        #[arg(long, conflicts_with = "ndjson")]
+        json: bool,
+        /// Print the measures as --json does, but as one JSON object per line, written out as it goes
+        #[arg(long)]
+        ndjson: bool,
+        #[command(flatten)]
+        report: report::ReportArgs,
+    },
+    /// Write a static HTML site about the project: an index of scene stats, each scene's tree, the dependencies and the lint findings
+    Report {
+        /// Project directory, or any path inside it
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Directory to write the site into
+        #[arg(short, long, value_name = "DIR")]
+        output: PathBuf,
+        /// Directory of rhai lint rules (defaults to .gdtree/rules under the project root)
+        #[arg(long, value_name = "DIR")]
+        rules: Option<PathBuf>,
+    },
+    /// List the project's scenes that are never instanced, autoloaded or run as the main scene
+    Orphans {
+        /// Project directory, or any path inside it
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// List the resources each scene loads
+    Deps {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// List only the resources of this kind
+        #[arg(long, value_enum, default_value_t = report::Kind::All)]
+        kind: report::Kind,
+        /// Print the list, or a Graphviz digraph grouping each directory's files into a cluster
+        #[arg(long, value_enum, default_value_t = report::DepsFormat::Text)]
+        format: report::DepsFormat,
+        /// Follow the scenes each scene instances, listing their resources too
+        #[arg(short, long)]
+        recursive: bool,
+        /// Print the chains of instanced scenes leading from this scene to the one given with --to instead
+        #[arg(long, value_name = "SCENE", requires = "to")]
+        from: Option<PathBuf>,
+        /// Scene the chains from --from lead to
+        #[arg(long, value_name = "SCENE", requires = "from")]
+        to: Option<PathBuf>,
+        #[command(flatten)]
+        report: report::ReportArgs,
+    },
+    /// List the nodes whose name or type matches a pattern
+    Find {
+        /// Scene file, or directory to search for scenes
+        path: PathBuf,
+        /// Node name or type, where * matches any run of characters and ? any one
+        pattern: String,
+        /// Print only the node paths, one per line, for scripts and get_node calls; with several scenes each follows its scene and a tab
+        #[arg(long)]
+        paths_only: bool,
+        #[command(flatten)]
+        report: report::ReportArgs,
+    },
+    /// List the 3D lights of each scene with their energy, shadows and range, scenes with the most shadows first
+    Lights {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// List the physics bodies and areas of each scene with their layers, masks and collision shapes, marking those with none
+    Physics {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Search the property values of scenes and resources for a string, such as a resource path
+    GrepValue {
+        /// Text to look for in the values as written, or in the paths of the resources they load
+        pattern: String,
+        /// Scene or resource file, or directory to search
+        #[arg(default_value = ".")]
+        path: PathBuf,
+    },
+    /// Count how often each value of a node property appears across the scenes
+    Values {
+        /// Scene file, or directory to search for scenes
+        #[arg(default_value = ".")]
+        path: PathBuf,
+        /// Property to count, such as texture_filter or z_index
+        #[arg(long, value_name = "PROPERTY")]
+        key: String,
+    },
+    /// Print the node paths of a scene that start with a prefix, for shell completion
+    CompletePath {
+        /// Scene file
+        file: PathBuf,
+        /// Start of a node path relative to the root, or of a %UniqueName
+        #[arg(default_value = "")]
+        prefix: String,
+    },
+    /// Manage the cached Godot class references used by --describe, --inheritance and --check-properties
+    Classdb {
+        #[command(subcommand)]
+        action: ClassdbAction,
+    },
+    /// Save, list or delete named node paths of a scene for --bookmark
+    Bookmark {
+        #[command(subcommand)]
+        action: BookmarkAction,
+    },
+    /// Print the JSON Schema of the scene document selected by --format-version
+    Schema {
+        /// Print the schema of each scene's stats, as stats --json and --ndjson print them, instead
+        #[arg(long)]
+        stats: bool,
+    },
 }
 
-impl ExtResource {
-    fn new(path: String, _type: String) -> Self {
-        Self {
-            path: path,
-            _type: _type,
+#[derive(Subcommand)]
+enum ClassdbAction {
+    /// Download the class reference of a Godot release
+    Fetch {
+        /// Godot version, such as 4.2 or 4.3-rc1
+        version: String,
+        /// URL or local path of the engine source .tar.gz to read instead of the release on GitHub
+        #[arg(long, value_name = "URL|FILE")]
+        source: Option<String>,
+    },
+    /// List the Godot versions whose class reference is cached
+    List,
+    /// Delete the cached class reference of a Godot version
+    Remove {
+        version: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum BookmarkAction {
+    /// Save a node path of a scene under a name
+    Add {
+        /// Scene file
+        file: PathBuf,
+        name: String,
+        /// Node path relative to the root
+        node: String,
+    },
+    /// List the saved bookmarks, of one scene or all of them
+    List {
+        /// Scene file
+        file: Option<PathBuf>,
+    },
+    /// Delete a bookmark
+    Remove {
+        /// Scene file
+        file: PathBuf,
+        name: String,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ConnectionFlag {
+    Deferred,
+    Oneshot,
+    ReferenceCounted,
+}
+
+impl ConnectionFlag {
+    fn bit(self) -> u32 {
+        match self {
+            ConnectionFlag::Deferred => scene::CONNECT_DEFERRED,
+            ConnectionFlag::Oneshot => scene::CONNECT_ONE_SHOT,
+            ConnectionFlag::ReferenceCounted => scene::CONNECT_REFERENCE_COUNTED,
         }
     }
 }
 
-#[derive(Debug)]
-struct SubResource {
-    _type: String,
-    parameters: Vec<Parameter>,
+/// Display settings shared by every level of the tree walk.
+struct Options<'a> {
+    project: Option<&'a Project>,
+    plugins: &'a [Plugin],
+    classdb: Option<ClassDb>,
+    absolute_paths: bool,
+    check_files: bool,
+    describe: bool,
+    inheritance: bool,
+    check_properties: bool,
+    units: bool,
+    weights: bool,
+    tool_scripts: bool,
+    /// Whether to print node properties, or only the structure.
+    properties: bool,
+    depth: Option<usize>,
+    /// Flag bits a connection needs to be shown.
+    connection_flags: u32,
+    /// Which Godot wrote the scene, for reading settings whose meaning changed.
+    version: GodotVersion,
+    color: Palette,
+    glyphs: &'static Glyphs,
+    /// Notes from --annotations or the scene's sidecar file.
+    annotations: Annotations,
+    res_path_re: Regex,
+    tool_re: Regex,
 }
 
-impl SubResource {
-    fn new(_type: String) -> Self {
+impl<'a> Options<'a> {
+    fn new(cli: &Cli, config: &Config, project: Option<&'a Project>, plugins: &'a [Plugin], classdb: Option<ClassDb>, version: GodotVersion, annotations: Annotations) -> Self {
         Self {
-            _type: _type,
-            parameters: Vec::new(),
+            project,
+            plugins,
+            classdb,
+            absolute_paths: cli.absolute_paths,
+            check_files: cli.check_files,
+            describe: cli.describe,
+            inheritance: cli.inheritance,
+            check_properties: cli.check_properties,
+            units: cli.units,
+            weights: cli.weights,
+            tool_scripts: cli.tool_scripts,
+            properties: cli.properties || !(cli.no_properties || config.structure_only),
+            depth: cli.depth,
+            connection_flags: cli.connection_flags.iter().fold(0, |bits, flag| bits | flag.bit()),
+            version,
+            // only the plain tree goes to the terminal as it is; a file
+            // written with --output gets colors only when asked for
+            color: match (cli.format.as_str(), cli.color) {
+                ("tree", _) if cli.git_textconv => Palette::off(),
+                ("tree", color::ColorChoice::Auto) if cli.output.is_some() => Palette::off(),
+                ("tree", choice) => Palette::new(choice),
+                _ => Palette::off(),
+            },
+            glyphs: if cli.ascii { &ASCII } else { &BOX },
+            annotations,
+            res_path_re: Regex::new(r#"res://[^"]*"#).unwrap(),
+            // @tool in Godot 4, a leading tool line in Godot 3, and [Tool] in C#
+            tool_re: Regex::new(r"(?m)^(?:@tool\b|tool\s*$|tool\s+extends\b|\s*\[Tool\])").unwrap(),
+        }
+    }
+
+    /// Rewrites any res:// paths in a displayed value according to the options.
+    fn paths<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        match (self.project, self.absolute_paths) {
+            (Some(project), true) => self.res_path_re.replace_all(text, |caps: &regex::Captures| {
+                project.resolve(&caps[0]).to_string_lossy().to_string()
+            }),
+            _ => Cow::Borrowed(text),
+        }
+    }
+
+    /// Formats a referenced resource's path, plus the family of a font file and
+    /// its file status under --check-files.
+    fn resource(&self, res: &ExtResource) -> String {
+        let Some(project) = self.project else {
+            return self.color.path(&self.paths(&res.path));
+        };
+        let path = project.resolve(&res.path);
+        let mut text = self.color.path(&self.paths(&res.path));
+        if let Some(family) = media::font_family(&path) {
+            text += &format!(" ({})", family);
+        }
+        if self.check_files {
+            text += &format!(" [{}]", files::describe(&path, self.units));
+        }
+        text
+    }
+
+    /// The ` [owner: combat]` following a node that --annotations has notes for.
+    fn annotation(&self, path: &str) -> String {
+        match self.annotations.label(path) {
+            Some(label) => format!(" {}", self.color.note(&format!("[{}]", label))),
+            None => String::new(),
+        }
+    }
+
+    /// The ` — description` following a node of the given type, under --describe.
+    fn description(&self, _type: &str) -> String {
+        match self.classdb.as_ref().filter(|_| self.describe).and_then(|db| db.brief(_type)) {
+            Some(brief) => format!(" — {}", brief),
+            None => String::new(),
+        }
+    }
+
+    /// The ` [size]` following a node under --weights: the bytes its section and
+    /// sub_resources take, and with children, those of its whole subtree.
+    fn weight(&self, node: &Node) -> String {
+        match (self.weights, node.children.is_empty()) {
+            (false, _) => String::new(),
+            (true, true) => format!(" [{}]", units::bytes(node.weight)),
+            (true, false) => format!(" [{}, {} with children]", units::bytes(node.weight), units::bytes(node.total_weight())),
         }
     }
-}
 
-#[derive(Debug, Clone)]
-struct Parameter {
-    key: String,
-    val: String,
+    /// The ` (Type)` following a node's name, spelled out as `(Type < Base < Object)`
+    /// under --inheritance.
+    fn type_label(&self, name: &str, _type: &str) -> String {
+        match (&self.classdb, self.inheritance, _type) {
+            (_, _, "") => String::new(),
+            (Some(db), true, _) => format!(" {}", self.color.type_name(&format!("({})", db.ancestry(_type).join(" < ")))),
+            _ if name == _type => String::new(),
+            _ => format!(" {}", self.color.type_name(&format!("({})", _type))),
+        }
+    }
+
+    /// The ` [tool]` following a node whose script runs in the editor, under --tool-scripts.
+    fn tool(&self, node: &Node) -> &'static str {
+        let (true, Some(script)) = (self.tool_scripts, node.script()) else {
+            return "";
+        };
+        let path = match self.project {
+            Some(project) => project.resolve(&script.path),
+            None => PathBuf::from(&script.path),
+        };
+        match fs::read_to_string(path) {
+            Ok(source) if self.tool_re.is_match(&source) => " [tool]",
+            _ => "",
+        }
+    }
+
+    /// The ` (90°)` following a radian-valued property, under --units.
+    fn angle(&self, param: &NodeParameter) -> String {
+        match self.units && param.sub_type.is_none() && param.resource.is_none() {
+            true => units::angle(&param.key, &param.val).unwrap_or_default(),
+            false => String::new(),
+        }
+    }
+
+    /// The ` [default]` or ` [unknown property]` note after a property, under --check-properties.
+    fn property_note(&self, node: &Node, param: &NodeParameter) -> &'static str {
+        let Some(db) = self.classdb.as_ref().filter(|_| self.check_properties) else {
+            return "";
+        };
+        // instanced scenes have no type of their own, and the editor keeps these outside the class
+        if node._type.is_empty() || param.key == "script" || param.key.contains('/') || db.class(&node._type).is_none() {
+            return "";
+        }
+        match db.member(&node._type, &param.key) {
+            Some(Some(default)) if classdb::same_value(&default, &param.val) => " [default]",
+            Some(_) => "",
+            // a script can declare properties of its own
+            None if node.script().is_some() => "",
+            None => " [unknown property]",
+        }
+    }
+
+    /// Asks the plugins for a one-line summary of a sub_resource, falling back to
+    /// the built-in pictures of curves and gradients.
+    fn decode(&self, _type: &str, params: &[Parameter]) -> Option<String> {
+        if !self.plugins.is_empty() {
+            let resource = json::sub_resource(_type, params);
+            if let Some(summary) = self.plugins.iter().find_map(|p| p.decode(_type, &resource)) {
+                return Some(summary);
+            }
+        }
+        preview::summary(_type, params, self.project, self.version)
+    }
 }
 
-#[derive(Debug, Clone)]
-struct NodeParameter {
-    key: String,
-    val: String,
-    sub_params: Vec<Parameter>,
+/// The pieces the tree is drawn with: the branch to a child, the branch to the
+/// last child, and the line carried down past a child to its later siblings.
+struct Glyphs {
+    branch: &'static str,
+    last: &'static str,
+    pipe: &'static str,
 }
 
-#[derive(Debug, Clone)]
-struct Node {
+const BOX: Glyphs = Glyphs { branch: "├── ", last: "└── ", pipe: "│   " };
+const ASCII: Glyphs = Glyphs { branch: "|-- ", last: "`-- ", pipe: "|   " };
+
+/// A property line of the tree and the lines nested under it: the properties of
+/// its sub_resource, or for a group of `group/key` properties, its members.
+struct Entry {
     name: String,
-    _type: String,
-    parent: String,
-    index: i32,
-    instance: Option<ExtResource>,
-    parameters: Vec<NodeParameter>,
-    children: IndexMap<String, Node>,
-    connections: Vec<Connection>,
+    text: String,
+    group: bool,
+    children: Vec<Entry>,
 }
 
-impl Node {
-    fn new(name: &str) -> Self {
-        Self {
-            name: name.to_string(),
-            _type: "".to_string(),
-            parent: "".to_string(),
-            index: -1,
-            instance: None,
-            parameters: Vec::new(),
-            children: IndexMap::new(),
-            connections: Vec::new(),
-        }
-    }
-    fn add_child(&mut self, node: Node, mut parents: Vec<String>) {
-        if parents.len() > 0 {
-            let parent = parents.remove(0);
-            let child = self.children.entry(parent).or_insert(Node::new(""));
-            child.add_child(node, parents);
-        } else {
-            self.children.entry(node.name.clone()).or_insert(node);
-        }
+impl Entry {
+    fn line(text: String) -> Self {
+        Self { name: String::new(), text, group: false, children: Vec::new() }
     }
-}
 
-#[derive(Debug, Clone)]
-struct Connection {
-    signal: String,
-    from: String,
-    to: String,
-    method: String,
+    /// Files a property under the groups its key's leading segments name,
+    /// creating them as needed, so `tracks/0/path` lands in `tracks` → `0`.
+    fn insert(entries: &mut Vec<Entry>, groups: &[&str], leaf: Entry) {
+        let Some((first, rest)) = groups.split_first() else {
+            entries.push(leaf);
+            return;
+        };
+        let at = match entries.iter().rposition(|e| e.group && e.name == *first) {
+            Some(at) => at,
+            None => {
+                entries.push(Entry { name: first.to_string(), text: first.to_string(), group: true, children: Vec::new() });
+                entries.len() - 1
+            }
+        };
+        Entry::insert(&mut entries[at].children, rest, leaf);
+    }
 }
 
-impl Connection {
-    fn new(signal: &str, from: &str, to: &str, method: &str) -> Self {
-        Self {
-            signal: signal.to_string(),
-            from: from.to_string(),
-            to: to.to_string(),
-            method: method.to_string(),
+fn write_entries(entries: &[Entry], base: &str, glyphs: &Glyphs, out: &mut dyn Write) -> io::Result<()> {
+    for (i, entry) in entries.iter().enumerate() {
+        if i + 1 == entries.len() {
+            writeln!(out, "{}{}{}", base, glyphs.last, entry.text)?;
+            write_entries(&entry.children, &format!("{}    ", base), glyphs, out)?;
+        } else {
+            writeln!(out, "{}{}{}", base, glyphs.branch, entry.text)?;
+            write_entries(&entry.children, &format!("{}{}", base, glyphs.pipe), glyphs, out)?;
         }
     }
+    Ok(())
+}
+
+/// The lines beneath a sub_resource property: the sub_resource's own properties,
+/// with any sub_resources they refer to in turn nested beneath them.
+fn sub_entries(params: &[Parameter], opts: &Options) -> Vec<Entry> {
+    let mut entries = Vec::new();
+    // keys like _data hold the resource's internal storage, not settings
+    for param in params.iter().filter(|p| !p.key.starts_with('_')) {
+        let segments: Vec<&str> = param.key.split('/').collect();
+        let last = segments[segments.len() - 1];
+        let leaf = match &param.sub_type {
+            Some(sub_type) => Entry { name: last.to_string(), text: format!("{}: {}", last, sub_type), group: false, children: sub_entries(&param.sub_params, opts) },
+            None => Entry::line(format!("{}: {}", last, units::one_line(&opts.paths(&param.val)))),
+        };
+        Entry::insert(&mut entries, &segments[..segments.len() - 1], leaf);
+    }
+    entries
 }
 
-fn walk(node: &Node, prefix: &str) -> io::Result<()> {
-    let mut index = node.children.len();
+/// Writes the lines beneath the node at `path`, `level` levels below the root.
+fn walk(node: &Node, path: &str, prefix: &str, level: usize, opts: &Options, out: &mut dyn Write) -> io::Result<()> {
+    // past --depth, the children go uncounted and unshown
+    let hide_children = opts.depth.is_some_and(|depth| level >= depth);
+    let mut index = match hide_children {
+        true => 0,
+        false => node.children.len(),
+    };
     if let Some(res) = &node.instance {
         if index == 0 {
-            println!("{}    * ({}) {}", prefix, res._type, res.path);
+            writeln!(out, "{}    * {} {}", prefix, opts.color.type_name(&format!("({})", res._type)), opts.resource(res))?;
         } else {
-            println!("{}│   * ({}) {}", prefix, res._type, res.path);
+            writeln!(out, "{}{}* {} {}", prefix, opts.glyphs.pipe, opts.color.type_name(&format!("({})", res._type)), opts.resource(res))?;
         }
     }
-    for param in node.parameters.iter() {
+    let mut entries: Vec<Entry> = Vec::new();
+    for param in node.parameters.iter().filter(|_| opts.properties) {
+        let segments: Vec<&str> = param.key.split('/').collect();
+        let name = segments[segments.len() - 1];
+        let decoded = param.sub_type.as_ref().and_then(|t| opts.decode(t, &param.sub_params));
+        let val = match (&param.resource, &decoded) {
+            (_, Some(summary)) => summary.clone(),
+            (Some(res), None) => opts.resource(res),
+            (None, None) => units::one_line(&opts.paths(&preview::property(&param.key, &param.val).unwrap_or(param.val.clone()))),
+        };
+        let note = format!("{}{}", opts.angle(param), opts.property_note(node, param));
+        let groups = param.sub_type.as_ref().and_then(|t| preview::groups(t, &param.sub_params, opts.project, opts.version));
+        let children = match (&decoded, groups) {
+            (Some(_), _) => Vec::new(),
+            (None, Some(groups)) => {
+                let mut children = Vec::new();
+                for (key, val) in groups {
+                    let segments: Vec<&str> = key.split('/').collect();
+                    let last = segments[segments.len() - 1];
+                    Entry::insert(&mut children, &segments[..segments.len() - 1], Entry::line(format!("{}: {}", last, val)));
+                }
+                children
+            }
+            (None, None) => sub_entries(&param.sub_params, opts),
+        };
+        let leaf = Entry { name: name.to_string(), text: format!("{}: {}{}", name, val, note), group: false, children };
+        Entry::insert(&mut entries, &segments[..segments.len() - 1], leaf);
+    }
+    for entry in entries.iter() {
         if index == 0 {
-            println!("{}    * {}: {}", prefix, param.key, param.val);
+            writeln!(out, "{}    * {}", prefix, entry.text)?;
         } else {
-            println!("{}│   * {}: {}", prefix, param.key, param.val);
-        }
-        let mut sub_index = param.sub_params.len();
-        let padding = (0..param.key.chars().count()+2).map(|_| " ").collect::<String>();
-        for sub in param.sub_params.iter() {
-            sub_index -= 1;
-            if sub_index == 0 {
-                println!("{}      {}└── {}: {}", prefix, padding, sub.key, sub.val);
-            } else {
-                println!("{}      {}├── {}: {}", prefix, padding, sub.key, sub.val);
-            }
+            writeln!(out, "{}{}* {}", prefix, opts.glyphs.pipe, entry.text)?;
         }
+        // nested lines start beneath the value
+        let padding = (0..entry.name.chars().count()+2).map(|_| " ").collect::<String>();
+        write_entries(&entry.children, &format!("{}      {}", prefix, padding), opts.glyphs, out)?;
     }
-    for conn in node.connections.iter() {
+    for conn in node.connections.iter().filter(|c| c.flags & opts.connection_flags == opts.connection_flags) {
+        let text = opts.color.connection(&format!("connection: {}:{}() => {}:{}(){}", conn.from, conn.signal, conn.to, conn.method, conn.tags()));
         if index == 0 {
-            println!("{}    * connection: {}:{}() => {}:{}()", prefix, conn.from, conn.signal, conn.to, conn.method);
+            writeln!(out, "{}    * {}", prefix, text)?;
         } else {
-            println!("{}│   * connection: {}:{}() => {}:{}()", prefix, conn.from, conn.signal, conn.to, conn.method);
+            writeln!(out, "{}{}* {}", prefix, opts.glyphs.pipe, text)?;
         }
     }
+    if hide_children {
+        return Ok(());
+    }
     for (name, child) in node.children.iter() {
         index -= 1;
-        let node_type = match child.name == child._type || child._type == "".to_string() {
-            true => "".to_string(),
-            false => format!(" ({})", child._type),
+        let child_path = match path {
+            "." => name.clone(),
+            _ => format!("{}/{}", path, name),
         };
+        let node_type = opts.type_label(&child.name, &child._type);
+        let dropped = match child.dropped {
+            true => " [dropped on load: instance children not editable]",
+            false => "",
+        };
+        let label = format!(
+            "{}{}{}{}{}{}{}",
+            opts.color.name(name),
+            node_type,
+            opts.tool(child),
+            opts.weight(child),
+            dropped,
+            opts.annotation(&child_path),
+            opts.description(&child._type)
+        );
         if index == 0 {
-            println!("{}└── {}{}", prefix, name, node_type);
-            walk(&child, &format!("{}    ", prefix))?;
+            writeln!(out, "{}{}{}", prefix, opts.glyphs.last, label)?;
+            walk(child, &child_path, &format!("{}    ", prefix), level + 1, opts, out)?;
         } else {
-            println!("{}├── {}{}", prefix, name, node_type);
-            walk(&child, &format!("{}│   ", prefix))?;
+            writeln!(out, "{}{}{}", prefix, opts.glyphs.branch, label)?;
+            walk(child, &child_path, &format!("{}{}", prefix, opts.glyphs.pipe), level + 1, opts, out)?;
         }
     }
     Ok(())
 }
 
-fn main() -> io::Result<()> {
-    let cli = Cli::parse();
-
-    let f = File::open(&cli.file)?;
-    let reader = BufReader::new(f);
+/// The lint rules directory given by --rules, or else the project's.
+fn rules_dir(rules: &Option<PathBuf>, project: Option<&Project>) -> PathBuf {
+    match (rules, project) {
+        (Some(dir), _) => dir.clone(),
+        (None, Some(project)) => project.root.join(".gdtree/rules"),
+        (None, None) => PathBuf::from(".gdtree/rules"),
+    }
+}
 
-    let ext_res_re = Regex::new(r#"^\[ext_resource path="(?P<path>[^"]+)" type="(?P<type>[^"]+)" id=(?P<id>[0-9]+).*\]$"#).unwrap();
-    let sub_res_re = Regex::new(r#"^\[sub_resource type="(?P<type>[^"]+)" id=(?P<id>[0-9]+).*\]$"#).unwrap();
+/// The project given by --project-root, or else the one enclosing `path`.
+fn project_for(cli: &Cli, path: &Path) -> Option<Project> {
+    match &cli.project_root {
+        Some(dir) => Some(Project::new(fs::canonicalize(dir).unwrap_or(dir.clone()))),
+        None => Project::find(path),
+    }
+}
 
-    let node_re = Regex::new(r#"^\[node name="(?P<name>[^"]+)"(?P<remainder>.*)\]$"#).unwrap();
-    let node_type_re = Regex::new(r#"type="(?P<type>[^"]+)".*"#).unwrap();
-    let node_parent_re = Regex::new(r#"parent="(?P<parent>[^"]+)".*"#).unwrap();
-    let node_index_re = Regex::new(r#"index="(?P<index>[^"]+)".*"#).unwrap();
-    let node_instance_re = Regex::new(r#"instance=ExtResource\( (?P<instance>[^"]+) \).*"#).unwrap();
+/// The project `project_for` finds, for the commands that can't go without one;
+/// exits with a usage error when there is none.
+fn require_project(cli: &Cli, path: &Path) -> Project {
+    project_for(cli, path).unwrap_or_else(|| {
+        Cli::command()
+            .error(ErrorKind::MissingRequiredArgument, format!("no project.godot found above {}, so pass --project-root", path.display()))
+            .exit()
+    })
+}
 
-    let parameter_re = Regex::new(r"^(?P<k>[a-z][a-z_]*) = (?P<v>.*)").unwrap();
-    let connection_re = Regex::new(r#"^\[connection signal="(?P<signal>[^"]+)" from="(?P<from>[^"]+)" to="(?P<to>[^"]+)" method="(?P<method>[^"]+)"\]"#).unwrap();
+fn main() {
+    let cli = Cli::parse();
+    if let Some(threads) = cli.threads {
+        // only fails when the pool was already started
+        let _ = rayon::ThreadPoolBuilder::new().num_threads(threads as usize).build_global();
+    }
+    let mut timings = Timings::new(cli.timings);
+    let status = match run(&cli, &mut timings) {
+        Ok(status) => status,
+        // the output was piped to something like head, which has seen enough
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => 0,
+        Err(err) => {
+            eprintln!("gdtree: {}", err);
+            1
+        }
+    };
+    timings.print();
+    process::exit(status);
+}
 
-    let mut ext_resources = vec![ExtResource::new("".to_string(), "".to_string())];
-    let mut sub_resources = vec![SubResource::new("".to_string())];
-    let mut connections = Vec::<Connection>::new();
-    let mut nodes: Vec<Node> = Vec::new();
-    let mut root = Node::new("");
+/// Does what the command line asks, returning the exit status.
+fn run(cli: &Cli, timings: &mut Timings) -> io::Result<i32> {
+    let plugins = cli.plugins.iter().map(|p| Plugin::load(p)).collect::<io::Result<Vec<_>>>()?;
+    timings.lap("load plugins");
 
-    for line in reader.lines() {
-        let line = line?;
-        if let Some(caps) = ext_res_re.captures(&line) {
-            while ext_resources.len() < caps.name("id").unwrap().as_str().parse().unwrap() {
-                ext_resources.push(ExtResource::new("".to_string(), "".to_string()));
+    match &cli.command {
+        Some(Command::Autoloads { path }) => {
+            let project = require_project(cli, path);
+            autoloads::report(&project)?;
+            return Ok(0);
+        }
+        Some(Command::InputActions { path }) => {
+            let project = require_project(cli, path);
+            if actions::report(&project)? > 0 {
+                return Ok(1);
             }
-            ext_resources.push(ExtResource::new(
-                String::from(caps.name("path").unwrap().as_str()),
-                String::from(caps.name("type").unwrap().as_str()),
-            ));
-        }
-        else if let Some(caps) = sub_res_re.captures(&line) {
-            while sub_resources.len() < caps.name("id").unwrap().as_str().parse().unwrap() {
-                sub_resources.push(SubResource::new("".to_string()));
+            return Ok(0);
+        }
+        Some(Command::AudioBuses { path }) => {
+            let project = require_project(cli, path);
+            if buses::report(&project)? > 0 {
+                return Ok(1);
             }
-            sub_resources.push(SubResource::new(
-                String::from(caps.name("type").unwrap().as_str()),
-            ));
-        }
-        else if let Some(caps) = node_re.captures(&line) {
-            let mut node = Node::new(caps.name("name").unwrap().as_str());
-            if let Some(caps) = node_type_re.captures(caps.name("remainder").unwrap().as_str()) {
-                node._type = String::from(caps.name("type").unwrap().as_str());
+            return Ok(0);
+        }
+        Some(Command::Deprecated { path }) => {
+            if deprecated::report(path, project_for(cli, path).as_ref())? > 0 {
+                return Ok(1);
             }
-            if let Some(caps) = node_parent_re.captures(caps.name("remainder").unwrap().as_str()) {
-                node.parent = String::from(caps.name("parent").unwrap().as_str());
+            return Ok(0);
+        }
+        Some(Command::Check { path }) => {
+            if check::run(path) > 0 {
+                return Ok(1);
             }
-            if let Some(caps) = node_index_re.captures(caps.name("remainder").unwrap().as_str()) {
-                node.index = caps.name("index").unwrap().as_str().parse().unwrap();
+            return Ok(0);
+        }
+        Some(Command::MigrateProperties { path, write }) => {
+            let project = project_for(cli, path);
+            let config = Config::load(project.as_ref())?;
+            let renames = migrate::Renames::new(&config.renamed_properties);
+            if migrate::run(path, project.as_ref(), &renames, *write)? > 0 && !write {
+                return Ok(1);
             }
-            if let Some(caps) = node_instance_re.captures(caps.name("remainder").unwrap().as_str()) {
-                if let Ok(instance) = caps.name("instance").unwrap().as_str().parse::<usize>() {
-                    node.instance = Some(ext_resources[instance].clone());
+            return Ok(0);
+        }
+        Some(Command::Lint { path, rules }) => {
+            let project = project_for(cli, path);
+            let rules = rules_dir(rules, project.as_ref());
+            let config = Config::load(project.as_ref())?;
+            match lint::run(path, &rules, project.as_ref(), &plugins, &config, cli.format_version, timings) {
+                Ok(0) => return Ok(0),
+                Ok(_) => return Ok(1),
+                Err(err) => {
+                    eprintln!("gdtree: {}", err);
+                    return Ok(2);
                 }
             }
-            nodes.push(node);
-        }
-        else if let Some(caps) = parameter_re.captures(&line) {
-            if nodes.len() == 0 {
-                if let Some(last_sub) = sub_resources.last_mut() {
-                    (*last_sub).parameters.push(Parameter{
-                        key: String::from(caps.name("k").unwrap().as_str()),
-                        val: String::from(caps.name("v").unwrap().as_str()),
-                    });
+        }
+        Some(Command::Apply { plan, dry_run }) => {
+            let project = project_for(cli, Path::new("."));
+            let plan = apply::Plan::load(plan)?;
+            let edits = apply::run(&plan, project.as_ref(), *dry_run)?;
+            match dry_run {
+                true => println!("{} edits to make", edits),
+                false => println!("made {} edits", edits),
+            }
+            return Ok(0);
+        }
+        Some(Command::New { template, vars, output }) => {
+            let text = fs::read_to_string(template).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", template.display(), err)))?;
+            let scene = template::render(&text, vars).map_err(|err| io::Error::other(format!("{}: {}", template.display(), err)))?;
+            match output {
+                Some(output) => fs::write(output, scene)?,
+                None => print!("{}", scene),
+            }
+            return Ok(0);
+        }
+        Some(Command::Reconnect { file, signal, from, to, method, remove, new_to, new_method, dry_run }) => {
+            let scene = file.display().to_string();
+            let (signal, from) = (signal.clone(), from.clone());
+            let (to, method) = (to.clone(), method.clone());
+            let operation = match (remove, new_to.is_some() || new_method.is_some(), &to, &method) {
+                (true, _, _, _) => apply::Operation::Disconnect { scene, signal, from, to, method },
+                (false, true, _, _) => apply::Operation::Retarget { scene, signal, from, to, method, new_to: new_to.clone(), new_method: new_method.clone() },
+                (false, false, Some(to), Some(method)) => apply::Operation::Connect { scene, signal, from, to: to.clone(), method: method.clone() },
+                (false, false, _, _) => {
+                    Cli::command()
+                        .error(ErrorKind::MissingRequiredArgument, "adding a connection needs --to and --method; pass --remove, --new-to or --new-method to change existing ones")
+                        .exit();
                 }
+            };
+            let plan = apply::Plan { operations: vec![operation] };
+            // the scene is named by its file path, so there is nothing to resolve against a project
+            if apply::run(&plan, None, *dry_run)? == 0 {
+                println!("already connected");
+            }
+            return Ok(0);
+        }
+        Some(Command::Badge { file, rules, summary }) => {
+            let project = project_for(cli, file);
+            let budgets = Config::load(project.as_ref())?.budgets();
+            let linter = lint::Linter::new(&rules_dir(rules, project.as_ref()), &plugins, budgets)?;
+            let scene = scene::parse_scene(BufReader::new(File::open(file)?), project.as_ref())?;
+            let scene_path = project
+                .as_ref()
+                .and_then(|p| p.res_path(&fs::canonicalize(file).unwrap_or(file.clone())))
+                .unwrap_or_else(|| file.display().to_string());
+            let findings = linter.check(&scene, &scene_path, cli.format_version)?;
+            let health = badge::Health::new(scene_path, &scene.root, linter.rule_count(), findings);
+            let value = match summary {
+                true => health.summary(),
+                false => health.shields(),
+            };
+            println!("{}", serde_json::to_string_pretty(&value).unwrap());
+            return Ok(0);
+        }
+        Some(Command::Expect { file, output }) => {
+            let project = project_for(cli, file);
+            let root = scene::parse(BufReader::new(File::open(file)?), project.as_ref())?;
+            let scene_path = project
+                .as_ref()
+                .and_then(|p| p.res_path(&fs::canonicalize(file).unwrap_or(file.clone())))
+                .unwrap_or_else(|| file.display().to_string());
+            let text = expect::write(&scene_path, &root);
+            match output {
+                Some(output) => fs::write(output, text)?,
+                None => print!("{}", text),
+            }
+            return Ok(0);
+        }
+        Some(Command::VerifyExpect { files }) => {
+            let mut problems = 0;
+            for file in files.iter() {
+                // the file may sit outside the project, in a separate tests directory
+                let project = project_for(cli, file).or_else(|| project_for(cli, Path::new(".")));
+                problems += expect::verify(file, project.as_ref())?;
+            }
+            match problems {
+                0 => return Ok(0),
+                _ => return Ok(1),
+            }
+        }
+        Some(Command::Conflicts { file }) => {
+            // like the markers themselves, 1 means there is still merging to do
+            if conflicts::explain(file, project_for(cli, file).as_ref())? > 0 {
+                return Ok(1);
+            }
+            return Ok(0);
+        }
+        Some(Command::Diff { old, new, summary }) => {
+            let old_root = scene::parse(BufReader::new(File::open(old)?), project_for(cli, old).as_ref())?;
+            let new_root = scene::parse(BufReader::new(File::open(new)?), project_for(cli, new).as_ref())?;
+            timings.lap("parse");
+            let changes = diff::changes(&old_root, &new_root);
+            if *summary {
+                println!("{}", diff::summary(&changes));
             } else {
-                if let Some(last_node) = nodes.last_mut() {
-                    let val = String::from(caps.name("v").unwrap().as_str());
-                    (*last_node).parameters.push(NodeParameter{
-                        key: String::from(caps.name("k").unwrap().as_str()),
-                        val: if val.starts_with("ExtResource") {
-                                let idx: usize = val.replace("ExtResource( ", "").replace(" )", "").parse().unwrap();
-                                ext_resources[idx].path.clone()
-                                // format!("{:?}", ext_resources[idx])
-                            } else if val.starts_with("SubResource") {
-                                let idx: usize = val.replace("SubResource( ", "").replace(" )", "").parse().unwrap();
-                                sub_resources[idx]._type.clone()
-                            } else {
-                                val.clone()
-                            },
-                        sub_params: if val.starts_with("SubResource") {
-                                let idx: usize = val.replace("SubResource( ", "").replace(" )", "").parse().unwrap();
-                                sub_resources[idx].parameters.clone()
-                            } else {
-                                Vec::new()
-                            },
-                    });
+                for change in changes.iter() {
+                    println!("{}", change);
                 }
             }
+            timings.lap("render");
+            // like diff(1), 1 means the scenes differ
+            if !changes.is_empty() {
+                return Ok(1);
+            }
+            return Ok(0);
+        }
+        Some(Command::Blame { file, node }) => {
+            blame::report(file, node)?;
+            return Ok(0);
+        }
+        Some(Command::Serve { path }) => {
+            let project = require_project(cli, path);
+            serve::run(project)?;
+            return Ok(0);
         }
-        else if let Some(caps) = connection_re.captures(&line) {
-            let conn = Connection::new(
-                caps.name("signal").unwrap().as_str(),
-                caps.name("from").unwrap().as_str(),
-                match caps.name("to").unwrap().as_str() {
-                    "." => nodes[0].name.as_str(),
-                    s => s,
-                },
-                caps.name("method").unwrap().as_str(),
-            );
-            connections.push(conn);
+        Some(Command::Tui { file }) => {
+            let project = project_for(cli, file);
+            let root = scene::parse(BufReader::new(File::open(file)?), project.as_ref())?;
+            let config = Config::load(project.as_ref())?;
+            // without a class reference, which nodes can be hidden is guessed from their types
+            let classdb = match (&config.docs, &config.godot) {
+                (Some(dir), _) => Some(ClassDb::new(dir.clone())),
+                (None, Some(version)) => ClassDb::cached(version),
+                (None, None) => None,
+            };
+            tui::run(root, fs::canonicalize(file)?, project.as_ref(), classdb)?;
+            return Ok(0);
+        }
+        Some(Command::History { file, samples }) => {
+            history::report(file, *samples as usize)?;
+            return Ok(0);
+        }
+        Some(Command::Deps { path, from: Some(from), to: Some(to), .. }) => {
+            let project = require_project(cli, path);
+            let reports = report::load(&project.root, Some(&project), timings)?;
+            let scene = |file: &PathBuf| {
+                let file = fs::canonicalize(file).unwrap_or(file.clone());
+                project.res_path(&file).unwrap_or_else(|| file.display().to_string())
+            };
+            if report::paths(&reports, &scene(from), &scene(to)) == 0 {
+                return Ok(1);
+            }
+            return Ok(0);
         }
+        Some(Command::Stats { path, report, ndjson: true, .. }) => {
+            let project = project_for(cli, path);
+            let owners = Owners::load(project.as_ref(), &Config::load(project.as_ref())?.owners)?;
+            let mut out = io::BufWriter::new(io::stdout().lock());
+            report.each(path, project.as_ref(), timings, |r| report::stats_line(&r, &owners, &mut out))?;
+            out.flush()?;
+            timings.lap("render");
+            return Ok(0);
+        }
+        Some(Command::Stats { path, report, .. }) | Some(Command::Deps { path, report, .. }) | Some(Command::Find { path, report, .. }) => {
+            let project = project_for(cli, path);
+            let reports = report.load(path, project.as_ref(), timings)?;
+            match &cli.command {
+                Some(Command::Stats { json, .. }) => {
+                    let owners = Owners::load(project.as_ref(), &Config::load(project.as_ref())?.owners)?;
+                    report::stats(&reports, *json, &owners);
+                }
+                Some(Command::Deps { kind, format, recursive, .. }) => report::deps(&reports, *kind, *format, project.as_ref(), *recursive),
+                Some(Command::Find { pattern, paths_only, .. }) => {
+                    if report::find(&reports, pattern, *paths_only) == 0 {
+                        return Ok(1);
+                    }
+                }
+                _ => unreachable!(),
+            }
+            timings.lap("render");
+            return Ok(0);
+        }
+        Some(Command::Report { path, output, rules }) => {
+            let project = require_project(cli, path);
+            let config = Config::load(Some(&project))?;
+            let linter = lint::Linter::new(&rules_dir(rules, Some(&project)), &plugins, config.budgets())?;
+            let reports = report::load(&project.root, Some(&project), timings)?;
+            let findings = reports.iter().map(|r| linter.check(&r.scene, &r.name, cli.format_version)).collect::<io::Result<Vec<_>>>()?;
+            timings.lap("lint");
+            let owners = Owners::load(Some(&project), &config.owners)?;
+            let pages = site::write(&site::title(&project), &reports, &findings, &owners, output)?;
+            timings.lap("render");
+            println!("wrote {} pages to {}", pages, output.display());
+            return Ok(0);
+        }
+        Some(Command::Lights { path }) => {
+            let reports = report::load(path, project_for(cli, path).as_ref(), timings)?;
+            report::lights(&reports);
+            timings.lap("render");
+            return Ok(0);
+        }
+        Some(Command::Physics { path }) => {
+            let reports = report::load(path, project_for(cli, path).as_ref(), timings)?;
+            let shapeless = report::physics(&reports);
+            timings.lap("render");
+            if shapeless > 0 {
+                return Ok(1);
+            }
+            return Ok(0);
+        }
+        Some(Command::GrepValue { pattern, path }) => {
+            // as grep does: 2 when a file couldn't be searched, else 1 when nothing matched
+            return match grep::run(path, pattern, project_for(cli, path).as_ref()) {
+                (_, 1..) => Ok(2),
+                (0, _) => Ok(1),
+                _ => Ok(0),
+            };
+        }
+        Some(Command::Values { path, key }) => {
+            let reports = report::load(path, project_for(cli, path).as_ref(), timings)?;
+            if report::values(&reports, key) == 0 {
+                return Ok(1);
+            }
+            timings.lap("render");
+            return Ok(0);
+        }
+        Some(Command::Orphans { path }) => {
+            let project = require_project(cli, path);
+            if orphans::report(&project, timings)? > 0 {
+                return Ok(1);
+            }
+            return Ok(0);
+        }
+        Some(Command::CompletePath { file, prefix }) => {
+            let root = scene::parse(BufReader::new(File::open(file)?), None)?;
+            for (path, node) in root.paths().into_iter().skip(1) {
+                let unique = node.parameters.iter().any(|p| p.key == "unique_name_in_owner" && p.val == "true");
+                let path = match prefix.starts_with('%') {
+                    true if unique => format!("%{}", node.name),
+                    true => continue,
+                    false => path,
+                };
+                if path.starts_with(prefix.as_str()) {
+                    println!("{}", path);
+                }
+            }
+            return Ok(0);
+        }
+        Some(Command::Classdb { action }) => {
+            match action {
+                ClassdbAction::Fetch { version, source } => {
+                    let count = classdb::fetch(version, source.as_deref())?;
+                    println!("cached {} classes for Godot {}", count, version);
+                }
+                ClassdbAction::List => {
+                    for version in classdb::versions()? {
+                        println!("{}", version);
+                    }
+                }
+                ClassdbAction::Remove { version } => classdb::remove(version)?,
+            }
+            return Ok(0);
+        }
+        Some(Command::Bookmark { action }) => {
+            let file = match action {
+                BookmarkAction::Add { file, .. } | BookmarkAction::Remove { file, .. } => Some(file),
+                BookmarkAction::List { file } => file.as_ref(),
+            };
+            let start = file.map_or(Path::new("."), |f| f.as_path());
+            let Some(project) = project_for(cli, start) else {
+                Cli::command()
+                    .error(ErrorKind::MissingRequiredArgument, format!("bookmarks are kept in the project, but no project.godot was found above {}, so pass --project-root", start.display()))
+                    .exit();
+            };
+            let mut bookmarks = Bookmarks::load(&project)?;
+            let scene = file.map(|f| bookmarks::scene_key(&project, f));
+            match action {
+                BookmarkAction::Add { file, name, node } => {
+                    let root = scene::parse(BufReader::new(File::open(file)?), Some(&project))?;
+                    if root.find(node).is_none() {
+                        Cli::command().error(ErrorKind::InvalidValue, format!("{} has no node at {}", file.display(), node)).exit();
+                    }
+                    bookmarks.add(scene.as_deref().unwrap(), name, node);
+                    bookmarks.save(&project)?;
+                }
+                BookmarkAction::List { .. } => {
+                    for (scene, name, node) in bookmarks.list(scene.as_deref()) {
+                        println!("{}  {}  {}", scene, name, node);
+                    }
+                }
+                BookmarkAction::Remove { file, name } => {
+                    if !bookmarks.remove(scene.as_deref().unwrap(), name) {
+                        Cli::command().error(ErrorKind::InvalidValue, format!("{} has no bookmark named {}", file.display(), name)).exit();
+                    }
+                    bookmarks.save(&project)?;
+                }
+            }
+            return Ok(0);
+        }
+        Some(Command::Schema { stats }) => {
+            match stats {
+                true => print!("{}", report::STATS_SCHEMAS[report::STATS_FORMAT_VERSION as usize - 1]),
+                false => print!("{}", json::SCHEMAS[cli.format_version as usize - 1]),
+            }
+            return Ok(0);
+        }
+        None => {}
     }
 
-    for mut node in nodes {
-        if let Some(index) = connections.iter().position(|c| c.from == node.name) {
-            let conn = connections.swap_remove(index);
-            node.connections.push(conn);
+    let mut files = Vec::new();
+    for input in cli.files.iter() {
+        match Path::new(input).is_dir() {
+            true => files.extend(project::list_files(Path::new(input), &["tscn", "tres"]).into_iter().map(|f| f.to_string_lossy().to_string())),
+            false => files.push(input.clone()),
         }
-        if node.parent == "".to_string() {
-            // root node
-            root = node;
-        } else {
-            let parents: Vec<String>;
-            if node.parent == ".".to_string() {
-                parents = Vec::new();
-            } else {
-                parents = node.parent.split("/").map(|x| x.to_string()).collect();
+    }
+    match files.len() {
+        0 => Cli::command().error(ErrorKind::InvalidValue, format!("no .tscn or .tres files found in {}", cli.files.join(", "))).exit(),
+        1 => return print_file(cli, &files[0], &plugins, true, timings),
+        _ => (),
+    }
+    for (flag, set) in [("--watch", cli.watch), ("--bookmark", cli.bookmark.is_some()), ("--git-textconv", cli.git_textconv), ("--output", cli.output.is_some())] {
+        if set {
+            Cli::command().error(ErrorKind::ArgumentConflict, format!("{} takes a single scene, not {} files", flag, files.len())).exit();
+        }
+    }
+    // print what can be printed, and say what couldn't at the end
+    let mut status = 0;
+    let mut failed = 0;
+    let mut out = io::stdout().lock();
+    for (i, file) in files.iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        match file.as_str() {
+            "-" => writeln!(out, "==> standard input <==")?,
+            _ => writeln!(out, "==> {} <==", file)?,
+        }
+        match print_file(cli, file, &plugins, false, timings) {
+            Ok(0) => (),
+            Ok(code) => status = code,
+            // nobody is reading any more, so the other files needn't be printed
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => return Err(err),
+            Err(err) => {
+                eprintln!("gdtree: {}: {}", file, err);
+                failed += 1;
             }
-            root.add_child(node, parents)
         }
     }
+    if failed > 0 {
+        eprintln!("gdtree: {} of {} files could not be printed", failed, files.len());
+        status = 1;
+    }
+    Ok(status)
+}
 
-    println!("{}", root.name);
-    walk(&root, "")?;
+/// Prints one scene or resource, in whichever form the options ask for.
+/// `paged` lets a tree taller than the terminal go through the pager, which
+/// only suits a scene printed on its own.
+fn print_file(cli: &Cli, file: &str, plugins: &[Plugin], paged: bool, timings: &mut Timings) -> io::Result<i32> {
+    // `-` reads the scene from stdin, as piped from git show
+    let stdin = file == "-";
+    for (flag, set) in [("--watch", cli.watch), ("--bookmark", cli.bookmark.is_some())] {
+        if set && stdin {
+            Cli::command().error(ErrorKind::ArgumentConflict, format!("{} needs the scene's file, not standard input", flag)).exit();
+        }
+    }
+    // git hands textconv a temporary copy of older revisions, outside the
+    // project, so the project is left out for both sides to print alike;
+    // a scene on stdin takes the project of the current directory
+    let project = match (cli.git_textconv, stdin) {
+        (true, _) => None,
+        (false, true) => project_for(cli, Path::new(".")),
+        (false, false) => project_for(cli, Path::new(file)),
+    };
+
+    for (flag, set) in [("--absolute-paths", cli.absolute_paths), ("--check-files", cli.check_files)] {
+        if set && project.is_none() {
+            Cli::command()
+                .error(ErrorKind::MissingRequiredArgument, format!("{} needs a project root; none was found above the scene, so pass --project-root", flag))
+                .exit();
+        }
+    }
+    let focus = match (&cli.bookmark, &project) {
+        (None, _) => None,
+        (Some(_), None) => Cli::command()
+            .error(ErrorKind::MissingRequiredArgument, "--bookmark needs a project root; none was found above the scene, so pass --project-root")
+            .exit(),
+        (Some(name), Some(project)) => match Bookmarks::load(project)?.get(&bookmarks::scene_key(project, Path::new(file)), name) {
+            Some(path) => Some(path.to_string()),
+            None => Cli::command()
+                .error(ErrorKind::InvalidValue, format!("{} has no bookmark named {}; see gdtree bookmark list", file, name))
+                .exit(),
+        },
+    };
+    let config = Config::load(project.as_ref())?;
+    let classdb = match (&config.docs, &config.godot) {
+        _ if !(cli.describe || cli.inheritance || cli.check_properties) => None,
+        (Some(dir), _) => Some(ClassDb::new(dir.clone())),
+        (None, Some(version)) => match ClassDb::cached(version) {
+            Some(classdb) => Some(classdb),
+            None => Cli::command()
+                .error(ErrorKind::ArgumentConflict, format!("no class reference cached for Godot {0}; run gdtree classdb fetch {0}", version))
+                .exit(),
+        },
+        (None, None) => Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--describe, --inheritance and --check-properties need the Godot class reference: set godot = \"4.2\" (after gdtree classdb fetch 4.2) or docs = \"path/to/doc/classes\" in .gdtree/config.toml",
+            )
+            .exit(),
+    };
+
+    let annotations = match (&cli.annotations, cli.git_textconv) {
+        (Some(path), _) => Some(Annotations::load(path)?),
+        (None, false) if !stdin && annotations::sidecar(Path::new(file)).is_file() => Some(Annotations::load(&annotations::sidecar(Path::new(file)))?),
+        (None, _) => None,
+    };
+
+    timings.lap("resolve project");
+
+    // a running gdtree serve has the scene parsed already
+    let untouched = focus.is_none() && cli.filter.is_none() && cli.regex.is_none() && !cli.expand_instances && !cli.structure_only;
+    if let Some(project) = project.as_ref().filter(|_| cli.format == "json" && untouched && !stdin) {
+        if let Some(document) = serve::fetch(project, Path::new(file), cli.format_version) {
+            let mut out = output(cli)?;
+            writeln!(out, "{}", serde_json::to_string_pretty(&document?).unwrap())?;
+            out.flush()?;
+            timings.lap("fetch from server");
+            return Ok(0);
+        }
+    }
+
+    let text = match stdin {
+        true => {
+            let mut text = Vec::new();
+            io::stdin().lock().read_to_end(&mut text)?;
+            text
+        }
+        false => fs::read(file)?,
+    };
+
+    let scene = match read_scene(cli, &text, file, project.as_ref(), focus.as_deref(), timings) {
+        Ok(scene) => scene,
+        // git diff should still show something; textconv takes none of the
+        // options that could fail otherwise, so this is a scene that didn't parse
+        Err(_) if cli.git_textconv => {
+            io::stdout().lock().write_all(&text)?;
+            return Ok(0);
+        }
+        Err(err) => return Err(err),
+    };
+    let root = &scene.root;
+    // the sidecar's paths are relative to the scene's root, wherever --bookmark starts
+    let annotations = match annotations {
+        Some(annotations) => match &focus {
+            Some(path) => annotations.rebase(path),
+            None => annotations,
+        },
+        None => Annotations::default(),
+    };
+    let opts = Options::new(cli, &config, project.as_ref(), plugins, classdb, scene.detected.version, annotations);
+
+    let stdout = &mut io::stdout().lock();
+    if cli.detect_version {
+        writeln!(stdout, "{}", scene.detected.version)?;
+        for evidence in scene.detected.evidence.iter() {
+            writeln!(stdout, "  {}", evidence)?;
+        }
+        return Ok(0);
+    }
 
+    if cli.script_coverage {
+        coverage::report(root, stdout)?;
+        return Ok(0);
+    }
+
+    if cli.draw_order {
+        zorder::report(root, opts.classdb.as_ref(), stdout)?;
+        return Ok(0);
+    }
+
+    if cli.world_transforms {
+        world::report(root, opts.classdb.as_ref(), stdout)?;
+        return Ok(0);
+    }
+
+    if cli.viewports {
+        viewports::report(root, stdout)?;
+        return Ok(0);
+    }
+
+    if cli.connections_only {
+        connections(root, opts.connection_flags, stdout)?;
+        return Ok(0);
+    }
+
+    if let Some(limit) = cli.top {
+        let size = text.len() as u64;
+        weights(root, size, limit, stdout)?;
+        return Ok(0);
+    }
+
+    if let Some(language) = cli.emit_snippet {
+        let nodes: Vec<_> = root.paths().into_iter().skip(1).collect();
+        for line in snippet::lines(&nodes, language) {
+            writeln!(stdout, "{}", line)?;
+        }
+        return Ok(0);
+    }
+
+    if !["tree", "comment", "matrix", "json", "dot", "mermaid", "html"].contains(&cli.format.as_str()) {
+        let Some(plugin) = plugins.iter().find(|p| p.name == cli.format && p.renders()) else {
+            Cli::command()
+                .error(ErrorKind::InvalidValue, format!("unknown format '{}'; use tree, comment, matrix, json, dot, mermaid, html or load a plugin that provides it", cli.format))
+                .exit();
+        };
+        let mut out = output(cli)?;
+        out.write_all(plugin.render(&json::scene(&scene, cli.format_version)).unwrap_or_default().as_bytes())?;
+        out.flush()?;
+        return Ok(0);
+    }
+
+    match paged && !cli.no_pager && !cli.watch && cli.output.is_none() && io::stdout().is_terminal() {
+        true => {
+            let mut pager = Pager::default();
+            print_tree(&scene, cli, &opts, &mut pager)?;
+            pager.finish()?;
+        }
+        false => print_tree(&scene, cli, &opts, &mut *output(cli)?)?,
+    }
+    timings.lap("render");
+    if cli.watch {
+        let mut watcher = watch::Watcher::new(Path::new(file));
+        let mut last = scene;
+        loop {
+            watcher.wait();
+            let scene = match fs::read(file).and_then(|text| read_scene(cli, &text, file, project.as_ref(), focus.as_deref(), timings)) {
+                Ok(scene) => scene,
+                Err(err) => {
+                    eprintln!("gdtree: {}", err);
+                    continue;
+                }
+            };
+            println!();
+            println!("== {} ==", files::timestamp(SystemTime::now()));
+            match cli.diff {
+                true => {
+                    let changes = diff::changes(&last.root, &scene.root);
+                    if cli.summary {
+                        println!("{}", diff::summary(&changes));
+                    } else if changes.is_empty() {
+                        println!("no changes");
+                    } else {
+                        for change in changes.iter() {
+                            println!("{}", change);
+                        }
+                    }
+                }
+                false => print_tree(&scene, cli, &opts, &mut io::stdout().lock())?,
+            }
+            last = scene;
+        }
+    }
+
+    Ok(0)
+}
+
+/// Where the tree or export goes: the file --output names, or else stdout.
+fn output(cli: &Cli) -> io::Result<Box<dyn Write>> {
+    match &cli.output {
+        Some(path) => {
+            let file = File::create(path).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", path.display(), err)))?;
+            Ok(Box::new(io::BufWriter::new(file)))
+        }
+        None => Ok(Box::new(io::stdout().lock())),
+    }
+}
+
+/// Parses the scene and shapes it as the options ask: only its structure
+/// under --structure-only, with its instances expanded, narrowed to the
+/// bookmarked node and pruned to the nodes --filter or --regex match. Both
+/// the first print and each refresh of --watch read the scene through here.
+fn read_scene(cli: &Cli, text: &[u8], file: &str, project: Option<&Project>, focus: Option<&str>, timings: &mut Timings) -> io::Result<Scene> {
+    let mut scene = match cli.structure_only {
+        true => scene::parse_structure(text)?,
+        false => scene::parse_scene(text, project)?,
+    };
+    timings.lap("parse");
+    if cli.expand_instances {
+        let mut seen: Vec<PathBuf> = fs::canonicalize(file).into_iter().collect();
+        scene::expand_instances(&mut scene.root, project, &mut seen)?;
+        timings.lap("expand instances");
+    }
+    let mut scene = subtree(scene, focus)?;
+    match (&cli.filter, &cli.regex) {
+        (Some(pattern), _) => prune(&mut scene.root, &|node| report::glob_match(pattern, &node.name)),
+        (_, Some(regex)) => prune(&mut scene.root, &|node| regex.is_match(&node.name)),
+        (None, None) => true,
+    };
+    timings.lap("filter");
+    Ok(scene)
+}
+
+/// Narrows the scene down to the node at `path`, as --bookmark asks.
+fn subtree(mut scene: Scene, path: Option<&str>) -> io::Result<Scene> {
+    let Some(path) = path else {
+        return Ok(scene);
+    };
+    match scene.root.find(path) {
+        Some(node) => {
+            scene.root = node.clone();
+            Ok(scene)
+        }
+        None => Err(io::Error::new(io::ErrorKind::NotFound, format!("the bookmarked node {} is no longer in the scene", path))),
+    }
+}
+
+/// Drops the descendants of a node that neither match nor lead to a node that
+/// does, returning whether anything under or at the node matched.
+fn prune(node: &mut Node, matches: &dyn Fn(&Node) -> bool) -> bool {
+    node.children.retain(|_, child| prune(child, matches));
+    !node.children.is_empty() || matches(node)
+}
+
+/// Prints every connection with all of the `flags` bits, sorted by the node
+/// emitting the signal, then the signal, receiving node and method.
+fn connections(root: &Node, flags: u32, out: &mut impl Write) -> io::Result<()> {
+    let mut conns: Vec<&Connection> = Vec::new();
+    for (_, node) in root.paths() {
+        conns.extend(node.connections.iter().filter(|c| c.flags & flags == flags));
+    }
+    conns.sort_by(|a, b| (&a.from, &a.signal, &a.to, &a.method).cmp(&(&b.from, &b.signal, &b.to, &b.method)));
+    for conn in conns.iter() {
+        writeln!(out, "{}:{}() => {}:{}(){}", conn.from, conn.signal, conn.to, conn.method, conn.tags())?;
+    }
+    Ok(())
+}
+
+/// Prints a table of the `limit` nodes that take up the most of a scene file of
+/// `size` bytes.
+fn weights(root: &Node, size: u64, limit: usize, out: &mut impl Write) -> io::Result<()> {
+    let mut nodes = root.paths();
+    nodes.sort_by_key(|(_, node)| std::cmp::Reverse(node.weight));
+    nodes.truncate(limit);
+    let share = |weight: u64| match size {
+        0 => 0.0,
+        _ => weight as f64 * 100.0 / size as f64,
+    };
+    writeln!(out, "{:>10}  {:>6}  node", "size", "share")?;
+    for (path, node) in nodes.iter() {
+        let label = match node._type.is_empty() {
+            true => path.clone(),
+            false => format!("{} ({})", path, node._type),
+        };
+        writeln!(out, "{:>10}  {:>5.1}%  {}", units::bytes(node.weight), share(node.weight), label)?;
+    }
+    writeln!(out, "{} of {} in nodes", units::bytes(root.total_weight()), units::bytes(size))?;
     Ok(())
 }
+
+/// Prints the scene as a tree, or as a comment block under --format comment, its
+/// connection grid under --format matrix, as JSON under --format json, as a
+/// Graphviz or Mermaid diagram under --format dot or mermaid, or as a web page
+/// under --format html.
+fn print_tree(scene: &Scene, cli: &Cli, opts: &Options, out: &mut dyn Write) -> io::Result<()> {
+    let root = &scene.root;
+    let rendered = match cli.format.as_str() {
+        "matrix" => Some(matrix::render(root, opts.connection_flags, cli.matrix_style)),
+        "dot" => Some(dot::render(root, opts.connection_flags)),
+        "mermaid" => Some(mermaid::render(root, opts.connection_flags)),
+        "html" => Some(html::render(root, opts.connection_flags, &opts.annotations)),
+        "json" => Some(format!("{}\n", serde_json::to_string_pretty(&json::scene(scene, cli.format_version)).unwrap())),
+        _ => None,
+    };
+    if let Some(rendered) = rendered {
+        out.write_all(rendered.as_bytes())?;
+        return out.flush();
+    }
+    let root_type = match opts.inheritance {
+        true => opts.type_label(&root.name, &root._type),
+        false => String::new(),
+    };
+    let mut tree = Vec::new();
+    writeln!(tree, "{}{}{}{}{}{}", opts.color.name(&root.name), root_type, opts.tool(root), opts.weight(root), opts.annotation("."), opts.description(&root._type))?;
+    walk(root, ".", "", 0, opts, &mut tree)?;
+    if let Some(depth) = opts.depth {
+        let hidden = root.paths().iter().filter(|(path, _)| path != "." && path.split('/').count() > depth).count();
+        if hidden > 0 {
+            writeln!(tree, "\n{} nodes below depth {} hidden", hidden, depth)?;
+        }
+    }
+    match cli.format.as_str() {
+        "comment" => out.write_all(comment::wrap(&String::from_utf8_lossy(&tree), cli.comment_style).as_bytes())?,
+        _ => out.write_all(&tree)?,
+    }
+    out.flush()
+}
@@ -0,0 +1,89 @@
+use clap::ValueEnum;
+
+use crate::scene::Node;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum MatrixStyle {
+    /// Columns padded to line up
+    Text,
+    /// Comma-separated values, for spreadsheets
+    Csv,
+}
+
+/// Lays out a scene's connections as a grid: a row per node that emits signals,
+/// a column per node receiving them, and the signals in the cells. Only the
+/// connections with all the `flags` bits are included.
+pub fn render(root: &Node, flags: u32, style: MatrixStyle) -> String {
+    // connections name the root `.` as their source, but by its name as their target
+    let name = |path: &str| match path {
+        "." => root.name.clone(),
+        path => path.to_string(),
+    };
+    let mut sources: Vec<String> = Vec::new();
+    let mut targets: Vec<String> = Vec::new();
+    let mut cells: Vec<(String, String, String)> = Vec::new();
+    for (_, node) in root.paths() {
+        for conn in node.connections.iter().filter(|c| c.flags & flags == flags) {
+            let (from, to) = (name(&conn.from), name(&conn.to));
+            if !sources.contains(&from) {
+                sources.push(from.clone());
+            }
+            if !targets.contains(&to) {
+                targets.push(to.clone());
+            }
+            cells.push((from, to, conn.signal.clone()));
+        }
+    }
+
+    let mut rows = vec![std::iter::once(String::new()).chain(targets.iter().cloned()).collect::<Vec<_>>()];
+    for source in sources.iter() {
+        let mut row = vec![source.clone()];
+        for target in targets.iter() {
+            let mut signals: Vec<&str> = Vec::new();
+            for (_, _, signal) in cells.iter().filter(|(from, to, _)| from == source && to == target) {
+                if !signals.contains(&signal.as_str()) {
+                    signals.push(signal);
+                }
+            }
+            row.push(signals.join(", "));
+        }
+        rows.push(row);
+    }
+
+    let mut out = String::new();
+    match style {
+        MatrixStyle::Csv => {
+            for row in rows.iter() {
+                out += &row.iter().map(|cell| csv_field(cell)).collect::<Vec<_>>().join(",");
+                out.push('\n');
+            }
+        }
+        MatrixStyle::Text => {
+            let widths: Vec<usize> = (0..rows[0].len())
+                .map(|column| rows.iter().map(|row| row[column].chars().count().max(1)).max().unwrap_or(1))
+                .collect();
+            for row in rows.iter() {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(widths.iter())
+                    .enumerate()
+                    .map(|(column, (cell, width))| match (column, cell.is_empty()) {
+                        (0, _) | (_, false) => format!("{:width$}", cell),
+                        (_, true) => format!("{:width$}", "-"),
+                    })
+                    .collect();
+                out += cells.join("  ").trim_end();
+                out.push('\n');
+            }
+        }
+    }
+    out
+}
+
+/// Quotes a CSV field when it holds a comma, quote or line break.
+fn csv_field(text: &str) -> String {
+    match text.contains([',', '"', '\n']) {
+        true => format!("\"{}\"", text.replace('"', "\"\"")),
+        false => text.to_string(),
+    }
+}
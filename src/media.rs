@@ -0,0 +1,234 @@
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::io::SeekFrom;
+use std::path::Path;
+
+use crate::units;
+
+/// Summarizes an audio or image file from its header, for `--check-files`:
+/// `2.50 s, 44100 Hz, stereo` or `1024x512, ~2 MiB VRAM`.
+pub fn describe(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    let mut file = File::open(path).ok()?;
+    match ext.as_str() {
+        "wav" => audio(wav(&mut file).ok()??),
+        "ogg" | "oga" | "opus" => audio(ogg(&mut file).ok()??),
+        "png" => image(png(&mut file).ok()??),
+        "jpg" | "jpeg" => image(jpeg(&mut file).ok()??),
+        "webp" => image(webp(&mut file).ok()??),
+        _ => None,
+    }
+}
+
+fn audio(Audio { seconds, rate, channels }: Audio) -> Option<String> {
+    let channels = match channels {
+        1 => "mono".to_string(),
+        2 => "stereo".to_string(),
+        n => format!("{} channels", n),
+    };
+    let length = match seconds < 60.0 {
+        true => format!("{:.2} s", seconds),
+        false => format!("{}:{:05.2}", (seconds / 60.0) as u64, seconds % 60.0),
+    };
+    Some(format!("{}, {} Hz, {}", length, rate, channels))
+}
+
+/// The size and memory footprint of an image, estimated as the RGBA8 texture
+/// Godot makes of it when it isn't compressed on import.
+fn image((width, height): (u32, u32)) -> Option<String> {
+    let vram = width as u64 * height as u64 * 4;
+    Some(format!("{}x{}, ~{} VRAM", width, height, units::bytes(vram)))
+}
+
+struct Audio {
+    seconds: f64,
+    rate: u32,
+    channels: u16,
+}
+
+fn u16_at(buf: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes([buf[at], buf[at + 1]])
+}
+
+fn u32_at(buf: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(buf[at..at + 4].try_into().unwrap())
+}
+
+/// Reads the fmt and data chunks of a RIFF WAVE file.
+fn wav(file: &mut File) -> io::Result<Option<Audio>> {
+    let mut header = [0; 12];
+    file.read_exact(&mut header)?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WAVE" {
+        return Ok(None);
+    }
+    let (mut rate, mut channels, mut byte_rate) = (0, 0, 0);
+    loop {
+        let mut chunk = [0; 8];
+        if file.read_exact(&mut chunk).is_err() {
+            return Ok(None);
+        }
+        let size = u32_at(&chunk, 4);
+        match &chunk[0..4] {
+            b"fmt " if size >= 16 => {
+                let mut fmt = vec![0; size as usize];
+                file.read_exact(&mut fmt)?;
+                channels = u16_at(&fmt, 2);
+                rate = u32_at(&fmt, 4);
+                byte_rate = u32_at(&fmt, 8);
+            }
+            b"data" if byte_rate > 0 => {
+                let seconds = size as f64 / byte_rate as f64;
+                return Ok(Some(Audio { seconds, rate, channels }));
+            }
+            // chunks are padded to an even length
+            _ => {
+                file.seek(SeekFrom::Current(size as i64 + (size % 2) as i64))?;
+            }
+        }
+    }
+}
+
+/// Reads the identification header of an Ogg Vorbis or Opus stream, and the
+/// granule position of its last page for the length.
+fn ogg(file: &mut File) -> io::Result<Option<Audio>> {
+    let mut first = [0; 512];
+    let n = file.read(&mut first)?;
+    let first = &first[..n];
+    if n < 28 || &first[0..4] != b"OggS" {
+        return Ok(None);
+    }
+    // the page header is 27 bytes plus one lacing value per segment
+    let packet = &first[(27 + first[26] as usize).min(n)..];
+    let (rate, channels, granule_rate, pre_skip) = if packet.len() >= 16 && &packet[0..7] == b"\x01vorbis" {
+        let rate = u32_at(packet, 12);
+        (rate, packet[11] as u16, rate, 0)
+    } else if packet.len() >= 16 && &packet[0..8] == b"OpusHead" {
+        // opus always counts granules at 48 kHz
+        (u32_at(packet, 12), packet[9] as u16, 48000, u16_at(packet, 10) as u64)
+    } else {
+        return Ok(None);
+    };
+    if granule_rate == 0 {
+        return Ok(None);
+    }
+
+    let len = file.seek(SeekFrom::End(0))?;
+    let tail_len = len.min(65536);
+    file.seek(SeekFrom::Start(len - tail_len))?;
+    let mut tail = vec![0; tail_len as usize];
+    file.read_exact(&mut tail)?;
+    let Some(last) = tail.windows(4).rposition(|w| w == b"OggS") else {
+        return Ok(None);
+    };
+    if last + 14 > tail.len() {
+        return Ok(None);
+    }
+    let granule = u64::from_le_bytes(tail[last + 6..last + 14].try_into().unwrap());
+    let seconds = granule.saturating_sub(pre_skip) as f64 / granule_rate as f64;
+    Ok(Some(Audio { seconds, rate, channels }))
+}
+
+fn u16_be(buf: &[u8], at: usize) -> u16 {
+    u16::from_be_bytes([buf[at], buf[at + 1]])
+}
+
+fn u32_be(buf: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes(buf[at..at + 4].try_into().unwrap())
+}
+
+/// Reads the dimensions from a PNG's IHDR chunk.
+fn png(file: &mut File) -> io::Result<Option<(u32, u32)>> {
+    let mut header = [0; 24];
+    file.read_exact(&mut header)?;
+    if &header[0..8] != b"\x89PNG\r\n\x1a\n" || &header[12..16] != b"IHDR" {
+        return Ok(None);
+    }
+    Ok(Some((u32_be(&header, 16), u32_be(&header, 20))))
+}
+
+/// Reads the dimensions from a JPEG's start-of-frame segment.
+fn jpeg(file: &mut File) -> io::Result<Option<(u32, u32)>> {
+    let mut buf = Vec::new();
+    file.read_to_end(&mut buf)?;
+    if !buf.starts_with(&[0xff, 0xd8]) {
+        return Ok(None);
+    }
+    let mut at = 2;
+    while at + 9 <= buf.len() {
+        if buf[at] != 0xff {
+            return Ok(None);
+        }
+        let marker = buf[at + 1];
+        // SOF0 to SOF15, leaving out DHT, JPG and DAC which share the range
+        if (0xc0..=0xcf).contains(&marker) && ![0xc4, 0xc8, 0xcc].contains(&marker) {
+            return Ok(Some((u16_be(&buf, at + 7) as u32, u16_be(&buf, at + 5) as u32)));
+        }
+        at += 2 + u16_be(&buf, at + 2) as usize;
+    }
+    Ok(None)
+}
+
+/// Reads the dimensions from a WebP's VP8, VP8L or VP8X chunk.
+fn webp(file: &mut File) -> io::Result<Option<(u32, u32)>> {
+    let mut header = [0; 30];
+    file.read_exact(&mut header)?;
+    if &header[0..4] != b"RIFF" || &header[8..12] != b"WEBP" {
+        return Ok(None);
+    }
+    let u24 = |at: usize| u32::from_le_bytes([header[at], header[at + 1], header[at + 2], 0]);
+    Ok(match &header[12..16] {
+        b"VP8 " => Some((u16_at(&header, 26) as u32 & 0x3fff, u16_at(&header, 28) as u32 & 0x3fff)),
+        b"VP8L" => {
+            let bits = u32_at(&header, 21);
+            Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+        }
+        b"VP8X" => Some((u24(24) + 1, u24(27) + 1)),
+        _ => None,
+    })
+}
+
+/// Reads the family name from a TrueType or OpenType font's name table,
+/// preferring the typographic family over the legacy one.
+pub fn font_family(path: &Path) -> Option<String> {
+    let ext = path.extension()?.to_string_lossy().to_lowercase();
+    if !["ttf", "otf", "ttc"].contains(&ext.as_str()) {
+        return None;
+    }
+    let mut buf = Vec::new();
+    File::open(path).ok()?.read_to_end(&mut buf).ok()?;
+    let check = |at: usize, len: usize| (at + len <= buf.len()).then_some(at);
+    // a collection names its fonts' offset tables; the first one will do
+    let font = match buf.starts_with(b"ttcf") {
+        true => u32_be(&buf, check(12, 4)?) as usize,
+        false => 0,
+    };
+    let tables = u16_be(&buf, check(font + 4, 2)?) as usize;
+    let name = (0..tables).find_map(|i| {
+        let record = check(font + 12 + i * 16, 16)?;
+        (&buf[record..record + 4] == b"name").then(|| u32_be(&buf, record + 8) as usize)
+    })?;
+    let count = u16_be(&buf, check(name + 2, 2)?) as usize;
+    let strings = name + u16_be(&buf, check(name + 4, 2)?) as usize;
+    let mut families = Vec::new();
+    for i in 0..count {
+        let record = check(name + 6 + i * 12, 12)?;
+        let (platform, name_id) = (u16_be(&buf, record), u16_be(&buf, record + 6));
+        let (len, offset) = (u16_be(&buf, record + 8) as usize, u16_be(&buf, record + 10) as usize);
+        let Some(at) = check(strings + offset, len) else {
+            continue;
+        };
+        let bytes = &buf[at..at + len];
+        let text = match platform {
+            // Unicode and Windows names are UTF-16BE, Macintosh ones a single byte per character
+            0 | 3 => String::from_utf16_lossy(&bytes.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect::<Vec<_>>()),
+            1 => bytes.iter().map(|&b| b as char).collect(),
+            _ => continue,
+        };
+        if name_id == 1 || name_id == 16 {
+            families.push((name_id, text));
+        }
+    }
+    families.sort_by_key(|(id, _)| std::cmp::Reverse(*id));
+    families.into_iter().map(|(_, text)| text).find(|t| !t.is_empty())
+}
@@ -0,0 +1,50 @@
+use std::collections::HashMap;
+
+use crate::scene::Node;
+
+/// Draws a scene as a Mermaid flowchart: solid arrows from each node to its
+/// children, and dotted ones, labelled with the signal and method, for the
+/// connections with all the `flags` bits.
+pub fn render(root: &Node, flags: u32) -> String {
+    let nodes = root.paths();
+    // Mermaid ids can't hold every character a node name can, so number them
+    let ids: HashMap<&str, String> = nodes.iter().enumerate().map(|(i, (path, _))| (path.as_str(), format!("n{}", i))).collect();
+    let mut out = String::from("graph TD\n");
+    for (path, node) in nodes.iter() {
+        let label = match (&node.instance, node._type.as_str()) {
+            (Some(res), "") => format!("{}<br/>{}", node.name, res.path),
+            (_, "") => node.name.clone(),
+            (_, _type) => format!("{} ({})", node.name, _type),
+        };
+        out += &format!("    {}[\"{}\"]\n", ids[path.as_str()], escape(&label));
+    }
+    for (path, node) in nodes.iter() {
+        for name in node.children.keys() {
+            let child = match path.as_str() {
+                "." => name.clone(),
+                path => format!("{}/{}", path, name),
+            };
+            out += &format!("    {} --> {}\n", ids[path.as_str()], ids[child.as_str()]);
+        }
+    }
+    for (_, node) in nodes.iter() {
+        for conn in node.connections.iter().filter(|c| c.flags & flags == flags) {
+            // connections name the root by its name as their target
+            let to = match conn.to == root.name {
+                true => ".",
+                false => conn.to.as_str(),
+            };
+            let (Some(from), Some(to)) = (ids.get(conn.from.as_str()), ids.get(to)) else {
+                continue;
+            };
+            let label = format!("{} → {}(){}", conn.signal, conn.method, conn.tags());
+            out += &format!("    {} -.->|\"{}\"| {}\n", from, escape(&label), to);
+        }
+    }
+    out
+}
+
+/// Escapes the characters that would end a quoted Mermaid label.
+fn escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use regex::Regex;
+
+use crate::deprecated::PROPERTIES;
+use crate::project::{self, Project};
+
+/// Property renames to apply, the project's own before the built-in ones.
+pub struct Renames {
+    /// (class, old name, new name), where an empty class matches any.
+    renames: Vec<(String, String, String)>,
+}
+
+impl Renames {
+    /// Combines the built-in renames with the project's `renamed_properties`.
+    pub fn new(configured: &BTreeMap<String, String>) -> Self {
+        let mut renames: Vec<(String, String, String)> = configured
+            .iter()
+            .map(|(old, new)| match old.split_once('.') {
+                Some((class, old)) => (class.to_string(), old.to_string(), new.clone()),
+                None => (String::new(), old.clone(), new.clone()),
+            })
+            .collect();
+        renames.extend(PROPERTIES.iter().map(|(class, old, new)| (class.to_string(), old.to_string(), new.to_string())));
+        Self { renames }
+    }
+
+    /// The new name of a property of a node of the given class, if it was renamed.
+    /// A rename for the class wins over one for any class.
+    pub fn rename(&self, class: &str, key: &str) -> Option<String> {
+        let applies = |(c, old, _): &&(String, String, String)| match old.strip_suffix('/') {
+            Some(_) => key.starts_with(old.as_str()),
+            None => key == old,
+        } && (c.is_empty() || c == class);
+        let (_, old, new) = self
+            .renames
+            .iter()
+            .filter(applies)
+            .min_by_key(|(c, _, _)| c.is_empty())?;
+        Some(format!("{}{}", new, &key[old.len()..]))
+    }
+}
+
+/// A property that was found under its old name.
+pub struct Rename {
+    pub node: String,
+    pub old: String,
+    pub new: String,
+}
+
+/// Finds the properties of a scene's nodes that have been renamed, returning them
+/// along with the scene's text with the renames applied.
+///
+/// The scene is edited line by line rather than parsed and written back, so
+/// everything else about the file stays as it was.
+pub fn scan(text: &str, renames: &Renames) -> (Vec<Rename>, String) {
+    let node_re = Regex::new(r#"^\[node name="(?P<name>[^"]+)"(?P<remainder>.*)\]$"#).unwrap();
+    let node_type_re = Regex::new(r#"\btype="(?P<type>[^"]+)""#).unwrap();
+    let node_parent_re = Regex::new(r#"\bparent="(?P<parent>[^"]+)""#).unwrap();
+    let parameter_re = Regex::new(r"^(?P<k>_?[a-z][a-z0-9_]*(?:/[A-Za-z0-9_]+)*) = ").unwrap();
+
+    let mut found = Vec::new();
+    let mut out = String::with_capacity(text.len());
+    // the path and class of the node whose section we're in
+    let mut node: Option<(String, String)> = None;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if let Some(caps) = node_re.captures(trimmed) {
+            let remainder = caps.name("remainder").unwrap().as_str();
+            let path = match node_parent_re.captures(remainder).map(|c| c["parent"].to_string()) {
+                None => ".".to_string(),
+                Some(parent) if parent == "." => caps["name"].to_string(),
+                Some(parent) => format!("{}/{}", parent, &caps["name"]),
+            };
+            let class = node_type_re.captures(remainder).map_or(String::new(), |c| c["type"].to_string());
+            node = Some((path, class));
+        } else if trimmed.starts_with('[') {
+            node = None;
+        } else if let (Some((path, class)), Some(caps)) = (&node, parameter_re.captures(trimmed)) {
+            if let Some(new) = renames.rename(class, &caps["k"]) {
+                out.push_str(&new);
+                out.push_str(&line[caps["k"].len()..]);
+                found.push(Rename { node: path.clone(), old: caps["k"].to_string(), new });
+                continue;
+            }
+        }
+        out.push_str(line);
+    }
+    (found, out)
+}
+
+/// Reports the renamed properties in the scenes at `path`, and with `write`,
+/// renames them in place. Returns how many were found.
+pub fn run(path: &Path, project: Option<&Project>, renames: &Renames, write: bool) -> io::Result<usize> {
+    let files = match path.is_dir() {
+        true => project::list_files(path, &["tscn"]),
+        false => vec![path.to_path_buf()],
+    };
+    let (mut found, mut scenes) = (0, 0);
+    for file in files.iter() {
+        let text = fs::read_to_string(file)?;
+        let (renamed, migrated) = scan(&text, renames);
+        if renamed.is_empty() {
+            continue;
+        }
+        println!("{}", project.and_then(|p| p.res_path(file)).unwrap_or_else(|| file.display().to_string()));
+        for rename in renamed.iter() {
+            println!("  {}: {} -> {}", rename.node, rename.old, rename.new);
+        }
+        if write {
+            // write beside the scene and move it into place, so an interrupted
+            // run never leaves a half-written file
+            let partial = file.with_extension("tscn.partial");
+            fs::write(&partial, migrated)?;
+            fs::rename(&partial, file)?;
+        }
+        found += renamed.len();
+        scenes += 1;
+    }
+    match write {
+        true => println!("renamed {} properties in {} of {} scenes", found, scenes, files.len()),
+        false => println!("{} renamed properties in {} of {} scenes", found, scenes, files.len()),
+    }
+    Ok(found)
+}
@@ -0,0 +1,57 @@
+use std::collections::HashSet;
+use std::io;
+
+use crate::project::Project;
+use crate::report;
+use crate::timings::Timings;
+
+/// Lists the project's scenes that nothing uses: no other scene instances them,
+/// they are not autoloaded, and they are not the main scene. Returns how many
+/// there were.
+pub fn report(project: &Project, timings: &mut Timings) -> io::Result<usize> {
+    let mut used: HashSet<String> = HashSet::new();
+    for (_, path) in project.settings("autoload")? {
+        used.insert(canonical(project, path.trim_start_matches('*')));
+    }
+    for (key, path) in project.settings("application")? {
+        if key == "run/main_scene" {
+            used.insert(canonical(project, &path));
+        }
+    }
+    timings.lap("read project.godot");
+
+    let files = project.files(&["tscn"]);
+    let scenes = report::parse_all(&files, Some(project), timings);
+    let mut names = Vec::new();
+    for (file, scene) in files.iter().zip(scenes) {
+        let name = project.res_path(file).unwrap_or_else(|| file.display().to_string());
+        // what a broken scene instances is unknown, but the scene itself may still be unused
+        let scene = match scene {
+            Ok(scene) => scene,
+            Err(err) => {
+                eprintln!("gdtree: skipping {}", err);
+                names.push(name);
+                continue;
+            }
+        };
+        // a scene instancing itself doesn't keep it alive
+        used.extend(scene.ext_resources.iter().map(|res| res.path.clone()).filter(|path| *path != name));
+        names.push(name);
+    }
+
+    let orphans: Vec<&String> = names.iter().filter(|name| !used.contains(*name)).collect();
+    for name in orphans.iter() {
+        println!("{}", name);
+    }
+    println!("{} of {} scenes are never instanced, autoloaded or run as the main scene", orphans.len(), files.len());
+    Ok(orphans.len())
+}
+
+/// The res:// path a project.godot setting names, which Godot 4.3 and later
+/// may write as a uid:// instead.
+fn canonical(project: &Project, path: &str) -> String {
+    match path.starts_with("uid://") {
+        true => project.uid_path(path).unwrap_or(path).to_string(),
+        false => path.to_string(),
+    }
+}
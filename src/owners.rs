@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+
+use crate::project::Project;
+use crate::report;
+
+/// Where GitHub and GitLab look for a CODEOWNERS file, relative to the top of
+/// the repository.
+const CODEOWNERS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS", ".gitlab/CODEOWNERS"];
+
+/// A line of CODEOWNERS.
+struct Rule {
+    pattern: Regex,
+    owners: Vec<String>,
+}
+
+/// Who owns each scene, going by the `owners` table of the project's config
+/// when it has one, or else by the repository's CODEOWNERS file.
+#[derive(Default)]
+pub struct Owners {
+    /// res:// globs and their owners, from the config.
+    globs: BTreeMap<String, Vec<String>>,
+    /// Patterns and owners from CODEOWNERS, in the file's order, with the
+    /// directory its paths are relative to.
+    codeowners: Option<(PathBuf, Vec<Rule>)>,
+    /// The project root, that res:// paths are resolved against.
+    root: PathBuf,
+}
+
+impl Owners {
+    pub fn load(project: Option<&Project>, globs: &BTreeMap<String, Vec<String>>) -> io::Result<Self> {
+        let Some(project) = project else {
+            return Ok(Self::default());
+        };
+        let root = project.root.clone();
+        if !globs.is_empty() {
+            return Ok(Self { globs: globs.clone(), codeowners: None, root });
+        }
+        let codeowners = match find(&root) {
+            Some((base, file)) => Some((base, parse(&fs::read_to_string(file)?))),
+            None => None,
+        };
+        Ok(Self { globs: BTreeMap::new(), codeowners, root })
+    }
+
+    /// Whether any scene can have owners at all.
+    pub fn is_empty(&self) -> bool {
+        self.globs.is_empty() && self.codeowners.as_ref().is_none_or(|(_, rules)| rules.is_empty())
+    }
+
+    /// The owners of a scene, given by its res:// path or, outside a project,
+    /// its file path. Of the config's globs, the longest that matches decides;
+    /// in CODEOWNERS, as on GitHub, the last matching line does.
+    pub fn of(&self, scene: &str) -> &[String] {
+        if !self.globs.is_empty() {
+            return self
+                .globs
+                .iter()
+                .filter(|(glob, _)| report::glob_match(glob, scene))
+                .max_by_key(|(glob, _)| glob.len())
+                .map_or(&[], |(_, owners)| owners.as_slice());
+        }
+        let Some((base, rules)) = &self.codeowners else {
+            return &[];
+        };
+        let file = match scene.strip_prefix("res://") {
+            Some(rel) => self.root.join(rel),
+            None => PathBuf::from(scene),
+        };
+        let file = fs::canonicalize(&file).unwrap_or(file);
+        let Ok(relative) = file.strip_prefix(base) else {
+            return &[];
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        rules.iter().rev().find(|rule| rule.pattern.is_match(&relative)).map_or(&[], |rule| rule.owners.as_slice())
+    }
+}
+
+/// The nearest CODEOWNERS file at or above the project, and the directory its
+/// paths are relative to. The search stops at the top of the repository.
+fn find(root: &Path) -> Option<(PathBuf, PathBuf)> {
+    let root = fs::canonicalize(root).ok()?;
+    for dir in root.ancestors() {
+        if let Some(file) = CODEOWNERS.iter().map(|name| dir.join(name)).find(|file| file.is_file()) {
+            return Some((dir.to_path_buf(), file));
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Reads the rules of a CODEOWNERS file. A line naming no owners leaves the
+/// paths it matches unowned.
+fn parse(text: &str) -> Vec<Rule> {
+    let mut rules = Vec::new();
+    for line in text.lines() {
+        let line = line.split_once('#').map_or(line, |(rule, _)| rule).trim();
+        let mut words = line.split_whitespace();
+        let Some(pattern) = words.next().and_then(pattern) else {
+            continue;
+        };
+        rules.push(Rule { pattern, owners: words.map(str::to_string).collect() });
+    }
+    rules
+}
+
+/// Translates a CODEOWNERS path pattern, which follows .gitignore's rules, into
+/// a regular expression over paths relative to the top of the repository.
+fn pattern(glob: &str) -> Option<Regex> {
+    let trimmed = glob.trim_start_matches('/').trim_end_matches('/');
+    // a slash at the start or in the middle ties the pattern to the top; without
+    // one it matches at any depth
+    let anchored = glob.starts_with('/') || trimmed.contains('/');
+    let mut regex = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut rest = trimmed;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("**/") {
+            regex += "(?:.*/)?";
+            rest = after;
+        } else if let Some(after) = rest.strip_prefix("**") {
+            regex += ".*";
+            rest = after;
+        } else {
+            let c = rest.chars().next()?;
+            match c {
+                '*' => regex += "[^/]*",
+                '?' => regex += "[^/]",
+                c => regex += &regex::escape(&c.to_string()),
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    // a directory's pattern covers everything beneath it
+    regex += if glob.ends_with('/') { "/.*$" } else { "(?:/.*)?$" };
+    Regex::new(&regex).ok()
+}
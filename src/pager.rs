@@ -0,0 +1,56 @@
+use std::env;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use ratatui::crossterm::terminal;
+
+/// Output held back until it is known whether it fits on the terminal, as git
+/// does for `git log`: what is taller than the window goes through `$PAGER`
+/// (less by default), and the rest is printed as usual.
+#[derive(Default)]
+pub struct Pager {
+    buffer: Vec<u8>,
+}
+
+impl Write for Pager {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Pager {
+    pub fn finish(self) -> io::Result<()> {
+        let height = terminal::size().map_or(usize::MAX, |(_, rows)| rows as usize);
+        let lines = self.buffer.iter().filter(|&&b| b == b'\n').count();
+        // an empty PAGER, or cat, asks for no paging
+        let pager = env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+        let mut words = pager.split_whitespace();
+        let program = match words.next() {
+            Some(program) if lines >= height && program != "cat" => program,
+            _ => return io::stdout().lock().write_all(&self.buffer),
+        };
+        let mut command = Command::new(program);
+        command.args(words).stdin(Stdio::piped());
+        // like git: quit when it fits after all, keep the colors and leave the
+        // text on screen afterwards
+        if env::var_os("LESS").is_none() {
+            command.env("LESS", "FRX");
+        }
+        let mut child = match command.spawn() {
+            Ok(child) => child,
+            Err(_) => return io::stdout().lock().write_all(&self.buffer),
+        };
+        let written = child.stdin.take().unwrap().write_all(&self.buffer);
+        child.wait()?;
+        // quitting the pager before the end closes the pipe, which is fine
+        match written {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            written => written,
+        }
+    }
+}
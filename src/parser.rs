@@ -0,0 +1,257 @@
+use std::iter::Peekable;
+
+use crate::lexer::{self, Error, Kind, Lexer, Token};
+
+/// A value of the Godot text resource format, with the bytes of the text it
+/// was read from.
+#[derive(Debug)]
+pub struct Value {
+    pub kind: ValueKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+#[derive(Debug)]
+pub enum ValueKind {
+    /// A string, StringName or NodePath, with its escapes undone.
+    String(String),
+    /// A number, `true`, `false`, `null` or other bare word.
+    Word(String),
+    /// A constructor such as `Vector2(1, 2)` or `ExtResource("1_x")`. Typed
+    /// collections like `Array[int]([1])` list their element types in `types`,
+    /// and the `"key": value` pairs of an `Object(...)` are flattened into `args`.
+    Call { name: String, types: Vec<Value>, args: Vec<Value> },
+    Array(Vec<Value>),
+    Dictionary(Vec<(Value, Value)>),
+}
+
+impl Value {
+    /// The text the value was read from, as written.
+    pub fn raw<'a>(&self, text: &'a str) -> &'a str {
+        &text[self.start..self.end]
+    }
+
+    /// The contents of a string or bare word, such as the `"0"` of `index="0"` or
+    /// the `3` of `format=3`.
+    pub fn as_str(&self) -> Option<&str> {
+        match &self.kind {
+            ValueKind::String(text) | ValueKind::Word(text) => Some(text),
+            _ => None,
+        }
+    }
+
+    /// The kind and id of an `ExtResource(id)` or `SubResource(id)`.
+    pub fn reference(&self) -> Option<(&str, &str)> {
+        match &self.kind {
+            ValueKind::Call { name, args, .. } if (name == "ExtResource" || name == "SubResource") && args.len() == 1 => {
+                Some((name.as_str(), args[0].as_str()?))
+            }
+            _ => None,
+        }
+    }
+
+    /// Every `ExtResource` or `SubResource` within the value, the value itself included.
+    pub fn references(&self) -> Vec<&Value> {
+        let mut found = Vec::new();
+        self.collect_references(&mut found);
+        found
+    }
+
+    fn collect_references<'a>(&'a self, found: &mut Vec<&'a Value>) {
+        if self.reference().is_some() {
+            found.push(self);
+            return;
+        }
+        match &self.kind {
+            ValueKind::Call { types, args, .. } => types.iter().chain(args.iter()).for_each(|v| v.collect_references(found)),
+            ValueKind::Array(items) => items.iter().for_each(|v| v.collect_references(found)),
+            ValueKind::Dictionary(pairs) => pairs.iter().for_each(|(k, v)| {
+                k.collect_references(found);
+                v.collect_references(found);
+            }),
+            _ => (),
+        }
+    }
+}
+
+/// One statement of a scene or resource file.
+#[derive(Debug)]
+pub enum Statement {
+    /// A bracketed section header such as `[node name="Main" type="Node"]`,
+    /// starting at byte `start`.
+    Section { tag: String, attrs: Vec<(String, Value)>, start: usize },
+    /// A `key = value` line of the section above it, which may run over several lines.
+    Assignment { key: String, value: Value },
+}
+
+impl Statement {
+    /// The value of a section attribute.
+    pub fn attr(&self, key: &str) -> Option<&Value> {
+        match self {
+            Statement::Section { attrs, .. } => attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v),
+            Statement::Assignment { .. } => None,
+        }
+    }
+}
+
+/// Parses the text of a `.tscn` scene, `.tres` resource or `project.godot`.
+pub fn parse(text: &str) -> Result<Vec<Statement>, Error> {
+    let mut parser = Parser { text, tokens: Lexer::new(text).peekable(), end: 0 };
+    let mut statements = Vec::new();
+    while let Some(token) = parser.next_token()? {
+        statements.push(match token.kind {
+            Kind::Punct('[') => parser.section(token)?,
+            Kind::Word | Kind::String => {
+                let key = parser.name(token);
+                parser.expect('=', "`=` after the property name")?;
+                Statement::Assignment { key, value: parser.value()? }
+            }
+            _ => return Err(parser.error(token.start, "expected a section or a property")),
+        });
+    }
+    Ok(statements)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    tokens: Peekable<Lexer<'a>>,
+    /// Where the last token taken ended.
+    end: usize,
+}
+
+impl Parser<'_> {
+    fn error(&self, offset: usize, message: impl Into<String>) -> Error {
+        Error::new(self.text, offset, message)
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, Error> {
+        let token = self.tokens.next().transpose()?;
+        if let Some(token) = token {
+            self.end = token.end;
+        }
+        Ok(token)
+    }
+
+    fn peek(&mut self) -> Option<Token> {
+        self.tokens.peek().and_then(|t| t.as_ref().ok()).copied()
+    }
+
+    /// Takes the next token when it is the given punctuation.
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek().is_some_and(|t| t.kind == Kind::Punct(c));
+        if found {
+            self.end = self.tokens.next().unwrap().unwrap().end;
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char, what: &str) -> Result<Token, Error> {
+        match self.next_token()? {
+            Some(token) if token.kind == Kind::Punct(c) => Ok(token),
+            Some(token) => Err(self.error(token.start, format!("expected {}", what))),
+            None => Err(self.error(self.text.len(), format!("expected {} before the end of the file", what))),
+        }
+    }
+
+    /// A section tag, attribute or property name; properties with unusual
+    /// characters in their names are quoted.
+    fn name(&self, token: Token) -> String {
+        let raw = &self.text[token.start..token.end];
+        match token.kind {
+            Kind::String => lexer::unescape(raw),
+            _ => raw.to_string(),
+        }
+    }
+
+    fn section(&mut self, open: Token) -> Result<Statement, Error> {
+        let tag = match self.next_token()? {
+            Some(token) if token.kind == Kind::Word => self.name(token),
+            _ => return Err(self.error(open.start, "expected a section name after `[`")),
+        };
+        let mut attrs = Vec::new();
+        loop {
+            match self.next_token()? {
+                Some(token) if token.kind == Kind::Punct(']') => break,
+                Some(token) if token.kind == Kind::Word => {
+                    let key = self.name(token);
+                    self.expect('=', "`=` after the attribute name")?;
+                    attrs.push((key, self.value()?));
+                }
+                Some(token) => return Err(self.error(token.start, format!("expected an attribute or `]` in the {} section", tag))),
+                None => return Err(self.error(open.start, format!("the {} section header is never closed", tag))),
+            }
+        }
+        Ok(Statement::Section { tag, attrs, start: open.start })
+    }
+
+    fn value(&mut self) -> Result<Value, Error> {
+        let Some(token) = self.next_token()? else {
+            return Err(self.error(self.text.len(), "expected a value before the end of the file"));
+        };
+        let raw = &self.text[token.start..token.end];
+        let kind = match token.kind {
+            Kind::String => ValueKind::String(lexer::unescape(raw)),
+            Kind::Punct('[') => ValueKind::Array(self.list(']')?),
+            Kind::Punct('{') => {
+                let mut pairs = Vec::new();
+                while !self.eat('}') {
+                    let key = self.value()?;
+                    self.expect(':', "`:` after the dictionary key")?;
+                    pairs.push((key, self.value()?));
+                    if !self.eat(',') {
+                        self.expect('}', "`,` or `}` in the dictionary")?;
+                        break;
+                    }
+                }
+                ValueKind::Dictionary(pairs)
+            }
+            Kind::Word => {
+                // Array[int]([...]), with no space before the bracket
+                let types = match self.peek() {
+                    Some(next) if next.kind == Kind::Punct('[') && next.start == token.end => {
+                        self.eat('[');
+                        self.list(']')?
+                    }
+                    _ => Vec::new(),
+                };
+                match self.eat('(') {
+                    true => ValueKind::Call { name: raw.to_string(), types, args: self.arguments()? },
+                    false if types.is_empty() => ValueKind::Word(raw.to_string()),
+                    false => return Err(self.error(token.end, format!("expected `(` after the type of {}", raw))),
+                }
+            }
+            Kind::Punct(c) => return Err(self.error(token.start, format!("expected a value, not `{}`", c))),
+        };
+        Ok(Value { kind, start: token.start, end: self.end })
+    }
+
+    /// Comma-separated values up to the closing bracket, which may follow a trailing comma.
+    fn list(&mut self, close: char) -> Result<Vec<Value>, Error> {
+        let mut items = Vec::new();
+        while !self.eat(close) {
+            items.push(self.value()?);
+            if !self.eat(',') {
+                self.expect(close, &format!("`,` or `{}`", close))?;
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    /// The arguments of a constructor, up to the closing parenthesis.
+    fn arguments(&mut self) -> Result<Vec<Value>, Error> {
+        let mut args = Vec::new();
+        while !self.eat(')') {
+            args.push(self.value()?);
+            // Object(InputEventKey, "keycode": 65, ...)
+            if self.eat(':') {
+                args.push(self.value()?);
+            }
+            if !self.eat(',') {
+                self.expect(')', "`,` or `)`")?;
+                break;
+            }
+        }
+        Ok(args)
+    }
+}
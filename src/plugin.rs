@@ -0,0 +1,109 @@
+use std::ffi::{c_char, CStr, CString};
+use std::io;
+use std::path::Path;
+
+use libloading::Library;
+use serde_json::Value;
+
+/// Version of the table below; bumped whenever its layout changes.
+pub const ABI_VERSION: u32 = 1;
+
+/// The function table a plugin returns from its exported `gdtree_plugin()`.
+///
+/// Strings cross the boundary as NUL-terminated UTF-8. Every hook is optional,
+/// and any string a hook returns is handed back to the plugin's `free`.
+#[repr(C)]
+pub struct PluginV1 {
+    pub abi_version: u32,
+    /// Name of the plugin, also the `--format` value its `render` hook answers to.
+    pub name: *const c_char,
+    /// Renders the scene JSON into a complete output document.
+    pub render: Option<unsafe extern "C" fn(scene: *const c_char) -> *mut c_char>,
+    /// Summarizes a sub_resource given its type and JSON, or returns null to decline.
+    pub decode: Option<unsafe extern "C" fn(_type: *const c_char, resource: *const c_char) -> *mut c_char>,
+    /// Checks the scene JSON, returning a JSON array of `{"node": ..., "message": ...}`.
+    pub lint: Option<unsafe extern "C" fn(scene: *const c_char) -> *mut c_char>,
+    pub free: unsafe extern "C" fn(s: *mut c_char),
+}
+
+/// A loaded plugin library.
+pub struct Plugin {
+    pub name: String,
+    table: &'static PluginV1,
+    // keeps the code behind `table` mapped
+    _library: Library,
+}
+
+impl Plugin {
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let error = |msg: String| io::Error::other(format!("plugin {}: {}", path.display(), msg));
+        // SAFETY: loading a library runs its initializers; plugins are only
+        // loaded when named explicitly on the command line.
+        unsafe {
+            let library = Library::new(path).map_err(|e| error(e.to_string()))?;
+            let entry = library
+                .get::<unsafe extern "C" fn() -> *const PluginV1>(b"gdtree_plugin\0")
+                .map_err(|e| error(e.to_string()))?;
+            let table = entry().as_ref().ok_or_else(|| error("gdtree_plugin() returned null".to_string()))?;
+            if table.abi_version != ABI_VERSION {
+                return Err(error(format!("built for plugin ABI {}, expected {}", table.abi_version, ABI_VERSION)));
+            }
+            if table.name.is_null() {
+                return Err(error("plugin has no name".to_string()));
+            }
+            let name = CStr::from_ptr(table.name).to_string_lossy().to_string();
+            Ok(Self { name, table, _library: library })
+        }
+    }
+
+    pub fn renders(&self) -> bool {
+        self.table.render.is_some()
+    }
+
+    pub fn lints(&self) -> bool {
+        self.table.lint.is_some()
+    }
+
+    pub fn render(&self, scene: &Value) -> Option<String> {
+        let scene = CString::new(scene.to_string()).ok()?;
+        // SAFETY: the hook receives a valid C string and returns one it owns or null
+        unsafe { self.take(self.table.render?(scene.as_ptr())) }
+    }
+
+    pub fn decode(&self, _type: &str, resource: &Value) -> Option<String> {
+        let _type = CString::new(_type).ok()?;
+        let resource = CString::new(resource.to_string()).ok()?;
+        // SAFETY: as for render
+        unsafe { self.take(self.table.decode?(_type.as_ptr(), resource.as_ptr())) }
+    }
+
+    /// Runs the plugin's lint hook, returning `(node path, message)` pairs.
+    pub fn lint(&self, scene: &Value) -> Option<Vec<(String, String)>> {
+        let scene = CString::new(scene.to_string()).ok()?;
+        // SAFETY: as for render
+        let findings = unsafe { self.take(self.table.lint?(scene.as_ptr()))? };
+        let findings: Value = serde_json::from_str(&findings).ok()?;
+        Some(
+            findings
+                .as_array()?
+                .iter()
+                .map(|f| {
+                    (
+                        f["node"].as_str().unwrap_or(".").to_string(),
+                        f["message"].as_str().unwrap_or_default().to_string(),
+                    )
+                })
+                .collect(),
+        )
+    }
+
+    /// Copies a string returned by a hook and releases the plugin's copy.
+    unsafe fn take(&self, s: *mut c_char) -> Option<String> {
+        if s.is_null() {
+            return None;
+        }
+        let owned = CStr::from_ptr(s).to_string_lossy().to_string();
+        (self.table.free)(s);
+        Some(owned)
+    }
+}
@@ -0,0 +1,429 @@
+use regex::Regex;
+
+use crate::media;
+use crate::project::Project;
+use crate::scene::Parameter;
+use crate::version::GodotVersion;
+
+/// Characters of the curve sparkline, lowest first.
+const BARS: &[char] = &['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Characters of the gradient bar, darkest first.
+const SHADES: &[char] = &[' ', '░', '▒', '▓', '█'];
+const WIDTH: usize = 16;
+
+/// Summarizes a sub_resource on one line, to print in place of its raw
+/// properties: a picture of a Curve or Gradient, or of a texture made from
+/// one, the family, settings and fallbacks of a font, and the dimensions of a
+/// collision shape or navigation mesh, or the properties a multiplayer
+/// synchronizer replicates.
+pub fn summary(_type: &str, params: &[Parameter], project: Option<&Project>, version: GodotVersion) -> Option<String> {
+    match _type {
+        "Curve" => curve(params),
+        "Gradient" => gradient(params),
+        "FontVariation" | "SystemFont" | "FontFile" | "DynamicFont" => font(_type, params, project),
+        "SceneReplicationConfig" => replication(params),
+        "Sky" => {
+            let material = params.iter().find(|p| p.key == "sky_material")?;
+            Some(format!("Sky of {}", material.sub_type.as_deref().unwrap_or(&material.val)))
+        }
+        _ if _type.contains("Shape") || _type.starts_with("Navigation") => shape(_type, params, version),
+        "CurveTexture" | "GradientTexture" | "GradientTexture1D" | "GradientTexture2D" => {
+            let source = params.iter().find(|p| p.key == "curve" || p.key == "gradient")?;
+            let picture = summary(source.sub_type.as_deref()?, &source.sub_params, project, version)?;
+            Some(format!("{} of {}", _type, picture))
+        }
+        _ => None,
+    }
+}
+
+/// Particle settings by what they affect, with the prefix each group's keys drop.
+const PARTICLE_GROUPS: &[(&str, &[&str], &str)] = &[
+    ("emission", &["emission_"], "emission_"),
+    ("direction", &["direction", "spread", "flatness", "gravity"], ""),
+    ("velocity", &["initial_velocity", "angular_velocity", "orbit_velocity", "radial_velocity", "velocity_", "damping"], ""),
+    ("acceleration", &["linear_accel", "radial_accel", "tangential_accel"], ""),
+    ("rotation", &["angle"], "angle_"),
+    ("scale", &["scale"], "scale_"),
+    ("color", &["color", "hue_variation", "alpha_curve", "emission_curve"], ""),
+    ("animation", &["anim_"], "anim_"),
+    ("turbulence", &["turbulence_"], "turbulence_"),
+    ("collision", &["collision_"], "collision_"),
+    ("sub-emitter", &["sub_emitter_"], "sub_emitter_"),
+];
+
+const ENVIRONMENT_GROUPS: &[(&str, &[&str], &str)] = &[
+    ("background", &["background_"], "background_"),
+    ("sky", &["sky"], "sky_"),
+    ("ambient light", &["ambient_light_"], "ambient_light_"),
+    ("reflected light", &["reflected_light_"], "reflected_light_"),
+    ("tonemap", &["tonemap_"], "tonemap_"),
+    ("auto exposure", &["auto_exposure_"], "auto_exposure_"),
+    ("glow", &["glow_"], "glow_"),
+    ("fog", &["fog_"], "fog_"),
+    ("volumetric fog", &["volumetric_fog_"], "volumetric_fog_"),
+    ("ssr", &["ssr_"], "ssr_"),
+    ("ssao", &["ssao_"], "ssao_"),
+    ("ssil", &["ssil_"], "ssil_"),
+    ("sdfgi", &["sdfgi_"], "sdfgi_"),
+    ("depth of field", &["dof_blur_"], "dof_blur_"),
+    ("adjustments", &["adjustment_"], "adjustment_"),
+];
+
+const SKY_GROUPS: &[(&str, &[&str], &str)] = &[
+    ("material", &["sky_material"], "sky_"),
+    ("sky", &["sky_"], "sky_"),
+    ("ground", &["ground_"], "ground_"),
+    ("sun", &["sun_"], "sun_"),
+    ("radiance", &["radiance_"], "radiance_"),
+];
+
+/// Breaks a sub_resource with many settings down into labelled groups, each
+/// summarized on one line, for printing beneath it instead of its raw properties.
+pub fn groups(_type: &str, params: &[Parameter], project: Option<&Project>, version: GodotVersion) -> Option<Vec<(String, String)>> {
+    match _type {
+        "ParticleProcessMaterial" | "ParticlesMaterial" => Some(grouped(_type, params, PARTICLE_GROUPS, project, version)),
+        "Environment" => Some(grouped(_type, params, ENVIRONMENT_GROUPS, project, version)),
+        // Godot 3's ProceduralSky holds the colors that Godot 4 moved to a sky material
+        "Sky" | "ProceduralSky" | "PanoramaSky" => Some(grouped(_type, params, SKY_GROUPS, project, version)),
+        _ => None,
+    }
+}
+
+fn grouped(
+    _type: &str,
+    params: &[Parameter],
+    groups: &[(&str, &[&str], &str)],
+    project: Option<&Project>,
+    version: GodotVersion,
+) -> Vec<(String, String)> {
+    let mut lines: Vec<(String, Vec<String>)> = Vec::new();
+    for param in params.iter().filter(|p| !p.key.starts_with('_')) {
+        // a _max is printed with its _min as one range
+        if let Some(min) = param.key.strip_suffix("_max") {
+            if params.iter().any(|p| p.key == format!("{}_min", min)) {
+                continue;
+            }
+        }
+        let (group, strip) = groups
+            .iter()
+            .find(|(_, prefixes, _)| prefixes.iter().any(|prefix| param.key.starts_with(prefix)))
+            .map_or(("other", ""), |(group, _, strip)| (*group, *strip));
+        let mut val = match &param.sub_type {
+            Some(sub_type) => summary(sub_type, &param.sub_params, project, version).unwrap_or(sub_type.clone()),
+            None => setting(_type, &param.key, &param.val, version),
+        };
+        let mut key = param.key.as_str();
+        if let Some(min) = key.strip_suffix("_min") {
+            if let Some(max) = params.iter().find(|p| p.key == format!("{}_max", min)) {
+                val = format!("{}..{}", val, max.val);
+                key = min;
+            }
+        }
+        let key = key.strip_prefix(strip).unwrap_or(key).replace('_', " ");
+        let entry = match val.as_str() {
+            "true" => key,
+            // no need to repeat the group's name
+            _ if key == group => val,
+            _ => format!("{} {}", key, val),
+        };
+        match lines.iter_mut().find(|(name, _)| name == group) {
+            Some((_, entries)) => entries.push(entry),
+            None => lines.push((group.to_string(), vec![entry])),
+        }
+    }
+    // keep the groups in the table's order, with anything unexpected last
+    let order = |name: &str| groups.iter().position(|(group, _, _)| *group == name).unwrap_or(groups.len());
+    lines.sort_by_key(|(name, _)| order(name));
+    lines.into_iter().map(|(name, entries)| (name, entries.join(", "))).collect()
+}
+
+/// Names the enumerated settings that are stored as bare numbers.
+fn setting(_type: &str, key: &str, val: &str, version: GodotVersion) -> String {
+    let godot3 = version != GodotVersion::Godot4;
+    let names: &[&str] = match (_type, key) {
+        ("ParticleProcessMaterial", "emission_shape") => &["point", "sphere", "sphere surface", "box", "points", "directed points", "ring"],
+        ("ParticlesMaterial", "emission_shape") => &["point", "sphere", "box", "points", "directed points", "ring"],
+        ("Environment", "background_mode") if godot3 => &["clear color", "custom color", "sky", "color and sky", "canvas", "keep", "camera feed"],
+        ("Environment", "background_mode") => &["clear color", "custom color", "sky", "canvas", "keep", "camera feed"],
+        ("Environment", "tonemap_mode") if godot3 => &["linear", "reinhard", "filmic", "aces", "aces fitted"],
+        ("Environment", "tonemap_mode") => &["linear", "reinhard", "filmic", "aces", "agx"],
+        ("Environment", "glow_blend_mode") => &["additive", "screen", "softlight", "replace", "mix"],
+        ("Environment", "ambient_light_source") => &["background", "disabled", "color", "sky"],
+        ("Environment", "reflected_light_source") => &["background", "disabled", "sky"],
+        _ => &[],
+    };
+    match val.parse::<usize>().ok().and_then(|i| names.get(i)) {
+        Some(name) => name.to_string(),
+        None => val.to_string(),
+    }
+}
+
+fn curve(params: &[Parameter]) -> Option<String> {
+    // each point is written as Vector2(x, y), left tangent, right tangent, left mode, right mode
+    let data = numbers(value(params, "_data")?);
+    let points: Vec<&[f64]> = data.chunks_exact(6).collect();
+    if points.is_empty() {
+        return None;
+    }
+    let min_value = value(params, "min_value").and_then(|v| v.parse().ok()).unwrap_or(0.0);
+    let max_value = value(params, "max_value").and_then(|v| v.parse().ok()).unwrap_or(1.0);
+    let min_domain = value(params, "min_domain").and_then(|v| v.parse().ok()).unwrap_or(0.0);
+    let max_domain = value(params, "max_domain").and_then(|v| v.parse().ok()).unwrap_or(1.0);
+
+    let sample = |x: f64| -> f64 {
+        let first = points[0];
+        let last = points[points.len() - 1];
+        if x <= first[0] {
+            return first[1];
+        }
+        if x >= last[0] {
+            return last[1];
+        }
+        let i = points.iter().rposition(|p| p[0] <= x).unwrap_or(0);
+        let (a, b) = (points[i], points[i + 1]);
+        let d = b[0] - a[0];
+        if d <= 0.0 {
+            return b[1];
+        }
+        // the same cubic bezier Godot bakes, with control points a third of the way along
+        let t = (x - a[0]) / d;
+        let (p0, p1, p2, p3) = (a[1], a[1] + a[3] * d / 3.0, b[1] - b[2] * d / 3.0, b[1]);
+        let mt = 1.0 - t;
+        mt * mt * mt * p0 + 3.0 * mt * mt * t * p1 + 3.0 * mt * t * t * p2 + t * t * t * p3
+    };
+    let line: String = (0..WIDTH)
+        .map(|i| {
+            let x = min_domain + (max_domain - min_domain) * i as f64 / (WIDTH - 1) as f64;
+            let y = match max_value > min_value {
+                true => ((sample(x) - min_value) / (max_value - min_value)).clamp(0.0, 1.0),
+                false => 0.0,
+            };
+            BARS[(y * (BARS.len() - 1) as f64).round() as usize]
+        })
+        .collect();
+    Some(format!("Curve {} ({} points, {}..{})", line, points.len(), min_value, max_value))
+}
+
+fn gradient(params: &[Parameter]) -> Option<String> {
+    let offsets = value(params, "offsets").map_or_else(|| vec![0.0, 1.0], numbers);
+    let colors = value(params, "colors").map_or_else(|| vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0], numbers);
+    let stops: Vec<(f64, &[f64])> = offsets.iter().copied().zip(colors.chunks_exact(4)).collect();
+    if stops.is_empty() {
+        return None;
+    }
+    // interpolation_mode 1 is constant: each stop holds until the next
+    let constant = value(params, "interpolation_mode") == Some("1");
+
+    let sample = |x: f64| -> [f64; 4] {
+        let i = stops.iter().rposition(|(offset, _)| *offset <= x);
+        let color = |c: &[f64]| [c[0], c[1], c[2], c[3]];
+        match i {
+            None => color(stops[0].1),
+            Some(i) if i + 1 == stops.len() || constant => color(stops[i].1),
+            Some(i) => {
+                let ((oa, a), (ob, b)) = (stops[i], stops[i + 1]);
+                let t = match ob > oa {
+                    true => (x - oa) / (ob - oa),
+                    false => 1.0,
+                };
+                [0, 1, 2, 3].map(|c| a[c] + (b[c] - a[c]) * t)
+            }
+        }
+    };
+    let bar: String = (0..WIDTH)
+        .map(|i| {
+            let [r, g, b, a] = sample(i as f64 / (WIDTH - 1) as f64);
+            let luminance = ((0.2126 * r + 0.7152 * g + 0.0722 * b) * a).clamp(0.0, 1.0);
+            SHADES[(luminance * (SHADES.len() - 1) as f64).round() as usize]
+        })
+        .collect();
+    let names: Vec<String> = stops.iter().map(|(_, color)| hex(color)).collect();
+    Some(format!("Gradient [{}] ({})", bar, names.join(" → ")))
+}
+
+fn font(_type: &str, params: &[Parameter], project: Option<&Project>) -> Option<String> {
+    let mut parts = Vec::new();
+    let mut fallbacks = Vec::new();
+    for param in params.iter() {
+        let key = param.key.as_str();
+        match key {
+            // Godot 3 names the font file font_data, Godot 4 base_font
+            "font_data" | "base_font" => parts.insert(0, font_name(&param.val, project)),
+            "font_names" => parts.insert(0, strings(&param.val).join(", ")),
+            "size" => parts.push(format!("{}px", param.val)),
+            "outline_size" => parts.push(format!("outline {}", param.val)),
+            "font_weight" => parts.push(format!("weight {}", param.val)),
+            "font_italic" if param.val == "true" => parts.push("italic".to_string()),
+            "variation_embolden" => parts.push(format!("embolden {}", param.val)),
+            "variation_opentype" => parts.push(format!("features {}", param.val)),
+            "fallbacks" => fallbacks.extend(strings(&param.val).iter().map(|path| font_name(path, project))),
+            // Godot 3 lists fallbacks one per key, as fallback/0, fallback/1 and so on
+            _ if key.starts_with("fallback/") => fallbacks.push(font_name(&param.val, project)),
+            _ if key.starts_with("spacing_") || key.starts_with("extra_spacing_") => {
+                let what = key.trim_start_matches("extra_").trim_start_matches("spacing_");
+                parts.push(format!("{} spacing {}", what, param.val));
+            }
+            _ => {}
+        }
+    }
+    if !fallbacks.is_empty() {
+        parts.push(format!("fallbacks: {}", fallbacks.join(", ")));
+    }
+    match parts.is_empty() {
+        true => Some(_type.to_string()),
+        false => Some(format!("{} {}", _type, parts.join(", "))),
+    }
+}
+
+fn shape(_type: &str, params: &[Parameter], version: GodotVersion) -> Option<String> {
+    let get = |key: &str| value(params, key).map(numbers);
+    let scalar = |key: &str, default: f64| get(key).and_then(|n| n.first().copied()).unwrap_or(default);
+    // a missing property has the class default, which Godot leaves out of the file
+    let size = |default: &[f64]| match (get("size"), get("extents")) {
+        (Some(size), _) => size,
+        // Godot 3 stores half the size
+        (None, Some(extents)) => extents.iter().map(|e| e * 2.0).collect(),
+        (None, None) => default.to_vec(),
+    };
+    let count = |key: &str, per: usize| get(key).map_or(0, |n| n.len() / per);
+    let dimensions = |n: &[f64]| n.iter().map(|n| format!("{}", n)).collect::<Vec<_>>().join("×");
+    let summary = match _type {
+        "RectangleShape2D" => dimensions(&size(&[20.0, 20.0])),
+        "BoxShape3D" => dimensions(&size(&[1.0, 1.0, 1.0])),
+        // Godot 3's 3D shapes, without the suffix, were twice the size by default
+        "BoxShape" => dimensions(&size(&[2.0, 2.0, 2.0])),
+        "CircleShape2D" => format!("radius {}", scalar("radius", 10.0)),
+        "SphereShape3D" => format!("radius {}", scalar("radius", 0.5)),
+        "SphereShape" => format!("radius {}", scalar("radius", 1.0)),
+        // Godot 4 counts the rounded ends in the height
+        "CapsuleShape2D" => {
+            let height = if version == GodotVersion::Godot4 { 30.0 } else { 20.0 };
+            format!("radius {}, height {}", scalar("radius", 10.0), scalar("height", height))
+        }
+        "CapsuleShape3D" | "CylinderShape3D" => format!("radius {}, height {}", scalar("radius", 0.5), scalar("height", 2.0)),
+        "CapsuleShape" => format!("radius {}, height {}", scalar("radius", 1.0), scalar("height", 1.0)),
+        "CylinderShape" => format!("radius {}, height {}", scalar("radius", 1.0), scalar("height", 2.0)),
+        "SegmentShape2D" => format!("from ({}) to ({})", dimensions(&get("a").unwrap_or_default()), dimensions(&get("b").unwrap_or_default())),
+        "SeparationRayShape2D" | "SeparationRayShape3D" | "RayShape2D" | "RayShape" => format!("length {}", scalar("length", 20.0)),
+        "WorldBoundaryShape2D" | "LineShape2D" => format!("normal ({}), distance {}", dimensions(&get("normal").unwrap_or(vec![0.0, -1.0])), scalar("distance", 0.0)),
+        "ConvexPolygonShape2D" => format!("polygon with {}", plural(count("points", 2), "point")),
+        "ConcavePolygonShape2D" => plural(count("segments", 4), "segment"),
+        "ConvexPolygonShape3D" | "ConvexPolygonShape" => format!("hull with {}", plural(count("points", 3), "point")),
+        "ConcavePolygonShape3D" | "ConcavePolygonShape" => plural(count("data", 9).max(count("_faces", 9)), "triangle"),
+        "HeightMapShape3D" | "HeightMapShape" => format!("{}×{} heights", scalar("map_width", 2.0), scalar("map_depth", 2.0)),
+        // vertices plus polygons and outlines, which are nested arrays of vertex indices
+        "NavigationPolygon" | "NavigationMesh" => {
+            let polygons = value(params, "polygons").map_or(0, |v| v.matches("PackedInt32Array(").count() + v.matches("PoolIntArray(").count());
+            let outlines = value(params, "outlines").map_or(0, |v| v.matches("PackedVector2Array(").count() + v.matches("PoolVector2Array(").count());
+            let per = if _type == "NavigationMesh" { 3 } else { 2 };
+            let mut summary = format!("{}, {}", plural(count("vertices", per), "vertex"), plural(polygons, "polygon"));
+            if outlines > 0 {
+                summary += &format!(", {}", plural(outlines, "outline"));
+            }
+            summary
+        }
+        _ => return None,
+    };
+    Some(format!("{} {}", _type, summary))
+}
+
+fn replication(params: &[Parameter]) -> Option<String> {
+    // each property is written as properties/N/path plus its settings under the same N
+    let mut properties: Vec<(&str, String, Vec<&str>)> = Vec::new();
+    for param in params.iter() {
+        let Some((index, field)) = param.key.strip_prefix("properties/").and_then(|k| k.split_once('/')) else {
+            continue;
+        };
+        let at = match properties.iter().position(|(i, _, _)| *i == index) {
+            Some(at) => at,
+            None => {
+                properties.push((index, String::new(), Vec::new()));
+                properties.len() - 1
+            }
+        };
+        let property = &mut properties[at];
+        match (field, param.val.as_str()) {
+            ("path", path) => property.1 = node_path(path),
+            ("spawn", "true") => property.2.push("spawn"),
+            // Godot 4.2 replaced sync and watch with replication_mode
+            ("sync", "true") => property.2.push("sync"),
+            ("watch", "true") => property.2.push("watch"),
+            ("replication_mode", "0") => property.2.push("never"),
+            ("replication_mode", "1") => property.2.push("always"),
+            ("replication_mode", "2") => property.2.push("on change"),
+            _ => {}
+        }
+    }
+    if properties.is_empty() {
+        return Some("SceneReplicationConfig, nothing replicated".to_string());
+    }
+    let list: Vec<String> = properties
+        .iter()
+        .map(|(_, path, modes)| match modes.is_empty() {
+            true => path.clone(),
+            false => format!("{} ({})", path, modes.join(", ")),
+        })
+        .collect();
+    Some(format!("SceneReplicationConfig {}", list.join(", ")))
+}
+
+/// Rewrites a node property's value when the raw form hides what it means,
+/// such as the scenes a MultiplayerSpawner may spawn.
+pub fn property(key: &str, val: &str) -> Option<String> {
+    match key {
+        "_spawnable_scenes" => Some(strings(val).join(", ")),
+        _ => None,
+    }
+}
+
+/// Unwraps `NodePath("Sprite:frame")` to `Sprite:frame`.
+fn node_path(val: &str) -> String {
+    val.trim_start_matches("NodePath(").trim_end_matches(')').trim().trim_matches('"').to_string()
+}
+
+fn plural(n: usize, word: &str) -> String {
+    match (n, word) {
+        (1, _) => format!("1 {}", word),
+        (_, "vertex") => format!("{} vertices", n),
+        _ => format!("{} {}s", n, word),
+    }
+}
+
+/// Names a font file by its family, when it can be read, or else by its path.
+fn font_name(path: &str, project: Option<&Project>) -> String {
+    let family = project
+        .filter(|_| path.starts_with("res://"))
+        .and_then(|project| media::font_family(&project.resolve(path)));
+    match family {
+        Some(family) => family,
+        None => path.to_string(),
+    }
+}
+
+/// The quoted strings of an array value, such as a PackedStringArray or a list of paths.
+fn strings(val: &str) -> Vec<String> {
+    let string_re = Regex::new(r#""(?P<s>[^"]*)""#).unwrap();
+    string_re.captures_iter(val).map(|caps| caps["s"].to_string()).collect()
+}
+
+/// Formats a color as `#rrggbb`, adding the alpha when it isn't opaque.
+fn hex(color: &[f64]) -> String {
+    let byte = |c: f64| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+    match color[3] < 1.0 {
+        true => format!("#{:02x}{:02x}{:02x}{:02x}", byte(color[0]), byte(color[1]), byte(color[2]), byte(color[3])),
+        false => format!("#{:02x}{:02x}{:02x}", byte(color[0]), byte(color[1]), byte(color[2])),
+    }
+}
+
+fn value<'a>(params: &'a [Parameter], key: &str) -> Option<&'a str> {
+    params.iter().find(|p| p.key == key).map(|p| p.val.as_str())
+}
+
+/// Pulls the numbers out of an array value, skipping type names like `Vector2`.
+fn numbers(val: &str) -> Vec<f64> {
+    let number_re = Regex::new(r"[A-Za-z_][A-Za-z0-9_]*|(?P<n>-?\d+(?:\.\d*)?(?:e[-+]?\d+)?)").unwrap();
+    number_re
+        .captures_iter(val)
+        .filter_map(|caps| caps.name("n")?.as_str().parse().ok())
+        .collect()
+}
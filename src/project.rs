@@ -0,0 +1,165 @@
+use std::collections::HashMap;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// A Godot project on disk, rooted at the directory holding `project.godot`.
+#[derive(Debug)]
+pub struct Project {
+    pub root: PathBuf,
+    uids: OnceLock<HashMap<String, String>>,
+    classes: OnceLock<HashMap<String, String>>,
+}
+
+impl Project {
+    pub fn new(root: PathBuf) -> Self {
+        Self {
+            root,
+            uids: OnceLock::new(),
+            classes: OnceLock::new(),
+        }
+    }
+
+    /// Walks up from `start` to the nearest directory containing `project.godot`.
+    pub fn find(start: &Path) -> Option<Self> {
+        let start = fs::canonicalize(start).ok()?;
+        let mut dir = if start.is_dir() { Some(start.as_path()) } else { start.parent() };
+        while let Some(d) = dir {
+            if d.join("project.godot").is_file() {
+                return Some(Self::new(d.to_path_buf()));
+            }
+            dir = d.parent();
+        }
+        None
+    }
+
+    /// Maps a `res://` path onto the filesystem; other paths are returned as-is.
+    pub fn resolve(&self, path: &str) -> PathBuf {
+        match path.strip_prefix("res://") {
+            Some(rel) => self.root.join(rel),
+            None => PathBuf::from(path),
+        }
+    }
+
+    pub fn exists(&self, path: &str) -> bool {
+        self.resolve(path).exists()
+    }
+
+    /// Converts a filesystem path under the project root back to its `res://` form.
+    pub fn res_path(&self, path: &Path) -> Option<String> {
+        let rel = path.strip_prefix(&self.root).ok()?;
+        Some(format!("res://{}", rel.to_string_lossy().replace('\\', "/")))
+    }
+
+    /// Lists the project's files with one of the given extensions, skipping hidden directories.
+    pub fn files(&self, extensions: &[&str]) -> Vec<PathBuf> {
+        list_files(&self.root, extensions)
+    }
+
+    /// Reads the `key="value"` entries of a section of `project.godot`.
+    pub fn settings(&self, section: &str) -> io::Result<Vec<(String, String)>> {
+        let header = format!("[{}]", section);
+        let mut entries = Vec::new();
+        let mut inside = false;
+        for line in BufReader::new(File::open(self.root.join("project.godot"))?).lines() {
+            let line = line?;
+            if line.starts_with('[') {
+                inside = line.trim() == header;
+            } else if let (true, Some((key, val))) = (inside, line.split_once('=')) {
+                entries.push((key.trim().to_string(), val.trim().trim_matches('"').to_string()));
+            }
+        }
+        Ok(entries)
+    }
+
+    /// Looks up the `res://` path currently registered for a `uid://` identifier.
+    ///
+    /// The index is built on first use by scanning `.uid` sidecars, `.import` files
+    /// and the headers of text scenes/resources, which is where Godot keeps them.
+    pub fn uid_path(&self, uid: &str) -> Option<&str> {
+        self.uids
+            .get_or_init(|| {
+                let mut uids = HashMap::new();
+                for path in self.files(&["uid", "import", "tscn", "tres"]) {
+                    if let Ok(Some((target, uid))) = file_uid(&path) {
+                        if let Some(target) = self.res_path(&target) {
+                            uids.insert(uid, target);
+                        }
+                    }
+                }
+                uids
+            })
+            .get(uid)
+            .map(|p| p.as_str())
+    }
+
+    /// Looks up the `res://` path of the script that names a class with
+    /// `class_name`, building the index from the project's scripts on first use.
+    pub fn class_path(&self, name: &str) -> Option<&str> {
+        self.classes
+            .get_or_init(|| {
+                let class_re = Regex::new(r"(?m)^\s*(?:@\w+\s+)*class_name\s+(?P<name>\w+)").unwrap();
+                let mut classes = HashMap::new();
+                for path in self.files(&["gd"]) {
+                    let (Ok(source), Some(script)) = (fs::read_to_string(&path), self.res_path(&path)) else {
+                        continue;
+                    };
+                    if let Some(caps) = class_re.captures(&source) {
+                        classes.insert(caps["name"].to_string(), script);
+                    }
+                }
+                classes
+            })
+            .get(name)
+            .map(|p| p.as_str())
+    }
+}
+
+/// Lists the files under `dir` with one of the given extensions, skipping hidden directories.
+pub fn list_files(dir: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+    collect_files(dir, extensions, &mut found);
+    found.sort();
+    found
+}
+
+fn collect_files(dir: &Path, extensions: &[&str], found: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, extensions, found);
+        } else if path.extension().is_some_and(|e| extensions.iter().any(|x| e == *x)) {
+            found.push(path);
+        }
+    }
+}
+
+/// Reads the uid a `.uid` sidecar, `.import` file or text scene/resource assigns, along
+/// with the file it belongs to.
+fn file_uid(path: &Path) -> io::Result<Option<(PathBuf, String)>> {
+    let uid_re = Regex::new(r#"uid="(?P<uid>uid://[^"]+)""#).unwrap();
+    Ok(match path.extension().and_then(|e| e.to_str()) {
+        Some("uid") => Some((path.with_extension(""), fs::read_to_string(path)?.trim().to_string())),
+        Some("import") => uid_re
+            .captures(&fs::read_to_string(path)?)
+            .map(|caps| (path.with_extension(""), caps["uid"].to_string())),
+        _ => {
+            // only the section header names the file's own uid
+            let mut header = String::new();
+            BufReader::new(File::open(path)?).read_line(&mut header)?;
+            uid_re.captures(&header).map(|caps| (path.to_path_buf(), caps["uid"].to_string()))
+        }
+    })
+}
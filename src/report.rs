@@ -0,0 +1,785 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use clap::{Args, ValueEnum};
+use rayon::prelude::*;
+use serde_json::{json, Value};
+
+use crate::owners::Owners;
+use crate::project::{self, Project};
+use crate::scene::{self, ExtResource, Node, Scene};
+use crate::timings::Timings;
+use crate::dot;
+use crate::units;
+
+/// The newest layout of a scene's stats, as `stats --json` and `--ndjson`
+/// print them.
+pub const STATS_FORMAT_VERSION: u32 = 1;
+
+/// JSON Schemas describing each layout of the stats, oldest first.
+pub const STATS_SCHEMAS: &[&str] = &[include_str!("../schema/stats-v1.schema.json")];
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SortBy {
+    /// Most nodes first
+    Nodes,
+    /// Largest files first
+    Size,
+    /// Deepest trees first
+    Depth,
+    /// Scenes referenced by the most other scenes first
+    Refs,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+pub enum Kind {
+    /// Other scenes
+    Scenes,
+    /// GDScript and C# scripts
+    Scripts,
+    /// Textures and images
+    Textures,
+    /// Audio streams
+    Audio,
+    /// Every resource
+    All,
+}
+
+impl Kind {
+    /// Whether a resource belongs to this kind, going by its type or, when that
+    /// is missing, its extension.
+    pub fn matches(self, res: &ExtResource) -> bool {
+        let ext = res.path.rsplit_once('.').map_or("", |(_, ext)| ext).to_ascii_lowercase();
+        let _type = res._type.as_str();
+        match self {
+            Kind::Scenes => _type == "PackedScene" || matches!(ext.as_str(), "tscn" | "scn"),
+            Kind::Scripts => _type.ends_with("Script") || matches!(ext.as_str(), "gd" | "cs"),
+            Kind::Textures => {
+                _type.contains("Texture") || _type == "Image" || matches!(ext.as_str(), "png" | "jpg" | "jpeg" | "webp" | "svg" | "bmp" | "tga" | "exr" | "hdr")
+            }
+            Kind::Audio => _type.starts_with("AudioStream") || matches!(ext.as_str(), "wav" | "ogg" | "mp3" | "opus"),
+            Kind::All => true,
+        }
+    }
+}
+
+/// Ordering and paging options shared by the project-wide reports.
+#[derive(Args)]
+pub struct ReportArgs {
+    /// Order the scenes by this measure, largest first, instead of by path
+    #[arg(long, value_name = "KEY")]
+    sort_by: Option<SortBy>,
+    /// Show only the first N scenes
+    #[arg(long, value_name = "N")]
+    limit: Option<usize>,
+    /// Skip the first N scenes, to page through them with --limit
+    #[arg(long, value_name = "N", conflicts_with = "cursor")]
+    offset: Option<usize>,
+    /// Start after this scene, the last of the page before, so pages stay put as scenes are added or removed
+    #[arg(long, value_name = "SCENE")]
+    cursor: Option<String>,
+}
+
+/// One scene of a project-wide report, with the measures it can be sorted by.
+pub struct SceneReport {
+    /// The res:// path when the scene is inside a project, else the file path.
+    pub name: String,
+    pub scene: Scene,
+    pub size: u64,
+    pub nodes: usize,
+    pub depth: usize,
+    /// How many other scenes list this one among their ext_resources.
+    pub refs: usize,
+}
+
+/// Parses the scenes at `path`, a scene file or a directory to search for them.
+///
+/// References are counted across the whole project when there is one, so a
+/// scene used from elsewhere in the project still shows its callers. A scene
+/// counted only for its references is skipped with a warning when it doesn't
+/// parse; one of the scenes at `path` failing to is an error.
+pub fn load(path: &Path, project: Option<&Project>, timings: &mut Timings) -> io::Result<Vec<SceneReport>> {
+    let (wanted, files) = listing(path, project);
+    let wanted: HashSet<PathBuf> = wanted.into_iter().collect();
+    let scenes = parse_all(&files, project, timings);
+    let mut kept = Vec::new();
+    let mut refs: HashMap<String, usize> = HashMap::new();
+    for (file, scene) in files.into_iter().zip(scenes) {
+        let scene = match scene {
+            Ok(scene) => scene,
+            // the scenes asked about must parse; the rest only add to the counts
+            Err(err) if wanted.contains(&file) => return Err(err),
+            Err(err) => {
+                eprintln!("gdtree: skipping {}", err);
+                continue;
+            }
+        };
+        count(&scene, &mut refs);
+        if wanted.contains(&file) {
+            kept.push((file, scene));
+        }
+    }
+    Ok(kept.into_iter().map(|(file, scene)| report(&file, name(&file, project), scene, &refs)).collect())
+}
+
+/// The scene files at `path`, in path order, and those to count references
+/// across: the project's, with any of the first outside it.
+fn listing(path: &Path, project: Option<&Project>) -> (Vec<PathBuf>, Vec<PathBuf>) {
+    let wanted = match path.is_dir() {
+        true => project::list_files(path, &["tscn"]),
+        false => vec![path.to_path_buf()],
+    };
+    let canonical = |files: Vec<PathBuf>| -> Vec<PathBuf> { files.into_iter().map(|f| fs::canonicalize(&f).unwrap_or(f)).collect() };
+    let wanted = canonical(wanted);
+    let mut files = match project {
+        Some(project) => canonical(project.files(&["tscn"])),
+        None => wanted.clone(),
+    };
+    let listed: HashSet<PathBuf> = files.iter().cloned().collect();
+    files.extend(wanted.iter().filter(|file| !listed.contains(*file)).cloned());
+    (wanted, files)
+}
+
+/// The res:// path of a scene file inside the project, or else its file path.
+fn name(file: &Path, project: Option<&Project>) -> String {
+    project.and_then(|p| p.res_path(file)).unwrap_or_else(|| file.display().to_string())
+}
+
+/// Adds one to the count of each resource the scene loads.
+fn count(scene: &Scene, refs: &mut HashMap<String, usize>) {
+    let used: HashSet<&str> = scene.ext_resources.iter().map(|r| r.path.as_str()).collect();
+    for path in used {
+        *refs.entry(path.to_string()).or_default() += 1;
+    }
+}
+
+fn report(file: &Path, name: String, scene: Scene, refs: &HashMap<String, usize>) -> SceneReport {
+    SceneReport {
+        size: fs::metadata(file).map_or(0, |m| m.len()),
+        nodes: scene.root.paths().len(),
+        depth: depth(&scene.root),
+        refs: refs.get(&name).copied().unwrap_or(0),
+        name,
+        scene,
+    }
+}
+
+/// Counts how many of `files` load each resource, parsing the scenes one at a
+/// time on rayon's threads and keeping nothing of them but the counts. Scenes
+/// that don't parse are skipped with a warning.
+fn count_refs(files: &[PathBuf], project: Option<&Project>, timings: &mut Timings) -> HashMap<String, usize> {
+    let mut refs = HashMap::new();
+    stream(
+        files,
+        || (),
+        |_, file| {
+            let start = Instant::now();
+            let scene = File::open(file).and_then(|f| scene::parse_scene(BufReader::new(f), project));
+            (scene, start.elapsed())
+        },
+        |file, (scene, time)| {
+            timings.record(format!("parse {}", file.display()), time);
+            match scene {
+                Ok(scene) => count(&scene, &mut refs),
+                Err(err) => eprintln!("gdtree: skipping {}: {}", file.display(), err),
+            }
+        },
+    );
+    refs
+}
+
+/// Parses scenes on the threads of rayon's pool, returning them in the order of
+/// `files`, each error naming the file that failed. Each scene's parse time is
+/// recorded, so under several threads the times add up to more than the wall
+/// clock.
+pub fn parse_all(files: &[PathBuf], project: Option<&Project>, timings: &mut Timings) -> Vec<io::Result<Scene>> {
+    let parsed: Vec<(io::Result<Scene>, Duration)> = files
+        .par_iter()
+        .map(|file| {
+            let start = Instant::now();
+            let scene = File::open(file).and_then(|f| scene::parse_scene(BufReader::new(f), project));
+            let scene = scene.map_err(|err| io::Error::new(err.kind(), format!("{}: {}", file.display(), err)));
+            (scene, start.elapsed())
+        })
+        .collect();
+    let mut scenes = Vec::new();
+    for (file, (scene, time)) in files.iter().zip(parsed) {
+        timings.record(format!("parse {}", file.display()), time);
+        scenes.push(scene);
+    }
+    scenes
+}
+
+/// Runs `work` on each item on the threads of rayon's pool, with a `state`
+/// made by `init` for each batch of items a thread takes on, and hands the
+/// results to `emit` on this thread in the order of `items`. Each result goes
+/// out as soon as those before it have, so output starts while later items
+/// are still being worked on.
+pub fn stream<T, S, R>(items: &[T], init: impl Fn() -> S + Sync + Send, work: impl Fn(&mut S, &T) -> R + Sync + Send, mut emit: impl FnMut(&T, R))
+where
+    T: Sync,
+    R: Send,
+{
+    let (tx, rx) = mpsc::channel();
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            items.par_iter().enumerate().map_init(init, |state, (i, item)| (i, work(state, item))).for_each_with(tx, |tx, result| {
+                // the receiver outlives the senders
+                let _ = tx.send(result);
+            });
+        });
+        let mut pending: HashMap<usize, R> = HashMap::new();
+        let mut next = 0;
+        for (i, result) in rx.iter() {
+            pending.insert(i, result);
+            while let Some(result) = pending.remove(&next) {
+                emit(&items[next], result);
+                next += 1;
+            }
+        }
+    });
+}
+
+/// How many levels lie below a node; a scene with only a root has depth 0.
+pub fn depth(node: &Node) -> usize {
+    node.children.values().map(|child| depth(child) + 1).max().unwrap_or(0)
+}
+
+impl ReportArgs {
+    /// Loads the scenes at `path` as `load` does, in the order and page the
+    /// options ask for.
+    pub fn load(&self, path: &Path, project: Option<&Project>, timings: &mut Timings) -> io::Result<Vec<SceneReport>> {
+        let mut reports = Vec::new();
+        self.each(path, project, timings, |report| {
+            reports.push(report);
+            Ok(())
+        })?;
+        Ok(reports)
+    }
+
+    /// Hands the scenes of the page to `emit` in order, each as soon as it is
+    /// parsed, for output written as it goes. The page is picked from the list
+    /// of files before any scene is parsed in full, and the rest of the project
+    /// is read only for its references and let go, so memory stays with the
+    /// page however many scenes there are. Ordering by nodes or depth needs
+    /// every scene's tree, though, so then all of them are loaded first.
+    pub fn each(&self, path: &Path, project: Option<&Project>, timings: &mut Timings, mut emit: impl FnMut(SceneReport) -> io::Result<()>) -> io::Result<()> {
+        if let Some(SortBy::Nodes | SortBy::Depth) = self.sort_by {
+            let reports = load(path, project, timings)?;
+            let reports = self.page(reports, |r| &r.name, |r, sort_by| match sort_by {
+                SortBy::Nodes => r.nodes as u64,
+                _ => r.depth as u64,
+            })?;
+            timings.lap("sort");
+            return reports.into_iter().try_for_each(emit);
+        }
+        let (wanted, files) = listing(path, project);
+        let refs = count_refs(&files, project, timings);
+        let named: Vec<(PathBuf, String)> = wanted
+            .into_iter()
+            .map(|file| {
+                let name = name(&file, project);
+                (file, name)
+            })
+            .collect();
+        let page = self.page(named, |(_, name)| name, |(file, name), sort_by| match sort_by {
+            SortBy::Size => fs::metadata(file).map_or(0, |m| m.len()),
+            _ => refs.get(name).copied().unwrap_or(0) as u64,
+        })?;
+        timings.lap("sort");
+        let mut failed = None;
+        stream(
+            &page,
+            || (),
+            |_, (file, _)| {
+                let start = Instant::now();
+                let scene = File::open(file).and_then(|f| scene::parse_scene(BufReader::new(f), project));
+                (scene, start.elapsed())
+            },
+            |(file, name), (scene, time)| {
+                if failed.is_some() {
+                    return;
+                }
+                timings.record(format!("parse {}", file.display()), time);
+                let emitted = scene
+                    .map_err(|err| io::Error::new(err.kind(), format!("{}: {}", file.display(), err)))
+                    .and_then(|scene| emit(report(file, name.clone(), scene, &refs)));
+                failed = emitted.err();
+            },
+        );
+        failed.map_or(Ok(()), Err)
+    }
+
+    /// Orders `items` as --sort-by asks, by the measure `measure` gives each,
+    /// and keeps those after --offset or --cursor, up to --limit of them.
+    fn page<T>(&self, mut items: Vec<T>, name: impl Fn(&T) -> &str, measure: impl Fn(&T, SortBy) -> u64) -> io::Result<Vec<T>> {
+        if let Some(sort_by) = self.sort_by {
+            // a stable sort keeps equal scenes in path order
+            items.sort_by_cached_key(|item| std::cmp::Reverse(measure(item, sort_by)));
+        }
+        let skip = match (&self.cursor, self.offset) {
+            (Some(cursor), _) => match items.iter().position(|item| name(item) == cursor) {
+                Some(at) => at + 1,
+                None => return Err(io::Error::new(io::ErrorKind::NotFound, format!("the cursor {} is not among the scenes", cursor))),
+            },
+            (None, offset) => offset.unwrap_or(0),
+        };
+        items.drain(..skip.min(items.len()));
+        if let Some(limit) = self.limit {
+            items.truncate(limit);
+        }
+        Ok(items)
+    }
+}
+
+/// Tabulates the measures of each scene. A single scene is followed by its
+/// node counts per type; with `json` every scene carries them instead, as
+/// objects a script can keep to track scenes growing over time. Scenes are
+/// listed with their owners when CODEOWNERS or the config name any.
+pub fn stats(reports: &[SceneReport], json: bool, owners: &Owners) {
+    if json {
+        let scenes: Vec<Value> = reports.iter().map(|r| stats_object(r, owners)).collect();
+        println!("{}", serde_json::to_string_pretty(&scenes).unwrap());
+        return;
+    }
+    let width = reports.iter().map(|r| r.name.chars().count()).max().unwrap_or(0).max("scene".len());
+    let owner = |r: &SceneReport| match owners.is_empty() {
+        true => String::new(),
+        false => format!("  {}", owners.of(&r.name).join(" ")),
+    };
+    let heading = if owners.is_empty() { "" } else { "  owners" };
+    println!("{:width$}  {:>6}  {:>10}  {:>5}  {:>4}  {:>4}  {:>4}  {:>5}{}", "scene", "nodes", "size", "depth", "refs", "ext", "sub", "conns", heading);
+    for r in reports.iter() {
+        println!(
+            "{:width$}  {:>6}  {:>10}  {:>5}  {:>4}  {:>4}  {:>4}  {:>5}{}",
+            r.name,
+            r.nodes,
+            units::bytes(r.size),
+            r.depth,
+            r.refs,
+            r.scene.ext_resources.len(),
+            r.scene.sub_resources.len(),
+            connections(r),
+            owner(r)
+        );
+    }
+    if let [r] = reports {
+        let types = types(&r.scene.root);
+        let type_width = types.iter().map(|(_type, _)| _type.chars().count()).max().unwrap_or(0);
+        println!();
+        for (_type, count) in types.iter() {
+            println!("  {:type_width$}  {:>6}", _type, count);
+        }
+    }
+    println!("{} scenes", reports.len());
+}
+
+/// Writes the measures of a scene as a line of JSON, as `stats --ndjson`
+/// prints each scene as soon as it has been parsed.
+pub fn stats_line(r: &SceneReport, owners: &Owners, out: &mut impl Write) -> io::Result<()> {
+    serde_json::to_writer(&mut *out, &stats_object(r, owners))?;
+    writeln!(out)
+}
+
+fn stats_object(r: &SceneReport, owners: &Owners) -> Value {
+    let types: serde_json::Map<String, Value> = types(&r.scene.root).into_iter().map(|(_type, count)| (_type, count.into())).collect();
+    json!({
+        "format_version": STATS_FORMAT_VERSION,
+        "scene": r.name,
+        "nodes": r.nodes,
+        "size": r.size,
+        "depth": r.depth,
+        "refs": r.refs,
+        "ext_resources": r.scene.ext_resources.len(),
+        "sub_resources": r.scene.sub_resources.len(),
+        "connections": connections(r),
+        "types": types,
+        "owners": owners.of(&r.name),
+    })
+}
+
+fn connections(r: &SceneReport) -> usize {
+    r.scene.root.paths().iter().map(|(_, node)| node.connections.len()).sum()
+}
+
+/// How many nodes of each type a scene has, most first. Instances count under
+/// the scene they instance.
+fn types(root: &Node) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for (_, node) in root.paths() {
+        let _type = match (&node.instance, node._type.as_str()) {
+            (Some(res), "") => res.path.clone(),
+            (_, _type) => _type.to_string(),
+        };
+        match counts.iter_mut().find(|(t, _)| *t == _type) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((_type, 1)),
+        }
+    }
+    // a stable sort keeps types of equal counts in the order they first appear
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum DepsFormat {
+    /// Each scene followed by its resources
+    Text,
+    /// A Graphviz digraph, with each directory's files in a cluster
+    Dot,
+}
+
+/// Lists the resources of a kind each scene loads. When `recursive`, the
+/// scenes they instance follow with theirs, read from the project when they
+/// are not among the reports; beneath each scene come those it instances,
+/// whatever the kind, so the chain stays in view.
+pub fn deps(reports: &[SceneReport], kind: Kind, format: DepsFormat, project: Option<&Project>, recursive: bool) {
+    let mut loaded = Loaded { project, scenes: reports.iter().map(|r| (r.name.clone(), Some(r.scene.ext_resources.clone()))).collect() };
+    if let DepsFormat::Dot = format {
+        let mut scenes: Vec<(String, Vec<String>)> = Vec::new();
+        let mut queue: Vec<String> = reports.iter().map(|r| r.name.clone()).collect();
+        let mut seen: Vec<String> = queue.clone();
+        while !queue.is_empty() {
+            let scene = queue.remove(0);
+            let resources = loaded.resources(&scene).to_vec();
+            if recursive {
+                for res in resources.iter().filter(|res| Kind::Scenes.matches(res)) {
+                    if !seen.contains(&res.path) {
+                        seen.push(res.path.clone());
+                        queue.push(res.path.clone());
+                    }
+                }
+            }
+            scenes.push((scene, resources.iter().filter(|res| kind.matches(res) || (recursive && Kind::Scenes.matches(res))).map(|res| res.path.clone()).collect()));
+        }
+        print!("{}", dot::dependencies(&scenes));
+        return;
+    }
+    for r in reports.iter() {
+        println!("{}", r.name);
+        let mut chain = vec![r.name.clone()];
+        list_deps(&mut loaded, &r.name, kind, recursive, &mut chain);
+    }
+}
+
+/// The ext_resources of scenes, by their path, read as they are first needed.
+struct Loaded<'a> {
+    project: Option<&'a Project>,
+    /// None for a scene that could not be read.
+    scenes: HashMap<String, Option<Vec<ExtResource>>>,
+}
+
+impl Loaded<'_> {
+    fn resources(&mut self, scene: &str) -> &[ExtResource] {
+        let project = self.project;
+        let resources = self.scenes.entry(scene.to_string()).or_insert_with(|| {
+            let file = project.map_or(PathBuf::from(scene), |p| p.resolve(scene));
+            File::open(file).and_then(|f| scene::parse_scene(BufReader::new(f), project)).ok().map(|s| s.ext_resources)
+        });
+        resources.as_deref().unwrap_or_default()
+    }
+}
+
+/// Prints the resources of `scene` indented beneath it, and with `recursive`
+/// those of the scenes it instances beneath them. `chain` holds the scenes
+/// leading here, so a scene that instances one of them isn't followed round.
+fn list_deps(loaded: &mut Loaded, scene: &str, kind: Kind, recursive: bool, chain: &mut Vec<String>) {
+    let indent = "  ".repeat(chain.len());
+    for res in loaded.resources(scene).to_vec().iter() {
+        let follow = recursive && Kind::Scenes.matches(res);
+        if !kind.matches(res) && !follow {
+            continue;
+        }
+        let looped = follow && chain.contains(&res.path);
+        match (res._type.is_empty(), looped) {
+            (_, true) => println!("{}{} (cycle back up the chain)", indent, res.path),
+            (true, false) => println!("{}{}", indent, res.path),
+            (false, false) => println!("{}{} ({})", indent, res.path, res._type),
+        }
+        if follow && !looped {
+            chain.push(res.path.clone());
+            list_deps(loaded, &res.path, kind, recursive, chain);
+            chain.pop();
+        }
+    }
+}
+
+/// Prints every chain of instanced scenes leading from the scene `from` to the
+/// scene `to`, with the node of each scene that brings in the next, returning
+/// how many there were. A scene counts as instancing another when one of its
+/// nodes is an instance of it or has it as a property, such as a PackedScene
+/// exported for spawning.
+pub fn paths(reports: &[SceneReport], from: &str, to: &str) -> usize {
+    let mut edges: HashMap<&str, Vec<(String, &str)>> = HashMap::new();
+    for r in reports.iter() {
+        let out = edges.entry(r.name.as_str()).or_default();
+        for (path, node) in r.scene.root.paths() {
+            if let Some(res) = node.instance.as_ref().filter(|res| res.path.ends_with(".tscn")) {
+                out.push((path.clone(), res.path.as_str()));
+            }
+            for param in node.parameters.iter() {
+                if let Some(res) = param.resource.as_ref().filter(|res| res.path.ends_with(".tscn")) {
+                    out.push((format!("{}:{}", path, param.key), res.path.as_str()));
+                }
+            }
+        }
+    }
+
+    let mut found = 0;
+    let mut chain: Vec<(&str, String)> = Vec::new();
+    walk_paths(&edges, from, to, &mut chain, &mut found);
+    match found {
+        1 => println!("1 path from {} to {}", from, to),
+        found => println!("{} paths from {} to {}", found, from, to),
+    }
+    found
+}
+
+fn walk_paths<'a>(edges: &HashMap<&'a str, Vec<(String, &'a str)>>, at: &'a str, to: &str, chain: &mut Vec<(&'a str, String)>, found: &mut usize) {
+    if at == to {
+        let steps: Vec<String> = chain.iter().map(|(scene, node)| format!("{} [{}]", scene, node)).collect();
+        println!("{} -> {}", steps.join(" -> "), to);
+        *found += 1;
+        return;
+    }
+    // a scene already on the chain would only lead round in a circle
+    if chain.iter().any(|(scene, _)| *scene == at) {
+        return;
+    }
+    for (node, next) in edges.get(at).into_iter().flatten() {
+        chain.push((at, node.clone()));
+        walk_paths(edges, next, to, chain, found);
+        chain.pop();
+    }
+}
+
+/// Prints how many nodes set a property to each value, most common first, with
+/// how many scenes each value turns up in, returning how many nodes set it at
+/// all. Nodes leaving the property at its default are not counted, since the
+/// scene files don't record it.
+pub fn values(reports: &[SceneReport], key: &str) -> usize {
+    // value -> (nodes, scenes)
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    let mut scenes = 0;
+    for r in reports.iter() {
+        let mut seen: Vec<&str> = Vec::new();
+        for (_, node) in r.scene.root.paths() {
+            for param in node.parameters.iter().filter(|p| p.key == key) {
+                let count = counts.entry(param.val.as_str()).or_default();
+                count.0 += 1;
+                if !seen.contains(&param.val.as_str()) {
+                    seen.push(param.val.as_str());
+                    count.1 += 1;
+                }
+            }
+        }
+        if !seen.is_empty() {
+            scenes += 1;
+        }
+    }
+    let mut counts: Vec<(&str, (usize, usize))> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1 .0.cmp(&a.1 .0).then(a.0.cmp(b.0)));
+    let width = counts.iter().map(|(val, _)| val.chars().count()).max().unwrap_or(0).max("value".len());
+    if !counts.is_empty() {
+        println!("{:width$}  {:>6}  {:>6}", "value", "nodes", "scenes");
+    }
+    for (val, (nodes, in_scenes)) in counts.iter() {
+        println!("{:width$}  {:>6}  {:>6}", val, nodes, in_scenes);
+    }
+    let total = counts.iter().map(|(_, (nodes, _))| nodes).sum();
+    println!("{} set on {} nodes in {} of {} scenes, {} distinct values", key, total, scenes, reports.len(), counts.len());
+    total
+}
+
+/// Lists the 3D lights of each scene with their energy, whether they cast
+/// shadows and their range, scenes with the most shadow-casting lights first.
+/// Returns how many lights there were.
+pub fn lights(reports: &[SceneReport]) -> usize {
+    // Godot 4 names, then Godot 3's
+    const LIGHTS: &[&str] = &["DirectionalLight3D", "OmniLight3D", "SpotLight3D", "DirectionalLight", "OmniLight", "SpotLight"];
+    let mut scenes = Vec::new();
+    for r in reports.iter() {
+        let lights: Vec<(String, &Node)> = r.scene.root.paths().into_iter().filter(|(_, node)| LIGHTS.contains(&node._type.as_str())).collect();
+        if lights.is_empty() {
+            continue;
+        }
+        let shadows = lights.iter().filter(|(_, node)| param(node, "shadow_enabled") == Some("true")).count();
+        scenes.push((r.name.as_str(), lights, shadows));
+    }
+    // a stable sort keeps scenes with as many shadows in path order
+    scenes.sort_by_key(|(_, _, shadows)| std::cmp::Reverse(*shadows));
+
+    let mut total = 0;
+    for (name, lights, shadows) in scenes.iter() {
+        println!("{}: {}, {} casting shadows", name, plural(lights.len(), "light"), shadows);
+        for (path, node) in lights.iter() {
+            let path = if path == "." { &node.name } else { path };
+            let energy = param(node, "light_energy").unwrap_or("1");
+            let shadow = match param(node, "shadow_enabled") {
+                Some("true") => "on",
+                _ => "off",
+            };
+            let range = match node._type.as_str() {
+                "OmniLight3D" | "OmniLight" => format!("  range {}", param(node, "omni_range").unwrap_or("5")),
+                "SpotLight3D" | "SpotLight" => format!("  range {}", param(node, "spot_range").unwrap_or("5")),
+                _ => String::new(),
+            };
+            println!("  {} ({})  energy {}  shadows {}{}", path, node._type, energy, shadow, range);
+        }
+        total += lights.len();
+    }
+    let shadows: usize = scenes.iter().map(|(_, _, shadows)| shadows).sum();
+    println!("{} in {} of {} scenes, {} casting shadows", plural(total, "light"), scenes.len(), reports.len(), shadows);
+    total
+}
+
+/// Lists the physics bodies and areas of each scene with their collision layers
+/// and masks and how many collision shapes they have, marking those with none,
+/// which collide with nothing. Returns how many have no shapes.
+pub fn physics(reports: &[SceneReport]) -> usize {
+    // Godot 4 names, then Godot 3's
+    const BODIES: &[&str] = &[
+        "StaticBody2D", "StaticBody3D", "RigidBody2D", "RigidBody3D", "CharacterBody2D", "CharacterBody3D", "AnimatableBody2D",
+        "AnimatableBody3D", "Area2D", "Area3D", "VehicleBody3D", "PhysicalBone2D", "PhysicalBone3D", "StaticBody", "RigidBody",
+        "KinematicBody2D", "KinematicBody", "Area", "VehicleBody", "PhysicalBone",
+    ];
+    const SHAPES: &[&str] = &["CollisionShape2D", "CollisionShape3D", "CollisionPolygon2D", "CollisionPolygon3D", "CollisionShape", "CollisionPolygon"];
+    let mut total = 0;
+    let mut scenes = 0;
+    let mut shapeless = 0;
+    for r in reports.iter() {
+        let bodies: Vec<(String, &Node)> = r.scene.root.paths().into_iter().filter(|(_, node)| BODIES.contains(&node._type.as_str())).collect();
+        if bodies.is_empty() {
+            continue;
+        }
+        println!("{}", r.name);
+        for (path, node) in bodies.iter() {
+            let path = if path == "." { &node.name } else { path };
+            let shapes: Vec<&Node> = node.children.values().filter(|child| SHAPES.contains(&child._type.as_str())).collect();
+            let disabled = shapes.iter().filter(|shape| param(shape, "disabled") == Some("true")).count();
+            let mut note = match disabled {
+                0 => String::new(),
+                disabled => format!(" ({} disabled)", disabled),
+            };
+            if shapes.len() == disabled {
+                note += " [no collision shape]";
+                shapeless += 1;
+            }
+            println!(
+                "  {} ({})  layer {}  mask {}  {}{}",
+                path,
+                node._type,
+                layers(param(node, "collision_layer")),
+                layers(param(node, "collision_mask")),
+                plural(shapes.len(), "shape"),
+                note
+            );
+        }
+        total += bodies.len();
+        scenes += 1;
+    }
+    println!("{} bodies and areas in {} of {} scenes, {} without collision shapes", total, scenes, reports.len(), shapeless);
+    shapeless
+}
+
+/// The layers a collision layer or mask bitmask has set, numbered from 1 as the
+/// editor shows them; both default to layer 1.
+fn layers(val: Option<&str>) -> String {
+    let bits: u64 = val.and_then(|v| v.parse().ok()).unwrap_or(1);
+    let set: Vec<String> = (0..64).filter(|bit| bits & (1 << bit) != 0).map(|bit| (bit + 1).to_string()).collect();
+    match set.is_empty() {
+        true => "none".to_string(),
+        false => set.join(","),
+    }
+}
+
+/// The value a node's scene gives a property, as written.
+fn param<'a>(node: &'a Node, key: &str) -> Option<&'a str> {
+    node.parameters.iter().find(|p| p.key == key).map(|p| p.val.as_str())
+}
+
+fn plural(count: usize, one: &str) -> String {
+    match count {
+        1 => format!("1 {}", one),
+        count => format!("{} {}s", count, one),
+    }
+}
+
+/// Lists the nodes whose name or type matches a glob pattern, returning how many
+/// were found. With `paths_only`, each match is a bare line with its node path,
+/// after its scene and a tab when there are several scenes.
+pub fn find(reports: &[SceneReport], pattern: &str, paths_only: bool) -> usize {
+    let mut found = 0;
+    for r in reports.iter() {
+        let matches: Vec<(String, &Node)> = r
+            .scene
+            .root
+            .paths()
+            .into_iter()
+            .filter(|(_, node)| glob_match(pattern, &node.name) || glob_match(pattern, &node._type))
+            .collect();
+        found += matches.len();
+        if paths_only {
+            for (path, _) in matches.iter() {
+                match reports.len() {
+                    1 => println!("{}", path),
+                    _ => println!("{}\t{}", r.name, path),
+                }
+            }
+            continue;
+        }
+        if matches.is_empty() {
+            continue;
+        }
+        println!("{}", r.name);
+        for (path, node) in matches.iter() {
+            match node._type.is_empty() {
+                true => println!("  {}", path),
+                false => println!("  {} ({})", path, node._type),
+            }
+        }
+    }
+    if paths_only {
+        return found;
+    }
+    println!("{} matching nodes in {} scenes", found, reports.len());
+    found
+}
+
+/// Matches text against a pattern where `*` stands for any run of characters
+/// and `?` for any one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text): (Vec<char>, Vec<char>) = (pattern.chars().collect(), text.chars().collect());
+    let (mut p, mut t) = (0, 0);
+    // where the last * was, and the text position it has swallowed up to
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, swallowed)) => {
+                    p = star + 1;
+                    t = swallowed + 1;
+                    backtrack = Some((star, swallowed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
@@ -0,0 +1,790 @@
+use indexmap::IndexMap;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use crate::parser::{self, Statement, Value};
+use crate::project::Project;
+use crate::version::{self, Detection};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtResource {
+    pub id: String,
+    pub path: String,
+    pub _type: String,
+}
+
+impl ExtResource {
+    pub fn new(id: String, path: String, _type: String) -> Self {
+        Self {
+            id,
+            path,
+            _type,
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubResource {
+    pub id: String,
+    pub _type: String,
+    pub parameters: Vec<Parameter>,
+    /// Bytes of its section, plus those of the sub_resources it refers to.
+    pub weight: u64,
+}
+
+impl SubResource {
+    pub fn new(id: String, _type: String) -> Self {
+        Self {
+            id,
+            _type,
+            parameters: Vec::new(),
+            weight: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Parameter {
+    pub key: String,
+    pub val: String,
+    /// The type and parameters of an earlier sub_resource this one refers to, such as
+    /// the Gradient of a GradientTexture.
+    pub sub_type: Option<String>,
+    pub sub_params: Vec<Parameter>,
+    pub resource: Option<ExtResource>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NodeParameter {
+    pub key: String,
+    pub val: String,
+    pub sub_params: Vec<Parameter>,
+    pub sub_type: Option<String>,
+    pub resource: Option<ExtResource>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Node {
+    pub name: String,
+    pub _type: String,
+    pub parent: String,
+    pub index: i32,
+    pub instance: Option<ExtResource>,
+    pub parameters: Vec<NodeParameter>,
+    pub children: IndexMap<String, Node>,
+    pub connections: Vec<Connection>,
+    /// Set when this node is added inside an instanced scene whose children are
+    /// not editable, so Godot drops it when loading the scene.
+    pub dropped: bool,
+    /// Bytes of this node's section of the file, plus those of the sub_resources
+    /// it refers to; one shared by several nodes counts toward each.
+    pub weight: u64,
+}
+
+impl Node {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            _type: "".to_string(),
+            parent: "".to_string(),
+            index: -1,
+            instance: None,
+            parameters: Vec::new(),
+            children: IndexMap::new(),
+            connections: Vec::new(),
+            dropped: false,
+            weight: 0,
+        }
+    }
+    pub fn add_child(&mut self, node: Node, mut parents: Vec<String>) {
+        if !parents.is_empty() {
+            let parent = parents.remove(0);
+            // a node of an instanced scene that this one doesn't declare
+            let child = self.children.entry(parent.clone()).or_insert_with(|| Node::new(&parent));
+            child.add_child(node, parents);
+        } else {
+            self.children.entry(node.name.clone()).or_insert(node);
+        }
+    }
+    /// Lists this node and its descendants with their paths relative to this node,
+    /// which itself is `.`.
+    pub fn paths(&self) -> Vec<(String, &Node)> {
+        let mut found = vec![(".".to_string(), self)];
+        self.collect_paths("", &mut found);
+        found
+    }
+    fn collect_paths<'a>(&'a self, prefix: &str, found: &mut Vec<(String, &'a Node)>) {
+        for (name, child) in self.children.iter() {
+            let path = match prefix.is_empty() {
+                true => name.clone(),
+                false => format!("{}/{}", prefix, name),
+            };
+            found.push((path.clone(), child));
+            child.collect_paths(&path, found);
+        }
+    }
+    /// Looks up a descendant by a node path relative to this node, which itself
+    /// is `.`, resolved as `resolve` does.
+    pub fn find(&self, path: &str) -> Option<&Node> {
+        self.at(&self.resolve(".", path)?)
+    }
+
+    /// The node at a path as `paths` spells them.
+    fn at(&self, path: &str) -> Option<&Node> {
+        path.split('/').filter(|name| *name != ".").try_fold(self, |node, name| node.children.get(name))
+    }
+
+    /// Resolves a node path the way Godot does, starting from the node at `from`:
+    /// names match case-sensitively, `..` climbs to the parent and `%Name` jumps to
+    /// the node with that unique name. Returns the path of the node it leads to,
+    /// as `paths` spells them, or None when there is no such node in the scene.
+    ///
+    /// Absolute paths lead outside the scene, so they never resolve.
+    pub fn resolve(&self, from: &str, path: &str) -> Option<String> {
+        // a property path after the colon doesn't change the node
+        let path = path.split(':').next().unwrap_or_default();
+        if path.starts_with('/') {
+            return None;
+        }
+        let mut segments: Vec<String> = match from {
+            "." | "" => Vec::new(),
+            from => from.split('/').map(|name| name.to_string()).collect(),
+        };
+        for name in path.split('/') {
+            match name {
+                "" | "." => (),
+                ".." => {
+                    segments.pop()?;
+                }
+                name if name.starts_with('%') => {
+                    let (unique, _) = self.paths().into_iter().skip(1).find(|(_, node)| {
+                        node.name == name[1..] && node.parameters.iter().any(|p| p.key == "unique_name_in_owner" && p.val == "true")
+                    })?;
+                    segments = unique.split('/').map(|name| name.to_string()).collect();
+                }
+                name => segments.push(name.to_string()),
+            }
+        }
+        let path = match segments.is_empty() {
+            true => ".".to_string(),
+            false => segments.join("/"),
+        };
+        self.at(&path).map(|_| path)
+    }
+    /// The weight of this node and all its descendants.
+    pub fn total_weight(&self) -> u64 {
+        self.weight + self.children.values().map(|child| child.total_weight()).sum::<u64>()
+    }
+    /// The external script attached to this node, if any.
+    pub fn script(&self) -> Option<&ExtResource> {
+        self.parameters.iter().find(|p| p.key == "script").and_then(|p| p.resource.as_ref())
+    }
+}
+
+/// Bits of a connection's `flags`, as Godot's Object.ConnectFlags has them.
+pub const CONNECT_DEFERRED: u32 = 1;
+pub const CONNECT_ONE_SHOT: u32 = 4;
+pub const CONNECT_REFERENCE_COUNTED: u32 = 8;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Connection {
+    pub signal: String,
+    pub from: String,
+    pub to: String,
+    pub method: String,
+    pub flags: u32,
+    /// The `from` or `to` path when it names no node of the scene, as happens
+    /// when a node is renamed or removed and its connections are left behind;
+    /// Godot fails to make the connection when it loads the scene.
+    pub missing: Option<String>,
+    /// The script of the `to` node when it doesn't define `method`, found when
+    /// the scene is parsed within a project.
+    pub missing_method: Option<String>,
+}
+
+impl Connection {
+    pub fn new(signal: &str, from: &str, to: &str, method: &str) -> Self {
+        Self {
+            signal: signal.to_string(),
+            from: from.to_string(),
+            to: to.to_string(),
+            method: method.to_string(),
+            flags: 0,
+            missing: None,
+            missing_method: None,
+        }
+    }
+    /// The ` [deferred]`-style tags for the flags that change how the connection
+    /// behaves; every connection saved in a scene persists, so that one is left out.
+    /// A connection to or from a node that isn't there ends in ` [no node Path]`,
+    /// and one to a method its script lacks in ` [no method in res://script.gd]`.
+    pub fn tags(&self) -> String {
+        let mut tags: String = [(CONNECT_DEFERRED, "deferred"), (CONNECT_ONE_SHOT, "oneshot"), (CONNECT_REFERENCE_COUNTED, "reference counted")]
+            .iter()
+            .filter(|(bit, _)| self.flags & bit != 0)
+            .map(|(_, name)| format!(" [{}]", name))
+            .collect();
+        if let Some(path) = &self.missing {
+            tags += &format!(" [no node {}]", path);
+        }
+        if let Some(script) = &self.missing_method {
+            tags += &format!(" [no method in {}]", script);
+        }
+        tags
+    }
+}
+
+/// The kind of bracketed section a line of a scene falls in.
+enum Section {
+    /// The file header, ext_resources, connections and the like
+    Header,
+    SubResource,
+    Node,
+}
+
+/// A parsed scene: its node tree plus the resources it declares.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Scene {
+    pub root: Node,
+    pub ext_resources: Vec<ExtResource>,
+    pub sub_resources: Vec<SubResource>,
+    /// The length of the scene file in bytes.
+    pub size: u64,
+    /// Which Godot wrote the scene, and why we think so.
+    pub detected: Detection,
+}
+
+/// Parses a text scene into its node tree, returning the root node.
+///
+/// When a project is given, stale ext_resource paths are repaired through their uids.
+pub fn parse(reader: impl BufRead, project: Option<&Project>) -> io::Result<Node> {
+    parse_scene(reader, project).map(|scene| scene.root)
+}
+
+/// Parses a text scene like `parse`, keeping the declared resources as well.
+pub fn parse_scene(mut reader: impl BufRead, project: Option<&Project>) -> io::Result<Scene> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let statements = parser::parse(&text).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+
+    let mut ext_resources = IndexMap::<String, ExtResource>::new();
+    let mut sub_resources = IndexMap::<String, SubResource>::new();
+    let mut connections = Vec::<Connection>::new();
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut format: Option<u32> = None;
+    let mut uid: Option<String> = None;
+    // the type a .tres file's header gives its [resource]
+    let mut resource_type = String::new();
+    let mut editable = Vec::<String>::new();
+    // the section the statements being read belong to, and the sub_resources it
+    // has referred to so far, so that each adds to its weight only once
+    let mut section = Section::Header;
+    let mut referred = Vec::<String>::new();
+    let attr = |statement: &Statement, key: &str| statement.attr(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+
+    for (i, statement) in statements.iter().enumerate() {
+        let values: Vec<&Value> = match statement {
+            Statement::Section { attrs, .. } => attrs.iter().map(|(_, v)| v).collect(),
+            Statement::Assignment { value, .. } => vec![value],
+        };
+        match statement {
+            Statement::Section { tag, .. } => {
+                (section, referred) = (Section::Header, Vec::new());
+                match tag.as_str() {
+                    "gd_scene" | "gd_resource" => {
+                        format = attr(statement, "format").and_then(|f| f.parse().ok());
+                        uid = attr(statement, "uid");
+                        resource_type = attr(statement, "type").unwrap_or_default();
+                    }
+                    "ext_resource" => {
+                        let (Some(id), Some(mut path)) = (attr(statement, "id"), attr(statement, "path")) else {
+                            continue;
+                        };
+                        // Godot 4 follows the uid when the recorded path has gone stale
+                        if let (Some(project), Some(uid)) = (project, attr(statement, "uid")) {
+                            if !project.exists(&path) {
+                                if let Some(moved) = project.uid_path(&uid) {
+                                    path = moved.to_string();
+                                }
+                            }
+                        }
+                        ext_resources.insert(id.clone(), ExtResource::new(id, path, attr(statement, "type").unwrap_or_default()));
+                    }
+                    "sub_resource" => {
+                        let Some(id) = attr(statement, "id") else {
+                            continue;
+                        };
+                        section = Section::SubResource;
+                        sub_resources.insert(id.clone(), SubResource::new(id, attr(statement, "type").unwrap_or_default()));
+                    }
+                    "node" => {
+                        let Some(name) = attr(statement, "name") else {
+                            continue;
+                        };
+                        let mut node = Node::new(&name);
+                        node._type = attr(statement, "type").unwrap_or_default();
+                        node.parent = attr(statement, "parent").unwrap_or_default();
+                        node.index = attr(statement, "index").and_then(|i| i.parse().ok()).unwrap_or(-1);
+                        if let Some(("ExtResource", id)) = statement.attr("instance").and_then(|v| v.reference()) {
+                            node.instance = ext_resources.get(id).cloned();
+                        }
+                        nodes.push(node);
+                        section = Section::Node;
+                    }
+                    // a .tres file's own properties, shown as a root node named after its type
+                    "resource" => {
+                        let mut node = Node::new(&resource_type);
+                        node._type = resource_type.clone();
+                        nodes.push(node);
+                        section = Section::Node;
+                    }
+                    "connection" => {
+                        let (Some(signal), Some(from), Some(to), Some(method)) =
+                            (attr(statement, "signal"), attr(statement, "from"), attr(statement, "to"), attr(statement, "method"))
+                        else {
+                            continue;
+                        };
+                        let to = match to.as_str() {
+                            "." => nodes.first().map_or(to.clone(), |root| root.name.clone()),
+                            _ => to,
+                        };
+                        let mut conn = Connection::new(&signal, &from, &to, &method);
+                        conn.flags = attr(statement, "flags").and_then(|f| f.parse().ok()).unwrap_or(0);
+                        connections.push(conn);
+                    }
+                    "editable" => editable.extend(attr(statement, "path")),
+                    _ => (),
+                }
+            }
+            Statement::Assignment { key, value } => match section {
+                Section::Header => (),
+                Section::SubResource => {
+                    let mut param = Parameter {
+                        key: key.clone(),
+                        val: value.raw(&text).to_string(),
+                        sub_type: None,
+                        sub_params: Vec::new(),
+                        resource: None,
+                    };
+                    match value.reference() {
+                        Some(("ExtResource", id)) => {
+                            if let Some(res) = ext_resources.get(id) {
+                                param.val = res.path.clone();
+                                param.resource = Some(res.clone());
+                            }
+                        }
+                        Some((_, id)) => {
+                            if let Some(res) = sub_resources.get(id) {
+                                param.sub_type = Some(res._type.clone());
+                                param.sub_params = res.parameters.clone();
+                            }
+                        }
+                        // arrays of resources, such as a font's fallbacks, list their paths
+                        None => param.val = with_paths(value, &text, &ext_resources),
+                    }
+                    sub_resources.last_mut().unwrap().1.parameters.push(param);
+                }
+                Section::Node => {
+                    let mut param = NodeParameter {
+                        key: key.clone(),
+                        val: value.raw(&text).to_string(),
+                        sub_params: Vec::new(),
+                        sub_type: None,
+                        resource: None,
+                    };
+                    match value.reference() {
+                        Some(("ExtResource", id)) => {
+                            if let Some(res) = ext_resources.get(id) {
+                                param.val = res.path.clone();
+                                param.resource = Some(res.clone());
+                            }
+                        }
+                        Some((_, id)) => {
+                            if let Some(res) = sub_resources.get(id) {
+                                param.val = res._type.clone();
+                                param.sub_params = res.parameters.clone();
+                                param.sub_type = Some(res._type.clone());
+                            }
+                        }
+                        None => (),
+                    }
+                    nodes.last_mut().unwrap().parameters.push(param);
+                }
+            },
+        }
+
+        // a section's weight runs from its header to the next one, blank lines and all
+        let mut weight = match statement {
+            Statement::Section { start, .. } => {
+                let next = statements[i + 1..].iter().find_map(|s| match s {
+                    Statement::Section { start, .. } => Some(*start),
+                    _ => None,
+                });
+                (next.unwrap_or(text.len()) - start) as u64
+            }
+            Statement::Assignment { .. } => 0,
+        };
+        for value in values.iter().flat_map(|v| v.references()) {
+            let Some(("SubResource", id)) = value.reference() else {
+                continue;
+            };
+            if let Some(sub) = sub_resources.get(id).filter(|_| !referred.iter().any(|r| r == id)) {
+                weight += sub.weight;
+                referred.push(id.to_string());
+            }
+        }
+        match section {
+            Section::Header => (),
+            Section::SubResource => sub_resources.last_mut().unwrap().1.weight += weight,
+            Section::Node => nodes.last_mut().unwrap().weight += weight,
+        }
+    }
+
+    let mut root = assemble(nodes, connections);
+    for (name, child) in root.children.iter_mut() {
+        mark_dropped(child, name, None, &editable);
+    }
+    if let Some(project) = project {
+        check_methods(&mut root, project);
+    }
+    Ok(Scene {
+        detected: version::detect(format, uid.as_deref(), &root),
+        root,
+        ext_resources: ext_resources.into_values().collect(),
+        sub_resources: sub_resources.into_values().collect(),
+        size: text.len() as u64,
+    })
+}
+
+/// Parses only the `[node]` headers of a text scene, for when just the
+/// hierarchy is wanted from a great many scenes: properties, resources and
+/// connections are skipped without being read, so nodes come back with none,
+/// no instanced scene and no weight.
+pub fn parse_structure(mut reader: impl BufRead) -> io::Result<Scene> {
+    let mut text = String::new();
+    reader.read_to_string(&mut text)?;
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut format: Option<u32> = None;
+    let mut uid: Option<String> = None;
+    let mut resource_type = String::new();
+    // a multi-line string may hold a line that looks like a header
+    let mut in_string = false;
+    for line in text.lines() {
+        let header = !in_string && ["[node ", "[resource]", "[gd_scene ", "[gd_resource "].iter().any(|tag| line.starts_with(tag));
+        let mut escaped = false;
+        for b in line.bytes() {
+            match b {
+                b'\\' if in_string => escaped = !escaped,
+                b'"' if !escaped => in_string = !in_string,
+                _ => escaped = false,
+            }
+        }
+        if !header {
+            continue;
+        }
+        let statements = parser::parse(line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+        let Some(statement @ Statement::Section { tag, .. }) = statements.first() else {
+            continue;
+        };
+        let attr = |key: &str| statement.attr(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+        match tag.as_str() {
+            "node" => (),
+            // a .tres file's root, named after its type
+            "resource" => {
+                let mut node = Node::new(&resource_type);
+                node._type = resource_type.clone();
+                nodes.push(node);
+                continue;
+            }
+            _ => {
+                format = attr("format").and_then(|f| f.parse().ok());
+                uid = attr("uid");
+                resource_type = attr("type").unwrap_or_default();
+                continue;
+            }
+        }
+        let Some(name) = attr("name") else {
+            continue;
+        };
+        let mut node = Node::new(&name);
+        node._type = attr("type").unwrap_or_default();
+        node.parent = attr("parent").unwrap_or_default();
+        node.index = attr("index").and_then(|i| i.parse().ok()).unwrap_or(-1);
+        nodes.push(node);
+    }
+    let root = assemble(nodes, Vec::new());
+    Ok(Scene {
+        detected: version::detect(format, uid.as_deref(), &root),
+        root,
+        ext_resources: Vec::new(),
+        sub_resources: Vec::new(),
+        size: text.len() as u64,
+    })
+}
+
+/// Builds the tree out of the nodes in the order the scene lists them, handing
+/// each the connections that start from it. Connections from a node that isn't
+/// there go to the root.
+fn assemble(nodes: Vec<Node>, mut connections: Vec<Connection>) -> Node {
+    // connections name their nodes by their paths from the root
+    let path_of = |node: &Node| match node.parent.as_str() {
+        "" => ".".to_string(),
+        "." => node.name.clone(),
+        parent => format!("{}/{}", parent, node.name),
+    };
+    let paths: HashSet<String> = nodes.iter().map(path_of).collect();
+    let instanced: HashSet<String> = nodes.iter().filter(|node| node.instance.is_some()).map(path_of).collect();
+    let root_name = nodes.iter().find(|node| node.parent.is_empty()).map(|node| node.name.clone());
+    let exists = |path: &str| {
+        // the root's own connections lead to it by name
+        paths.contains(path) || root_name.as_deref() == Some(path) || from_instance(path, &instanced)
+    };
+    for conn in connections.iter_mut() {
+        conn.missing = [&conn.from, &conn.to].into_iter().find(|path| !exists(path)).cloned();
+    }
+
+    let mut root = Node::new("");
+    for mut node in nodes {
+        let path = path_of(&node);
+        let (own, rest) = connections.into_iter().partition(|c| c.from == path);
+        node.connections = own;
+        connections = rest;
+        if node.parent.is_empty() {
+            // root node
+            root = node;
+        } else {
+            let parents: Vec<String> = if node.parent == "." {
+                Vec::new()
+            } else {
+                node.parent.split('/').map(|x| x.to_string()).collect()
+            };
+            root.add_child(node, parents)
+        }
+    }
+    root.connections.extend(connections);
+    root
+}
+
+/// Sets `missing_method` on the connections to a node whose GDScript, and the
+/// scripts it extends, define no function by the connection's method name.
+/// Once the scripts give way to an engine class, a method without a leading
+/// underscore may be one of the class's own, such as `queue_free`, so it
+/// passes.
+fn check_methods(root: &mut Node, project: &Project) {
+    let mut sources: HashMap<String, Option<String>> = HashMap::new();
+    let mut missing = Vec::new();
+    for (path, node) in root.paths() {
+        for (i, conn) in node.connections.iter().enumerate() {
+            let target = match conn.to == root.name {
+                true => Some(root as &Node),
+                false => root.find(&conn.to),
+            };
+            let Some(script) = target.and_then(|target| target.script()) else {
+                continue;
+            };
+            if conn.missing.is_none() && !defines(project, &script.path, &conn.method, &mut sources) {
+                missing.push((path.clone(), i, script.path.clone()));
+            }
+        }
+    }
+    for (path, i, script) in missing {
+        let node = path.split('/').filter(|name| *name != ".").fold(&mut *root, |node, name| node.children.get_mut(name).unwrap());
+        node.connections[i].missing_method = Some(script);
+    }
+}
+
+/// Whether the script at `script`, or one it extends, has `func method(`.
+/// Scripts that can't be read, or aren't GDScript, are given the benefit of
+/// the doubt.
+fn defines(project: &Project, script: &str, method: &str, sources: &mut HashMap<String, Option<String>>) -> bool {
+    let mut script = script.to_string();
+    // a chain this long must loop back on itself
+    for _ in 0..32 {
+        if !script.ends_with(".gd") {
+            return true;
+        }
+        let source = sources.entry(script.clone()).or_insert_with(|| fs::read_to_string(project.resolve(&script)).ok());
+        let Some(source) = source else {
+            return true;
+        };
+        for line in source.lines() {
+            let line = line.trim_start();
+            let line = line.strip_prefix("static ").unwrap_or(line);
+            if let Some(name) = line.strip_prefix("func ") {
+                if name.split(|c: char| c == '(' || c.is_whitespace()).next() == Some(method) {
+                    return true;
+                }
+            }
+        }
+        script = match base(project, source) {
+            Some(base) => base,
+            None => return !method.starts_with('_'),
+        };
+        // a relative extends is relative to the script's directory
+        if !script.starts_with("res://") {
+            return true;
+        }
+    }
+    true
+}
+
+/// The script a GDScript's `extends` names, by path or by class name; `None`
+/// when it extends an engine class, or nothing.
+fn base(project: &Project, source: &str) -> Option<String> {
+    let base = source.lines().rev().find_map(|line| line.trim_start().strip_prefix("extends "))?;
+    let base = base.trim().trim_end_matches(':');
+    match base.starts_with('"') || base.starts_with('\'') {
+        true => Some(base.trim_matches(|c| c == '"' || c == '\'').to_string()),
+        false => project.class_path(base).map(|path| path.to_string()),
+    }
+}
+
+/// The files besides its own that parsing `scene` within `project` read, or
+/// looked for: those of its ext_resources, and the scripts its scripts extend.
+pub fn dependencies(scene: &Scene, project: &Project) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for res in &scene.ext_resources {
+        files.push(project.resolve(&res.path));
+        let mut script = res.path.clone();
+        // a chain this long must loop back on itself
+        for _ in 0..32 {
+            if !script.ends_with(".gd") {
+                break;
+            }
+            let Ok(source) = fs::read_to_string(project.resolve(&script)) else {
+                break;
+            };
+            match base(project, &source) {
+                Some(base) if base.starts_with("res://") => script = base,
+                _ => break,
+            }
+            files.push(project.resolve(&script));
+        }
+    }
+    files
+}
+
+/// Whether the node at `path` may belong to a scene instanced at it or above
+/// it, one of the paths in `instanced`, which this scene does not list but may
+/// still refer to and add children to.
+pub fn from_instance(path: &str, instanced: &HashSet<String>) -> bool {
+    instanced.contains(".") || path.match_indices('/').map(|(i, _)| &path[..i]).chain([path]).any(|prefix| instanced.contains(prefix))
+}
+
+/// The text of a value with each `ExtResource(...)` in it replaced by the
+/// resource's quoted path.
+fn with_paths(value: &Value, text: &str, ext_resources: &IndexMap<String, ExtResource>) -> String {
+    let mut out = String::new();
+    let mut at = value.start;
+    for reference in value.references() {
+        let Some(("ExtResource", id)) = reference.reference() else {
+            continue;
+        };
+        if let Some(res) = ext_resources.get(id) {
+            out += &text[at..reference.start];
+            out += &format!("\"{}\"", res.path);
+            at = reference.end;
+        }
+    }
+    out + &text[at..value.end]
+}
+
+/// Inlines the scenes that nodes instance beneath them, and the scenes those
+/// instance in turn, giving the tree Godot builds at runtime. The instancing
+/// node's properties and the nodes it overrides take precedence over the
+/// instanced scene's, and the nodes it adds come after the scene's own.
+///
+/// `seen` holds the files being expanded, so that a scene instancing itself is
+/// left as it is; instances whose file can't be found are too.
+pub fn expand_instances(node: &mut Node, project: Option<&Project>, seen: &mut Vec<PathBuf>) -> io::Result<()> {
+    for child in node.children.values_mut() {
+        expand_instances(child, project, seen)?;
+    }
+    let Some(res) = node.instance.as_ref().filter(|res| res.path.ends_with(".tscn")) else {
+        return Ok(());
+    };
+    let file = match (project, res.path.starts_with("res://")) {
+        (Some(project), _) => project.resolve(&res.path),
+        (None, false) => PathBuf::from(&res.path),
+        (None, true) => return Ok(()),
+    };
+    let Ok(file) = fs::canonicalize(file) else {
+        return Ok(());
+    };
+    if seen.contains(&file) {
+        return Ok(());
+    }
+    let reader = File::open(&file).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", file.display(), err)))?;
+    let mut inner = parse(BufReader::new(reader), project).map_err(|err| io::Error::new(err.kind(), format!("{}: {}", file.display(), err)))?;
+    seen.push(file);
+    expand_instances(&mut inner, project, seen)?;
+    seen.pop();
+    merge(node, inner);
+    Ok(())
+}
+
+/// Lays a node of the scene being shown over the node of an instanced scene it stands for.
+fn merge(outer: &mut Node, inner: Node) {
+    if outer._type.is_empty() {
+        outer._type = inner._type;
+    }
+    let mut parameters = inner.parameters;
+    for param in outer.parameters.drain(..) {
+        match parameters.iter().position(|p| p.key == param.key) {
+            Some(at) => parameters[at] = param,
+            None => parameters.push(param),
+        }
+    }
+    outer.parameters = parameters;
+    outer.connections.extend(inner.connections);
+    let mut children = IndexMap::new();
+    for (name, inner_child) in inner.children {
+        match outer.children.shift_remove(&name) {
+            Some(mut outer_child) => {
+                merge(&mut outer_child, inner_child);
+                children.insert(name, outer_child);
+            }
+            None => {
+                children.insert(name, inner_child);
+            }
+        }
+    }
+    children.extend(outer.children.drain(..));
+    outer.children = children;
+}
+
+/// Marks the nodes this scene adds beneath a node that belongs to an instanced
+/// scene, when that instance lacks an `[editable]` section. `owner` is the path of
+/// the instance `node` comes from, if `node` is not one this scene declares.
+///
+/// Children of the root are left alone: an inherited scene may add nodes anywhere.
+fn mark_dropped(node: &mut Node, path: &str, owner: Option<&str>, editable: &[String]) {
+    let brings_instance = node.instance.is_some();
+    for (name, child) in node.children.iter_mut() {
+        let child_path = format!("{}/{}", path, name);
+        let declared = !child._type.is_empty() || child.instance.is_some();
+        let child_owner = match (declared, owner) {
+            (true, Some(owner)) => {
+                child.dropped = !editable.iter().any(|e| e == owner);
+                None
+            }
+            (true, None) => None,
+            (false, Some(owner)) => Some(owner.to_string()),
+            // an override of a node from the instance this node brings in
+            (false, None) => brings_instance.then(|| path.to_string()),
+        };
+        mark_dropped(child, &child_path, child_owner.as_deref(), editable);
+    }
+}
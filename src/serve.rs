@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use serde_json::{json, Value};
+
+use crate::project::Project;
+use crate::json;
+use crate::scene::{self, Scene};
+
+/// Where a project's server listens, and where clients look for it.
+pub fn socket(project: &Project) -> PathBuf {
+    project.root.join(".gdtree").join("serve.sock")
+}
+
+/// The modification time of each file a parse depended on, `None` for those
+/// that weren't there.
+type Stamps = Vec<(PathBuf, Option<SystemTime>)>;
+
+/// The scenes parsed so far, each with the stamps of its file and of the files
+/// it depends on, shared by every client of one server.
+struct Cache {
+    project: Project,
+    scenes: Mutex<HashMap<PathBuf, (Stamps, Arc<Scene>)>>,
+}
+
+fn modified(file: &Path) -> Option<SystemTime> {
+    fs::metadata(file).and_then(|meta| meta.modified()).ok()
+}
+
+impl Cache {
+    /// The parsed scene at `file`, a canonical path, parsing it again only
+    /// when it, a script it uses or a scene it instances has changed.
+    fn scene(&self, file: PathBuf) -> io::Result<Arc<Scene>> {
+        if let Some((stamps, scene)) = self.scenes.lock().unwrap().get(&file) {
+            if stamps.iter().all(|(file, when)| modified(file) == *when) {
+                return Ok(scene.clone());
+            }
+        }
+        // stamped before reading, so a change made meanwhile is seen next time
+        let when = fs::metadata(&file)?.modified()?;
+        // a fresh project looks uids and class names up again, as files may
+        // have moved since the last parse
+        let project = Project::new(self.project.root.clone());
+        let scene = Arc::new(scene::parse_scene(BufReader::new(fs::File::open(&file)?), Some(&project))?);
+        let mut stamps = vec![(file.clone(), Some(when))];
+        stamps.extend(scene::dependencies(&scene, &project).into_iter().map(|dep| {
+            let when = modified(&dep);
+            (dep, when)
+        }));
+        self.scenes.lock().unwrap().insert(file, (stamps, scene.clone()));
+        Ok(scene)
+    }
+
+    /// Answers one request, a JSON object such as
+    /// `{"scene": "res://main.tscn", "format_version": 2}`, with
+    /// `{"scene": {...}}` holding the document --format json prints, or with
+    /// `{"error": "..."}`. Only the project's own files are served.
+    fn answer(&self, request: &str) -> Value {
+        let request: Value = match serde_json::from_str(request) {
+            Ok(request) => request,
+            Err(err) => return json!({ "error": format!("bad request: {}", err) }),
+        };
+        let Some(path) = request["scene"].as_str() else {
+            return json!({ "error": "bad request: expected {\"scene\": \"res://...\"}" });
+        };
+        let version = match &request["format_version"] {
+            Value::Null => json::FORMAT_VERSION,
+            version => match version.as_u64() {
+                Some(v @ 1..) if v <= json::FORMAT_VERSION as u64 => v as u32,
+                _ => return json!({ "error": format!("bad request: format_version must be from 1 to {}, not {}", json::FORMAT_VERSION, version) }),
+            },
+        };
+        let file = match path.starts_with("res://") {
+            true => self.project.resolve(path),
+            false => PathBuf::from(path),
+        };
+        // the project's root is canonical already; `..` and links are not let out of it
+        let file = match fs::canonicalize(&file) {
+            Ok(file) if file.starts_with(&self.project.root) => file,
+            Ok(_) => return json!({ "error": format!("{}: not in the project at {}", path, self.project.root.display()) }),
+            Err(err) => return json!({ "error": format!("{}: {}", path, err) }),
+        };
+        match self.scene(file) {
+            Ok(scene) => json!({ "scene": json::scene(&scene, version) }),
+            Err(err) => json!({ "error": format!("{}: {}", path, err) }),
+        }
+    }
+}
+
+/// Serves the project's parsed scenes over a Unix socket in its .gdtree
+/// directory until killed, so that editor integrations, `--format json` and
+/// scripts working on the same project share one parse of each scene. Clients send a
+/// request per line and get an answer per line; see `Cache::answer`.
+#[cfg(unix)]
+pub fn run(project: Project) -> io::Result<()> {
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::thread;
+
+    let path = socket(&project);
+    if path.exists() {
+        // a socket nobody answers on was left by a server that was killed
+        match UnixStream::connect(&path) {
+            Ok(_) => return Err(io::Error::new(io::ErrorKind::AddrInUse, format!("a server is already running on {}", path.display()))),
+            Err(_) => fs::remove_file(&path)?,
+        }
+    }
+    fs::create_dir_all(path.parent().unwrap())?;
+    let listener = UnixListener::bind(&path)?;
+    eprintln!("serving {} on {}", project.root.display(), path.display());
+    let cache = Arc::new(Cache { project, scenes: Mutex::new(HashMap::new()) });
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let cache = cache.clone();
+        thread::spawn(move || {
+            let mut out = &stream;
+            for line in BufReader::new(&stream).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if writeln!(out, "{}", cache.answer(&line)).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+pub fn run(_project: Project) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, "gdtree serve needs Unix domain sockets, which this platform lacks"))
+}
+
+/// Asks the project's server for the JSON document of a scene. Returns `None`
+/// when no server is running, so the caller parses the scene itself.
+#[cfg(unix)]
+pub fn fetch(project: &Project, file: &Path, version: u32) -> Option<io::Result<Value>> {
+    use std::os::unix::net::UnixStream;
+
+    let stream = UnixStream::connect(socket(project)).ok()?;
+    let file = fs::canonicalize(file).unwrap_or(file.to_path_buf());
+    let request = json!({ "scene": file.to_string_lossy(), "format_version": version });
+    let answer = (|| {
+        writeln!(&stream, "{}", request)?;
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        serde_json::from_str::<Value>(&line).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    })();
+    Some(answer.and_then(|mut answer| match answer["error"].as_str() {
+        Some(err) => Err(io::Error::other(err.to_string())),
+        None => Ok(answer["scene"].take()),
+    }))
+}
+
+#[cfg(not(unix))]
+pub fn fetch(_project: &Project, _file: &Path, _version: u32) -> Option<io::Result<Value>> {
+    None
+}
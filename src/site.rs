@@ -0,0 +1,144 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::annotations::Annotations;
+use crate::dot;
+use crate::html::{self, escape};
+use crate::lint::Finding;
+use crate::owners::Owners;
+use crate::project::Project;
+use crate::report::SceneReport;
+use crate::units;
+
+/// Writes a static site about a project into `out`, for publishing from CI:
+/// an index of the scenes and their measures, a page with the foldable tree of
+/// each scene, the dependencies between files, and the lint findings. The
+/// dependency graph is also written as Graphviz's `deps.dot`.
+///
+/// `findings` holds the findings of each scene of `reports`, in the same order.
+/// Returns how many pages were written.
+pub fn write(title: &str, reports: &[SceneReport], findings: &[Vec<Finding>], owners: &Owners, out: &Path) -> io::Result<usize> {
+    fs::create_dir_all(out)?;
+    let mut pages = 0;
+    for r in reports.iter() {
+        let file = out.join(scene_page(&r.name));
+        fs::create_dir_all(file.parent().unwrap())?;
+        let up = "../".repeat(scene_page(&r.name).matches('/').count());
+        let body = format!("<nav><a href=\"{}index.html\">{}</a></nav>\n<h1>{}</h1>\n{}", up, escape(title), escape(&r.name), html::tree(&r.scene.root, 0, &Annotations::default()));
+        fs::write(file, html::page(&r.name, &body))?;
+        pages += 1;
+    }
+    fs::write(out.join("index.html"), index(title, reports, findings, owners))?;
+    fs::write(out.join("deps.html"), deps(title, reports))?;
+    fs::write(out.join("lint.html"), lint(title, reports, findings, owners))?;
+    let graph: Vec<(String, Vec<String>)> = reports.iter().map(|r| (r.name.clone(), r.scene.ext_resources.iter().map(|res| res.path.clone()).collect())).collect();
+    fs::write(out.join("deps.dot"), dot::dependencies(&graph))?;
+    Ok(pages + 3)
+}
+
+/// Where a scene's page goes, under `scenes/`, keeping the project's directories.
+fn scene_page(name: &str) -> String {
+    let relative = name.strip_prefix("res://").unwrap_or(name).trim_start_matches('/');
+    format!("scenes/{}.html", relative)
+}
+
+fn scene_link(name: &str) -> String {
+    format!("<a href=\"{}\">{}</a>", escape(&scene_page(name)), escape(name))
+}
+
+fn nav(title: &str) -> String {
+    format!("<nav><a href=\"index.html\">{}</a> · <a href=\"deps.html\">Dependencies</a> · <a href=\"lint.html\">Lint findings</a></nav>\n", escape(title))
+}
+
+fn index(title: &str, reports: &[SceneReport], findings: &[Vec<Finding>], owners: &Owners) -> String {
+    let mut body = nav(title);
+    body += &format!("<h1>{}</h1>\n<p>{} scenes, {} nodes, {} lint problems</p>\n", escape(title), reports.len(), reports.iter().map(|r| r.nodes).sum::<usize>(), findings.iter().map(Vec::len).sum::<usize>());
+    body += "<table>\n<tr><th>scene</th><th>nodes</th><th>size</th><th>depth</th><th>refs</th><th>ext</th><th>sub</th><th>conns</th><th>problems</th>";
+    if !owners.is_empty() {
+        body += "<th>owners</th>";
+    }
+    body += "</tr>\n";
+    for (r, scene_findings) in reports.iter().zip(findings) {
+        let connections: usize = r.scene.root.paths().iter().map(|(_, node)| node.connections.len()).sum();
+        let numbers = [r.nodes.to_string(), units::bytes(r.size), r.depth.to_string(), r.refs.to_string(), r.scene.ext_resources.len().to_string(), r.scene.sub_resources.len().to_string(), connections.to_string(), scene_findings.len().to_string()];
+        body += &format!("<tr><td>{}</td>", scene_link(&r.name));
+        for number in numbers.iter() {
+            body += &format!("<td class=\"number\">{}</td>", escape(number));
+        }
+        if !owners.is_empty() {
+            body += &format!("<td>{}</td>", escape(&owners.of(&r.name).join(" ")));
+        }
+        body += "</tr>\n";
+    }
+    body += "</table>\n";
+    html::page(title, &body)
+}
+
+/// Each scene with the files it loads and the scenes that load it.
+fn deps(title: &str, reports: &[SceneReport]) -> String {
+    let mut used_by: HashMap<&str, Vec<&str>> = HashMap::new();
+    for r in reports.iter() {
+        for res in r.scene.ext_resources.iter() {
+            let users = used_by.entry(res.path.as_str()).or_default();
+            if !users.contains(&r.name.as_str()) {
+                users.push(&r.name);
+            }
+        }
+    }
+    let scenes: Vec<&str> = reports.iter().map(|r| r.name.as_str()).collect();
+    let link = |path: &str| match scenes.contains(&path) {
+        true => format!("<a href=\"#{}\">{}</a>", escape(path), escape(path)),
+        false => escape(path),
+    };
+    let mut body = nav(title);
+    body += "<h1>Dependencies</h1>\n<p>The graph is also in <a href=\"deps.dot\">deps.dot</a>, for Graphviz.</p>\n";
+    for r in reports.iter() {
+        body += &format!("<h2 id=\"{}\">{}</h2>\n", escape(&r.name), scene_link(&r.name));
+        if !r.scene.ext_resources.is_empty() {
+            body += "<p>Loads:</p>\n<ul>\n";
+            for res in r.scene.ext_resources.iter() {
+                body += &format!("<li>{} <span class=\"type\">({})</span></li>\n", link(&res.path), escape(&res._type));
+            }
+            body += "</ul>\n";
+        }
+        if let Some(users) = used_by.get(r.name.as_str()) {
+            body += "<p>Used by:</p>\n<ul>\n";
+            for user in users.iter() {
+                body += &format!("<li>{}</li>\n", link(user));
+            }
+            body += "</ul>\n";
+        }
+    }
+    html::page(&format!("Dependencies · {}", title), &body)
+}
+
+fn lint(title: &str, reports: &[SceneReport], findings: &[Vec<Finding>], owners: &Owners) -> String {
+    let mut body = nav(title);
+    let total: usize = findings.iter().map(Vec::len).sum();
+    body += &format!("<h1>Lint findings</h1>\n<p>{} problems</p>\n", total);
+    for (r, scene_findings) in reports.iter().zip(findings).filter(|(_, f)| !f.is_empty()) {
+        body += &format!("<h2>{}</h2>\n", scene_link(&r.name));
+        let scene_owners = owners.of(&r.name);
+        if !scene_owners.is_empty() {
+            body += &format!("<p>Owners: {}</p>\n", escape(&scene_owners.join(" ")));
+        }
+        body += "<table>\n<tr><th>node</th><th>problem</th><th>rule</th></tr>\n";
+        for finding in scene_findings.iter() {
+            body += &format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n", escape(&finding.node), escape(&finding.message), escape(&finding.rule));
+        }
+        body += "</table>\n";
+    }
+    html::page(&format!("Lint findings · {}", title), &body)
+}
+
+/// The site's title: the project's name from project.godot, or else its
+/// directory's.
+pub fn title(project: &Project) -> String {
+    let settings = project.settings("application").unwrap_or_default();
+    match settings.into_iter().find(|(key, _)| key == "config/name") {
+        Some((_, name)) if !name.is_empty() => name,
+        _ => project.root.file_name().map_or("Godot project".to_string(), |name| name.to_string_lossy().to_string()),
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use clap::ValueEnum;
+
+use crate::scene::Node;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Language {
+    Gdscript,
+    Csharp,
+}
+
+/// Builds one accessor line per node, for pasting into the scene root's script.
+///
+/// Nodes marked `unique_name_in_owner` are reached through their `%Name` shortcut.
+pub fn lines(nodes: &[(String, &Node)], language: Language) -> Vec<String> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    for (_, node) in nodes.iter() {
+        *seen.entry(node.name.as_str()).or_default() += 1;
+    }
+    nodes
+        .iter()
+        .map(|(path, node)| {
+            let unique = node.parameters.iter().any(|p| p.key == "unique_name_in_owner" && p.val == "true");
+            let path = match unique {
+                true => format!("%{}", node.name),
+                false => path.clone(),
+            };
+            // spell out the path when several nodes share a name
+            let words = match seen[node.name.as_str()] {
+                1 => snake_case(&node.name),
+                _ => path.trim_start_matches('%').split('/').map(snake_case).collect::<Vec<_>>().join("_"),
+            };
+            let class = match node._type.as_str() {
+                "" => "Node",
+                t => t,
+            };
+            match language {
+                Language::Gdscript => format!("@onready var {} := {}", identifier(&words), node_path(&path)),
+                Language::Csharp => format!(
+                    "private {} {} => GetNode<{}>(\"{}\");",
+                    class,
+                    identifier(&pascal_case(&words)),
+                    class,
+                    path.replace('\\', "\\\\").replace('"', "\\\""),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Converts `HealthBar`, `HTTPRequest` or `Sprite2D` to `health_bar`, `http_request`, `sprite_2d`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !out.is_empty() && !out.ends_with('_') {
+                out.push('_');
+            }
+            continue;
+        }
+        if i > 0 && !out.is_empty() && !out.ends_with('_') {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+            let boundary = (c.is_uppercase() && (prev.is_lowercase() || prev.is_ascii_digit()))
+                || (c.is_uppercase() && prev.is_uppercase() && next_lower)
+                || (c.is_ascii_digit() && prev.is_alphabetic());
+            if boundary {
+                out.push('_');
+            }
+        }
+        out.extend(c.to_lowercase());
+    }
+    out.trim_end_matches('_').to_string()
+}
+
+fn pascal_case(words: &str) -> String {
+    words
+        .split('_')
+        .map(|w| {
+            let mut chars = w.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Makes sure a generated name doesn't start with a digit or come out empty.
+fn identifier(name: &str) -> String {
+    match name.chars().next() {
+        Some(c) if !c.is_ascii_digit() => name.to_string(),
+        _ => format!("_{}", name),
+    }
+}
+
+/// Writes `$Path` or `%Name` shorthand, quoting the path when a segment isn't a plain identifier.
+fn node_path(path: &str) -> String {
+    let (sigil, path) = match path.strip_prefix('%') {
+        Some(name) => ("%", name),
+        None => ("$", path),
+    };
+    let plain = path.split('/').all(|segment| {
+        let mut chars = segment.chars();
+        chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+    });
+    match plain {
+        true => format!("{}{}", sigil, path),
+        false => format!("{}\"{}\"", sigil, path.replace('\\', "\\\\").replace('"', "\\\"")),
+    }
+}
@@ -0,0 +1,41 @@
+use regex::{Captures, Regex};
+
+use crate::parser;
+
+/// Splits a `--var name=value` argument.
+pub fn parse_var(arg: &str) -> Result<(String, String), String> {
+    match arg.split_once('=') {
+        Some((name, value)) if !name.is_empty() => Ok((name.to_string(), value.to_string())),
+        _ => Err(format!("expected NAME=VALUE, not {}", arg)),
+    }
+}
+
+/// Fills in the `{{name}}` placeholders of a scene template, which may appear in
+/// node names, paths and values alike. The header's uid is dropped, so that
+/// Godot gives each scene stamped out from the template one of its own.
+///
+/// Fails when a placeholder has no value, or when the result isn't a scene.
+pub fn render(template: &str, vars: &[(String, String)]) -> Result<String, String> {
+    let placeholder_re = Regex::new(r"\{\{\s*(?P<name>[A-Za-z_][A-Za-z0-9_]*)\s*\}\}").unwrap();
+    let mut missing: Vec<&str> = Vec::new();
+    for caps in placeholder_re.captures_iter(template) {
+        let name = caps.name("name").unwrap().as_str();
+        if !vars.iter().any(|(n, _)| n == name) && !missing.contains(&name) {
+            missing.push(name);
+        }
+    }
+    if !missing.is_empty() {
+        return Err(format!("no value for {}; pass --var NAME=VALUE", missing.join(", ")));
+    }
+    let text = placeholder_re.replace_all(template, |caps: &Captures| {
+        // the last --var of a name wins, as it would on most command lines
+        vars.iter().rev().find(|(name, _)| name == &caps["name"]).unwrap().1.clone()
+    });
+
+    let uid_re = Regex::new(r#"^(?P<header>\[gd_(?:scene|resource)\b[^\n]*?) uid="[^"]*""#).unwrap();
+    let text = uid_re.replace(&text, "$header").to_string();
+    if let Err(err) = parser::parse(&text) {
+        return Err(format!("the rendered scene doesn't parse: {}", err));
+    }
+    Ok(text)
+}
@@ -0,0 +1,52 @@
+use std::time::{Duration, Instant};
+
+/// How long each step of a run took, for --timings.
+pub struct Timings {
+    enabled: bool,
+    /// When the last step ended.
+    mark: Instant,
+    start: Instant,
+    steps: Vec<(String, Duration)>,
+}
+
+impl Timings {
+    pub fn new(enabled: bool) -> Self {
+        let now = Instant::now();
+        Self { enabled, mark: now, start: now, steps: Vec::new() }
+    }
+
+    /// Ends a step, recording the time since the previous one ended.
+    pub fn lap(&mut self, step: impl Into<String>) {
+        if !self.enabled {
+            return;
+        }
+        let now = Instant::now();
+        self.steps.push((step.into(), now - self.mark));
+        self.mark = now;
+    }
+
+    /// Records a step timed elsewhere, such as on another thread, and counts
+    /// the time since the previous step as taken up by it.
+    pub fn record(&mut self, step: impl Into<String>, time: Duration) {
+        if !self.enabled {
+            return;
+        }
+        self.steps.push((step.into(), time));
+        self.mark = Instant::now();
+    }
+
+    /// Prints the steps to stderr, so they stay out of the output proper.
+    pub fn print(&self) {
+        if !self.enabled {
+            return;
+        }
+        let total = self.start.elapsed();
+        let other = total.saturating_sub(self.steps.iter().map(|(_, time)| *time).sum());
+        let width = self.steps.iter().map(|(step, _)| step.chars().count()).max().unwrap_or(0).max("total".len());
+        eprintln!();
+        for (step, time) in self.steps.iter().chain([("other".to_string(), other)].iter()) {
+            eprintln!("{:width$}  {:>10.3} ms", step, time.as_secs_f64() * 1000.0);
+        }
+        eprintln!("{:width$}  {:>10.3} ms", "total", total.as_secs_f64() * 1000.0);
+    }
+}
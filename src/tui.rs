@@ -0,0 +1,542 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::apply::{self, Operation, Plan};
+use crate::classdb::ClassDb;
+use crate::project::Project;
+use crate::scene::{self, Node};
+use crate::{units, world, zorder};
+
+/// One line of the tree pane.
+struct Row {
+    path: String,
+    depth: usize,
+}
+
+/// The explorer's state: the scene, which nodes are folded, where the cursor is,
+/// what is being searched for and the edits that can be undone.
+struct Explorer<'a> {
+    root: Node,
+    project: Option<&'a Project>,
+    /// The class reference, when configured, to tell which nodes can be hidden.
+    classdb: Option<ClassDb>,
+    /// The scene's own file, so an instance of it isn't opened inside itself.
+    file: PathBuf,
+    collapsed: HashSet<String>,
+    /// Instances whose scenes have been opened beneath them.
+    expanded: HashSet<String>,
+    rows: Vec<Row>,
+    list: ListState,
+    detail_scroll: u16,
+    /// What is being typed or confirmed, while the prompt is open.
+    prompt: Option<Prompt>,
+    search: String,
+    /// Each edit made so far, with the scene file as it read before it.
+    undo: Vec<(String, String)>,
+    status: String,
+}
+
+/// What the prompt on the status line is asking for.
+enum Prompt {
+    Search(String),
+    /// A new name for the node at the path.
+    Rename(String, String),
+    /// Whether to delete the node at the path.
+    Delete(String),
+}
+
+/// Where to look for a match of the search, from the cursor.
+enum Direction {
+    Here,
+    Next,
+    Previous,
+}
+
+const HELP: &str = "↑↓ move  ←→ fold  enter toggle  i open instance  / search  n/N next/previous  [ ] scroll details  r rename  v visible  d delete  u undo  q quit";
+
+/// Browses a scene in the terminal like Godot's Scene dock: a tree whose nodes
+/// fold away, the properties and connections of the selected node beside it,
+/// a search that jumps to matching node names as they are typed, and a key that
+/// opens an instanced scene to show its nodes in place.
+///
+/// Nodes can be renamed, shown or hidden and deleted with their subtrees. Each
+/// edit is written to the scene file straight away through the same edits as
+/// `gdtree apply`, and can be undone, newest first, for as long as the
+/// explorer is open.
+pub fn run(root: Node, file: PathBuf, project: Option<&Project>, classdb: Option<ClassDb>) -> io::Result<()> {
+    if !io::stdout().is_terminal() {
+        return Err(io::Error::other("gdtree tui needs a terminal"));
+    }
+    let mut explorer = Explorer {
+        root,
+        project,
+        classdb,
+        file,
+        collapsed: HashSet::new(),
+        expanded: HashSet::new(),
+        rows: Vec::new(),
+        list: ListState::default().with_selected(Some(0)),
+        detail_scroll: 0,
+        prompt: None,
+        search: String::new(),
+        undo: Vec::new(),
+        status: HELP.to_string(),
+    };
+    explorer.refresh();
+    let mut terminal = ratatui::init();
+    let result = explorer.run(&mut terminal);
+    ratatui::restore();
+    result
+}
+
+impl Explorer<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let quit = match self.prompt.is_some() {
+                true => {
+                    self.answer(key);
+                    false
+                }
+                false => self.command(key),
+            };
+            if quit {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Handles a key outside the search prompt, returning whether to quit.
+    fn command(&mut self, key: KeyEvent) -> bool {
+        let selected = self.list.selected().unwrap_or(0);
+        self.status = HELP.to_string();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.select(selected.saturating_sub(1)),
+            KeyCode::Down | KeyCode::Char('j') => self.select(selected + 1),
+            KeyCode::PageUp => self.select(selected.saturating_sub(20)),
+            KeyCode::PageDown => self.select(selected + 20),
+            KeyCode::Home | KeyCode::Char('g') => self.select(0),
+            KeyCode::End | KeyCode::Char('G') => self.select(self.rows.len().saturating_sub(1)),
+            KeyCode::Right | KeyCode::Char('l') => {
+                let path = self.rows[selected].path.clone();
+                match self.collapsed.remove(&path) {
+                    true => self.refresh(),
+                    false if self.has_children(&path) => self.select(selected + 1),
+                    false => (),
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                let path = self.rows[selected].path.clone();
+                if self.has_children(&path) && !self.collapsed.contains(&path) {
+                    self.collapsed.insert(path);
+                    self.refresh();
+                } else if let Some(parent) = parent(&path) {
+                    self.select_path(&parent);
+                }
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                let path = self.rows[selected].path.clone();
+                if !self.collapsed.remove(&path) && self.has_children(&path) {
+                    self.collapsed.insert(path);
+                }
+                self.refresh();
+            }
+            KeyCode::Char('i') => self.open_instance(),
+            KeyCode::Char('/') => self.prompt = Some(Prompt::Search(String::new())),
+            KeyCode::Char('r') => {
+                let path = self.rows[selected].path.clone();
+                let name = self.root.find(&path).map(|node| node.name.clone()).unwrap_or_default();
+                self.prompt = Some(Prompt::Rename(path, name));
+            }
+            KeyCode::Char('d') if self.rows[selected].path == "." => self.status = "the root can't be deleted".to_string(),
+            KeyCode::Char('d') => self.prompt = Some(Prompt::Delete(self.rows[selected].path.clone())),
+            KeyCode::Char('v') => self.toggle_visible(),
+            KeyCode::Char('u') => self.undo(),
+            KeyCode::Char('n') => self.jump(Direction::Next),
+            KeyCode::Char('N') => self.jump(Direction::Previous),
+            KeyCode::Char(']') => self.detail_scroll = self.detail_scroll.saturating_add(5),
+            KeyCode::Char('[') => self.detail_scroll = self.detail_scroll.saturating_sub(5),
+            _ => (),
+        }
+        false
+    }
+
+    /// Handles a key typed into the prompt: the search, jumping to the first
+    /// match as it changes, the new name of a node, or the answer to whether
+    /// to delete one.
+    fn answer(&mut self, key: KeyEvent) {
+        match (self.prompt.take(), key.code) {
+            (_, KeyCode::Esc) => (),
+            (Some(Prompt::Search(_)), KeyCode::Enter) => (),
+            (Some(Prompt::Search(mut text)), KeyCode::Backspace | KeyCode::Char(_)) => {
+                match key.code {
+                    KeyCode::Char(c) => text.push(c),
+                    _ => {
+                        text.pop();
+                    }
+                }
+                self.search = text.clone();
+                self.prompt = Some(Prompt::Search(text));
+                self.jump(Direction::Here);
+            }
+            (Some(Prompt::Rename(path, name)), KeyCode::Enter) => {
+                let renamed = match parent(&path) {
+                    None => ".".to_string(),
+                    Some(parent) if parent == "." => name.clone(),
+                    Some(parent) => format!("{}/{}", parent, name),
+                };
+                self.edit(Operation::Rename { scene: self.scene(), node: path, to: name }, &renamed);
+            }
+            (Some(Prompt::Rename(path, mut name)), KeyCode::Backspace | KeyCode::Char(_)) => {
+                match key.code {
+                    KeyCode::Char(c) => name.push(c),
+                    _ => {
+                        name.pop();
+                    }
+                }
+                self.prompt = Some(Prompt::Rename(path, name));
+            }
+            (Some(Prompt::Delete(path)), KeyCode::Char('y')) => {
+                let parent = parent(&path).unwrap_or_default();
+                self.edit(Operation::Delete { scene: self.scene(), node: path }, &parent);
+            }
+            (Some(Prompt::Delete(_)), _) => self.status = "not deleted".to_string(),
+            (prompt, _) => self.prompt = prompt,
+        }
+    }
+
+    /// The scene as a plan names it.
+    fn scene(&self) -> String {
+        self.file.display().to_string()
+    }
+
+    /// Shows the selected node if it is hidden and hides it otherwise. Only
+    /// CanvasItems and Node3Ds have `visible`; other nodes are left alone.
+    fn toggle_visible(&mut self) {
+        let Some(path) = self.list.selected().map(|i| self.rows[i].path.clone()) else {
+            return;
+        };
+        let Some(node) = self.root.find(&path) else {
+            return;
+        };
+        if !self.can_hide(node) {
+            self.status = format!("{} is not a CanvasItem or Node3D, so it can't be hidden", node.name);
+            return;
+        }
+        let hidden = node.parameters.iter().any(|p| p.key == "visible" && p.val == "false");
+        let value = if hidden { "true" } else { "false" };
+        self.edit(Operation::Set { scene: self.scene(), node: path.clone(), property: "visible".to_string(), value: value.to_string() }, &path);
+    }
+
+    /// Whether the node has a `visible` property, being a CanvasItem or a
+    /// Node3D. An instance is whatever the root of its scene is.
+    fn can_hide(&self, node: &Node) -> bool {
+        let classdb = self.classdb.as_ref();
+        let has_transform = node.parameters.iter().any(|p| p.key == "transform");
+        if zorder::is_canvas_item(node, classdb) || world::is_node_3d(node, has_transform, classdb) {
+            return true;
+        }
+        let Some(res) = node.instance.as_ref().filter(|_| node._type.is_empty()) else {
+            return false;
+        };
+        let file = self.project.map_or(PathBuf::from(&res.path), |p| p.resolve(&res.path));
+        let root = fs::File::open(file).and_then(|f| scene::parse(io::BufReader::new(f), self.project));
+        root.is_ok_and(|root| !root._type.is_empty() && self.can_hide(&root))
+    }
+
+    /// Makes an edit to the scene file, keeping what the file read before it
+    /// for undo, and reads the scene again with the cursor on `select`.
+    fn edit(&mut self, operation: Operation, select: &str) {
+        let before = match fs::read_to_string(&self.file) {
+            Ok(text) => text,
+            Err(err) => {
+                self.status = format!("could not read {}: {}", self.file.display(), err);
+                return;
+            }
+        };
+        let plan = Plan { operations: vec![operation] };
+        let edited = apply::edit(&plan, None).and_then(|applied| applied.write().map(|_| applied.edits));
+        match edited {
+            Ok(edits) => {
+                let edit = edits.join("; ");
+                let edit = edit.strip_prefix(&format!("{}: ", self.scene())).unwrap_or(&edit).to_string();
+                self.status = format!("{}; u to undo", edit);
+                self.undo.push((edit, before));
+                self.reload(select);
+            }
+            Err(err) => self.status = err.to_string(),
+        }
+    }
+
+    /// Puts the scene file back as it was before the last edit.
+    fn undo(&mut self) {
+        let Some((edit, before)) = self.undo.pop() else {
+            self.status = "nothing to undo".to_string();
+            return;
+        };
+        let current = self.list.selected().map(|i| self.rows[i].path.clone()).unwrap_or_default();
+        match fs::write(&self.file, before) {
+            Ok(()) => {
+                self.status = format!("undid {}", edit);
+                self.reload(&current);
+            }
+            Err(err) => self.status = format!("could not undo {}: {}", edit, err),
+        }
+    }
+
+    /// Reads the scene again after its file has changed. Instances opened
+    /// beneath their nodes are closed again, since the file doesn't hold them.
+    fn reload(&mut self, select: &str) {
+        let root = fs::File::open(&self.file).and_then(|f| scene::parse(io::BufReader::new(f), self.project));
+        match root {
+            Ok(root) => self.root = root,
+            Err(err) => {
+                self.status = format!("could not read the scene again: {}", err);
+                return;
+            }
+        }
+        self.expanded.clear();
+        self.reveal(select);
+    }
+
+    /// The paths of the nodes whose names contain the search, ignoring case, in
+    /// tree order.
+    fn matches(&self) -> Vec<String> {
+        let needle = self.search.to_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        self.root.paths().into_iter().filter(|(_, node)| node.name.to_lowercase().contains(&needle)).map(|(path, _)| path).collect()
+    }
+
+    /// Moves to the next match after the cursor, the previous one before it, or
+    /// the first at or after it, going round at the ends.
+    fn jump(&mut self, direction: Direction) {
+        let matches = self.matches();
+        if matches.is_empty() {
+            self.status = match self.search.is_empty() {
+                true => "nothing searched for yet; press / to search".to_string(),
+                false => format!("no node matches {}", self.search),
+            };
+            return;
+        }
+        let current = self.list.selected().map(|i| self.rows[i].path.clone()).unwrap_or_default();
+        let order: Vec<String> = self.root.paths().into_iter().map(|(path, _)| path).collect();
+        let position = |path: &str| order.iter().position(|p| p == path).unwrap_or(0);
+        let here = position(&current);
+        let next = match direction {
+            Direction::Here => matches.iter().find(|p| position(p) >= here).or(matches.first()),
+            Direction::Next => matches.iter().find(|p| position(p) > here).or(matches.first()),
+            Direction::Previous => matches.iter().rev().find(|p| position(p) < here).or(matches.last()),
+        };
+        if let Some(path) = next.cloned() {
+            self.reveal(&path);
+        }
+    }
+
+    /// Opens the scene the selected node instances and shows its nodes beneath it.
+    fn open_instance(&mut self) {
+        let Some(path) = self.list.selected().map(|i| self.rows[i].path.clone()) else {
+            return;
+        };
+        if self.expanded.contains(&path) {
+            self.status = "instance already open".to_string();
+            return;
+        }
+        let Some(node) = node_mut(&mut self.root, &path) else {
+            return;
+        };
+        let Some(res) = node.instance.clone() else {
+            self.status = "not an instance".to_string();
+            return;
+        };
+        let mut seen = vec![self.file.clone()];
+        match scene::expand_instances(node, self.project, &mut seen) {
+            Ok(()) => {
+                self.status = format!("opened {}", res.path);
+                self.expanded.insert(path.clone());
+                self.collapsed.remove(&path);
+                self.refresh();
+            }
+            Err(err) => self.status = format!("could not open {}: {}", res.path, err),
+        }
+    }
+
+    fn has_children(&self, path: &str) -> bool {
+        self.root.find(path).is_some_and(|node| !node.children.is_empty())
+    }
+
+    /// Unfolds the ancestors of the node at `path` and moves the cursor to it.
+    fn reveal(&mut self, path: &str) {
+        let mut ancestor = parent(path);
+        while let Some(dir) = ancestor {
+            self.collapsed.remove(&dir);
+            ancestor = parent(&dir);
+        }
+        self.refresh();
+        self.select_path(path);
+    }
+
+    fn select_path(&mut self, path: &str) {
+        if let Some(i) = self.rows.iter().position(|row| row.path == path) {
+            self.select(i);
+        }
+    }
+
+    fn select(&mut self, i: usize) {
+        self.list.select(Some(i.min(self.rows.len().saturating_sub(1))));
+        self.detail_scroll = 0;
+    }
+
+    /// Lists the rows left showing by the folds, keeping the cursor on the same
+    /// node, or its nearest shown ancestor when that was folded away.
+    fn refresh(&mut self) {
+        let current = self.list.selected().and_then(|i| self.rows.get(i)).map(|row| row.path.clone());
+        self.rows.clear();
+        visible(&self.root, ".", 0, &self.collapsed, &mut self.rows);
+        let mut wanted = current;
+        while let Some(path) = wanted {
+            if let Some(i) = self.rows.iter().position(|row| row.path == path) {
+                self.list.select(Some(i));
+                return;
+            }
+            wanted = parent(&path);
+        }
+        self.list.select(Some(0));
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [main, status] = Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+        let [tree, details] = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)]).areas(main);
+
+        let items: Vec<ListItem> = self.rows.iter().map(|row| ListItem::new(self.tree_line(row))).collect();
+        let list = List::new(items)
+            .block(Block::bordered().title(format!(" {} ", self.root.name)))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, tree, &mut self.list);
+
+        let lines = match self.list.selected().and_then(|i| self.rows.get(i)) {
+            Some(row) => self.detail_lines(&row.path),
+            None => Vec::new(),
+        };
+        let paragraph = Paragraph::new(lines).block(Block::bordered().title(" details ")).wrap(Wrap { trim: false }).scroll((self.detail_scroll, 0));
+        frame.render_widget(paragraph, details);
+
+        let status_line = match &self.prompt {
+            Some(Prompt::Search(text)) => format!("/{}", text),
+            Some(Prompt::Rename(path, name)) => format!("rename {} to: {}", path, name),
+            Some(Prompt::Delete(path)) => {
+                let below = self.root.find(path).map_or(0, |node| node.paths().len() - 1);
+                format!("delete {} and the {} nodes below it? y/n", path, below)
+            }
+            None => self.status.clone(),
+        };
+        frame.render_widget(Paragraph::new(status_line), status);
+    }
+
+    fn tree_line(&self, row: &Row) -> Line<'static> {
+        let Some(node) = self.root.find(&row.path) else {
+            return Line::from(row.path.clone());
+        };
+        let fold = match (node.children.is_empty(), self.collapsed.contains(&row.path)) {
+            (true, _) => "  ",
+            (false, true) => "▸ ",
+            (false, false) => "▾ ",
+        };
+        let mut spans = vec![Span::raw(format!("{}{}", "  ".repeat(row.depth), fold)), Span::styled(node.name.clone(), Style::new().fg(Color::Blue).add_modifier(Modifier::BOLD))];
+        if !node._type.is_empty() && node._type != node.name {
+            spans.push(Span::styled(format!(" ({})", node._type), Style::new().fg(Color::Cyan)));
+        }
+        if let Some(res) = node.instance.as_ref().filter(|_| !self.expanded.contains(&row.path)) {
+            spans.push(Span::styled(format!(" * {}", res.path), Style::new().fg(Color::Green)));
+        }
+        Line::from(spans)
+    }
+
+    fn detail_lines(&self, path: &str) -> Vec<Line<'static>> {
+        let Some(node) = self.root.find(path) else {
+            return Vec::new();
+        };
+        let heading = |text: &str| Line::styled(text.to_string(), Style::new().add_modifier(Modifier::BOLD));
+        let mut lines = vec![heading(path)];
+        if !node._type.is_empty() {
+            lines.push(Line::from(format!("type: {}", node._type)));
+        }
+        if let Some(res) = &node.instance {
+            lines.push(Line::from(vec![Span::raw("instance: "), Span::styled(res.path.clone(), Style::new().fg(Color::Green))]));
+        }
+        if !node.parameters.is_empty() {
+            lines.push(Line::default());
+            lines.push(heading("properties"));
+            for param in node.parameters.iter() {
+                let val = match &param.resource {
+                    Some(res) => res.path.clone(),
+                    None => param.sub_type.clone().unwrap_or_else(|| units::one_line(&param.val)),
+                };
+                lines.push(Line::from(format!("  {} = {}", param.key, val)));
+            }
+        }
+        let outgoing: Vec<String> = node.connections.iter().map(|c| format!("  {}() => {}:{}(){}", c.signal, c.to, c.method, c.tags())).collect();
+        let incoming: Vec<String> = self
+            .root
+            .paths()
+            .iter()
+            .flat_map(|(_, other)| other.connections.iter())
+            .filter(|c| c.to == path || (path == "." && c.to == self.root.name))
+            .map(|c| format!("  {}:{}() => {}(){}", c.from, c.signal, c.method, c.tags()))
+            .collect();
+        for (title, list) in [("connections out", outgoing), ("connections in", incoming)] {
+            if !list.is_empty() {
+                lines.push(Line::default());
+                lines.push(heading(title));
+                lines.extend(list.into_iter().map(|text| Line::styled(text, Style::new().fg(Color::Yellow))));
+            }
+        }
+        lines
+    }
+}
+
+/// Adds the rows of a node and, unless it is folded, those beneath it.
+fn visible(node: &Node, path: &str, depth: usize, collapsed: &HashSet<String>, rows: &mut Vec<Row>) {
+    rows.push(Row { path: path.to_string(), depth });
+    if collapsed.contains(path) {
+        return;
+    }
+    for (name, child) in node.children.iter() {
+        let child_path = match path {
+            "." => name.clone(),
+            _ => format!("{}/{}", path, name),
+        };
+        visible(child, &child_path, depth + 1, collapsed, rows);
+    }
+}
+
+fn parent(path: &str) -> Option<String> {
+    match path {
+        "." => None,
+        _ => Some(path.rsplit_once('/').map_or(".".to_string(), |(parent, _)| parent.to_string())),
+    }
+}
+
+fn node_mut<'a>(root: &'a mut Node, path: &str) -> Option<&'a mut Node> {
+    if path == "." {
+        return Some(root);
+    }
+    path.split('/').try_fold(root, |node, name| node.children.get_mut(name))
+}
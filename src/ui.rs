@@ -0,0 +1,62 @@
+use crate::scene::Node;
+use crate::zorder;
+
+/// Anchor and margin settings, under Godot 4 and Godot 3 names, that a
+/// Container overwrites when it lays its children out.
+const PLACEMENT: &[&str] = &[
+    "anchor_left", "anchor_top", "anchor_right", "anchor_bottom", "anchors_preset", "offset_left", "offset_top", "offset_right",
+    "offset_bottom", "margin_left", "margin_top", "margin_right", "margin_bottom",
+];
+
+/// Controls that have no minimum size of their own, so stay as small as their
+/// anchors and offsets make them.
+const SIZELESS: &[&str] = &["Control", "ColorRect", "Panel", "ReferenceRect", "NinePatchRect"];
+
+/// Checks a scene for UI layouts that go wrong without an error: Controls
+/// placed by hand inside a Container, which places them itself; Controls with
+/// no width or height, which show nothing; and Controls under a Node2D, whose
+/// anchors have no rectangle to follow. Returns the node path and message of
+/// each.
+pub fn check(root: &Node) -> Vec<(String, String)> {
+    let mut findings = Vec::new();
+    for (path, node) in root.paths().into_iter().skip(1) {
+        if !zorder::is_control(&node._type) {
+            continue;
+        }
+        let parent_path = path.rsplit_once('/').map_or(".", |(parent, _)| parent);
+        let Some(parent) = root.find(parent_path) else {
+            continue;
+        };
+        let param = |key: &str| node.parameters.iter().find(|p| p.key == key).map(|p| p.val.as_str());
+        let number = |key: &str| param(key).and_then(|v| v.parse::<f64>().ok()).unwrap_or(0.0);
+
+        let in_container = parent._type.ends_with("Container") && !parent._type.ends_with("ViewportContainer");
+        // Godot 4 marks container children with layout_mode 2 but keeps any anchors they had
+        let placed: Vec<&str> = PLACEMENT.iter().copied().filter(|key| param(key).is_some_and(|v| v != "0" && v != "0.0")).collect();
+        if in_container && !placed.is_empty() {
+            findings.push((path.clone(), format!("sets {} inside {} ({}), which lays it out and ignores them", placed.join(", "), parent.name, parent._type)));
+        }
+
+        if zorder::is_node_2d(&parent._type) {
+            findings.push((path.clone(), format!("is a Control under {} ({}), so its anchors have no parent rectangle to follow", parent.name, parent._type)));
+        }
+
+        // a size or minimum size written out is taken as deliberate
+        let sized = ["custom_minimum_size", "rect_min_size", "size", "rect_size"].iter().any(|key| param(key).is_some());
+        if !in_container && SIZELESS.contains(&node._type.as_str()) && !sized {
+            let offset = |side: &str| number(&format!("offset_{}", side)) + number(&format!("margin_{}", side));
+            let width = number("anchor_right") != number("anchor_left") || offset("right") != offset("left");
+            let height = number("anchor_bottom") != number("anchor_top") || offset("bottom") != offset("top");
+            let empty = match (width, height) {
+                (false, false) => Some("no width or height"),
+                (false, true) => Some("no width"),
+                (true, false) => Some("no height"),
+                (true, true) => None,
+            };
+            if let Some(empty) = empty {
+                findings.push((path.clone(), format!("has {}, from its anchors and offsets, so draws nothing", empty)));
+            }
+        }
+    }
+    findings
+}
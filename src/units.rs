@@ -0,0 +1,61 @@
+use regex::Regex;
+
+/// Properties that Godot stores in radians.
+const RADIANS: &[&str] = &["rotation", "skew", "global_rotation", "global_skew"];
+
+/// The ` (90°)` following a radian-valued property, or ` (0°, 90°, 0°)` for a vector
+/// of them.
+pub fn angle(key: &str, val: &str) -> Option<String> {
+    if !RADIANS.contains(&key) {
+        return None;
+    }
+    let number_re = Regex::new(r"-?\d+(?:\.\d*)?(?:e[-+]?\d+)?").unwrap();
+    let inner = match val.split_once('(') {
+        Some((class, args)) if class.starts_with("Vector") => args.trim_end_matches(')'),
+        Some(_) => return None,
+        None => val,
+    };
+    let degrees = number_re
+        .find_iter(inner)
+        .map(|m| m.as_str().parse::<f64>().map(|radians| format!("{}°", round(radians.to_degrees()))))
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+    match degrees.is_empty() {
+        true => None,
+        false => Some(format!(" ({})", degrees.join(", "))),
+    }
+}
+
+/// Rounds to two decimals, dropping the fraction when it's all zeros.
+fn round(n: f64) -> String {
+    let n = (n * 100.0).round() / 100.0;
+    match n == 0.0 {
+        // no -0°
+        true => "0".to_string(),
+        false => format!("{}", n),
+    }
+}
+
+/// Shortens a value that runs over several lines, like shader code, to its
+/// first line and a count of the rest.
+pub fn one_line(val: &str) -> String {
+    match val.split_once('\n') {
+        Some((first, rest)) => format!("{} … ({} more lines)", first.trim_end(), rest.lines().count().max(1)),
+        None => val.to_string(),
+    }
+}
+
+/// Formats a byte count as `512 bytes`, `1.5 KiB`, `12.3 MiB` and so on.
+pub fn bytes(n: u64) -> String {
+    const UNITS: &[&str] = &["KiB", "MiB", "GiB", "TiB"];
+    if n < 1024 {
+        return format!("{} bytes", n);
+    }
+    let mut size = n as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{} {}", (size * 10.0).round() / 10.0, UNITS[unit])
+}
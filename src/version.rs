@@ -0,0 +1,93 @@
+use std::fmt;
+
+use crate::deprecated::{self, Change, CLASSES};
+use crate::scene::Node;
+
+/// The Godot release family that wrote a scene.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GodotVersion {
+    Godot2,
+    Godot3,
+    Godot4,
+}
+
+impl fmt::Display for GodotVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GodotVersion::Godot2 => write!(f, "Godot 2.x"),
+            GodotVersion::Godot3 => write!(f, "Godot 3.x"),
+            GodotVersion::Godot4 => write!(f, "Godot 4.x"),
+        }
+    }
+}
+
+/// Property prefixes only one major version writes.
+const GODOT3_PROPERTIES: &[&str] = &["rect_", "margin_", "custom_fonts/", "custom_colors/", "custom_constants/", "custom_styles/", "custom_icons/", "__meta__"];
+const GODOT4_PROPERTIES: &[&str] = &["offset_", "theme_override_", "layout_mode", "anchors_preset", "metadata/"];
+
+/// What a scene's header and contents say about the version that wrote it.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Detection {
+    pub version: GodotVersion,
+    /// The observations the guess rests on, strongest first.
+    pub evidence: Vec<String>,
+}
+
+/// Works out which Godot wrote a scene: the header's `format=` settles it, and
+/// failing that, uids and the class and property names in use.
+pub fn detect(format: Option<u32>, uid: Option<&str>, root: &Node) -> Detection {
+    let mut evidence = Vec::new();
+    if let Some(format) = format {
+        evidence.push(format!("format={}", format));
+    }
+    if let Some(uid) = uid {
+        evidence.push(format!("uid=\"{}\"", uid));
+    }
+
+    let (mut classes3, mut classes4, mut keys3, mut keys4) = (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    let godot4_classes: Vec<&str> = CLASSES
+        .iter()
+        .filter_map(|(_, change)| match change {
+            Change::Renamed(new) => Some(*new),
+            Change::Removed(_) => None,
+        })
+        .collect();
+    for (_, node) in root.paths() {
+        let class = node._type.as_str();
+        if deprecated::change(class).is_some() && !classes3.contains(&class) {
+            classes3.push(class);
+        } else if godot4_classes.contains(&class) && !classes4.contains(&class) {
+            classes4.push(class);
+        }
+        for param in node.parameters.iter() {
+            let key = param.key.as_str();
+            if GODOT3_PROPERTIES.iter().any(|p| key.starts_with(p)) && !keys3.contains(&key) {
+                keys3.push(key);
+            } else if GODOT4_PROPERTIES.iter().any(|p| key.starts_with(p)) && !keys4.contains(&key) {
+                keys4.push(key);
+            }
+        }
+    }
+    for (label, names) in [
+        ("Godot 3 class names", &classes3),
+        ("Godot 4 class names", &classes4),
+        ("Godot 3 property names", &keys3),
+        ("Godot 4 property names", &keys4),
+    ] {
+        if !names.is_empty() {
+            evidence.push(format!("{}: {}", label, names.join(", ")));
+        }
+    }
+
+    let version = match (format, uid) {
+        (Some(1), _) => GodotVersion::Godot2,
+        (Some(2), _) => GodotVersion::Godot3,
+        (Some(_), _) | (None, Some(_)) => GodotVersion::Godot4,
+        // without a header, go by whichever naming the scene uses more of
+        (None, None) if classes3.len() + keys3.len() > classes4.len() + keys4.len() => GodotVersion::Godot3,
+        (None, None) => GodotVersion::Godot4,
+    };
+    Detection { version, evidence }
+}
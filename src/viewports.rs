@@ -0,0 +1,92 @@
+use std::io::{self, Write};
+
+use crate::scene::Node;
+
+/// The settings worth seeing for each kind of node, under Godot 4 and Godot 3
+/// names; whichever the scene sets are shown.
+const SETTINGS: &[(&[&str], &[&str])] = &[
+    (&["Camera2D"], &["enabled", "current", "zoom", "offset", "anchor_mode", "ignore_rotation", "custom_viewport", "limit_left", "limit_top", "limit_right", "limit_bottom"]),
+    (
+        &["Camera3D", "Camera", "ClippedCamera", "InterpolatedCamera"],
+        &["current", "projection", "fov", "size", "near", "far", "keep_aspect", "cull_mask", "environment"],
+    ),
+    (
+        &["SubViewport", "Viewport"],
+        &[
+            "size",
+            "size_2d_override",
+            "render_target_update_mode",
+            "render_target_clear_mode",
+            "render_target_v_flip",
+            "transparent_bg",
+            "own_world_3d",
+            "own_world",
+            "disable_3d",
+            "usage",
+            "msaa_2d",
+            "msaa_3d",
+            "msaa",
+        ],
+    ),
+    (&["SubViewportContainer", "ViewportContainer"], &["stretch", "stretch_shrink"]),
+    (&["CanvasLayer", "ParallaxBackground"], &["layer", "visible", "follow_viewport_enabled", "follow_viewport_enable", "custom_viewport", "offset", "scale"]),
+];
+
+/// Tabulates a scene's cameras, viewports, viewport containers and canvas
+/// layers with the settings the scene gives them, and warns when more than one
+/// camera of a kind is marked current in the same viewport, since only one of
+/// them wins.
+pub fn report(root: &Node, out: &mut impl Write) -> io::Result<()> {
+    let nodes = root.paths();
+    let viewports: Vec<&str> = nodes.iter().filter(|(_, node)| node._type.ends_with("Viewport")).map(|(path, _)| path.as_str()).collect();
+    let mut rows: Vec<(String, String, String)> = Vec::new();
+    // (kind, viewport, camera) for each current camera
+    let mut current: Vec<(&str, &str, String)> = Vec::new();
+    for (path, node) in nodes.iter() {
+        let Some((_, keys)) = SETTINGS.iter().find(|(types, _)| types.contains(&node._type.as_str())) else {
+            continue;
+        };
+        let viewport = viewports.iter().filter(|v| path.starts_with(&format!("{}/", v))).max_by_key(|v| v.len()).copied().unwrap_or(".");
+        let path = match path.as_str() {
+            "." => node.name.clone(),
+            _ => path.clone(),
+        };
+        let settings: Vec<String> = keys
+            .iter()
+            .filter_map(|key| node.parameters.iter().find(|p| p.key == *key))
+            .map(|p| match &p.resource {
+                Some(res) => format!("{}={}", p.key, res.path),
+                None => format!("{}={}", p.key, p.val),
+            })
+            .collect();
+        if node.parameters.iter().any(|p| p.key == "current" && p.val == "true") {
+            let kind = if node._type == "Camera2D" { "2D" } else { "3D" };
+            current.push((kind, viewport, path.clone()));
+        }
+        rows.push((path, node._type.clone(), settings.join(", ")));
+    }
+
+    let width = rows.iter().map(|(path, _, _)| path.chars().count()).max().unwrap_or(0).max("node".len());
+    let type_width = rows.iter().map(|(_, _type, _)| _type.chars().count()).max().unwrap_or(0).max("type".len());
+    writeln!(out, "{:width$}  {:type_width$}  settings", "node", "type")?;
+    for (path, _type, settings) in rows.iter() {
+        let settings = if settings.is_empty() { "(defaults)" } else { settings };
+        writeln!(out, "{:width$}  {:type_width$}  {}", path, _type, settings)?;
+    }
+    let mut warned: Vec<(&str, &str)> = Vec::new();
+    for (kind, viewport, _) in current.iter() {
+        if warned.contains(&(kind, viewport)) {
+            continue;
+        }
+        warned.push((kind, viewport));
+        let cameras: Vec<&str> = current.iter().filter(|(k, v, _)| k == kind && v == viewport).map(|(_, _, path)| path.as_str()).collect();
+        if cameras.len() > 1 {
+            let within = match *viewport {
+                "." => String::new(),
+                viewport => format!(" in {}", viewport),
+            };
+            writeln!(out, "warning: {} {} cameras are marked current{}: {}", cameras.len(), kind, within, cameras.join(", "))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher as _};
+
+const POLL: Duration = Duration::from_millis(250);
+/// How long a file must go without further events before it counts as saved.
+const SETTLE: Duration = Duration::from_millis(100);
+
+/// Waits for a file to be saved again, going by the filesystem's change
+/// notifications, or by polling its modification time and size where those
+/// aren't available.
+pub struct Watcher {
+    path: PathBuf,
+    seen: Option<(SystemTime, u64)>,
+    /// The watcher has to be kept for its events to keep coming.
+    events: Option<(RecommendedWatcher, Receiver<notify::Result<Event>>)>,
+}
+
+impl Watcher {
+    pub fn new(path: &Path) -> Self {
+        Self { path: path.to_path_buf(), seen: stamp(path), events: notifications(path) }
+    }
+
+    /// Blocks until the file has changed and then held still for a moment, so a
+    /// save that is still being written isn't read half-way.
+    pub fn wait(&mut self) {
+        loop {
+            match &self.events {
+                Some((_, events)) => {
+                    if !self.notified(events) {
+                        // the watcher stopped; carry on by polling
+                        self.events = None;
+                        continue;
+                    }
+                }
+                None => self.poll(),
+            }
+            let now = stamp(&self.path);
+            // a touch that left the file as it was isn't a save
+            if now.is_some() && now != self.seen {
+                self.seen = now;
+                return;
+            }
+        }
+    }
+
+    /// Waits for events on the file until they stop coming, returning false if
+    /// the watcher has gone away.
+    fn notified(&self, events: &Receiver<notify::Result<Event>>) -> bool {
+        let ours = |event: &notify::Result<Event>| match event {
+            Ok(event) => event.paths.iter().any(|p| p.file_name() == self.path.file_name()),
+            Err(_) => false,
+        };
+        loop {
+            match events.recv() {
+                Ok(event) if ours(&event) => break,
+                Ok(_) => continue,
+                Err(_) => return false,
+            }
+        }
+        loop {
+            match events.recv_timeout(SETTLE) {
+                Ok(_) => continue,
+                Err(RecvTimeoutError::Timeout) => return true,
+                Err(RecvTimeoutError::Disconnected) => return false,
+            }
+        }
+    }
+
+    fn poll(&self) {
+        let mut pending = None;
+        loop {
+            thread::sleep(POLL);
+            let now = stamp(&self.path);
+            // editors that save by renaming leave the file missing for a moment
+            if now.is_none() || now == self.seen {
+                pending = None;
+                continue;
+            }
+            if pending == now {
+                return;
+            }
+            pending = now;
+        }
+    }
+}
+
+/// Watches the file's directory, since editors that save by writing a new file
+/// and renaming it over the old one would leave a watch on the file itself
+/// behind.
+fn notifications(path: &Path) -> Option<(RecommendedWatcher, Receiver<notify::Result<Event>>)> {
+    let dir = fs::canonicalize(path).ok()?.parent()?.to_path_buf();
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).ok()?;
+    watcher.watch(&dir, RecursiveMode::NonRecursive).ok()?;
+    Some((watcher, rx))
+}
+
+fn stamp(path: &Path) -> Option<(SystemTime, u64)> {
+    let meta = fs::metadata(path).ok()?;
+    Some((meta.modified().ok()?, meta.len()))
+}
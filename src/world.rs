@@ -0,0 +1,120 @@
+use std::io::{self, Write};
+
+use crate::classdb::ClassDb;
+use crate::scene::Node;
+
+/// Godot 3's spatial classes, which lack the `3D` suffix of their Godot 4 names.
+const SPATIALS: &[&str] = &[
+    "Node3D", "Spatial", "MeshInstance", "MultiMeshInstance", "Camera", "ClippedCamera", "DirectionalLight", "OmniLight", "SpotLight",
+    "KinematicBody", "RigidBody", "StaticBody", "Area", "CollisionShape", "CollisionPolygon", "RayCast", "SpringArm", "Skeleton",
+    "BoneAttachment", "Particles", "CPUParticles", "VehicleBody", "VehicleWheel", "Position3D", "Listener", "GIProbe", "BakedLightmap",
+    "ReflectionProbe", "Sprite3D", "AnimatedSprite3D", "CSGBox", "CSGCombiner", "CSGCylinder", "CSGMesh", "CSGPolygon", "CSGSphere",
+    "CSGTorus", "GridMap", "Path", "PathFollow", "Navigation", "NavigationMeshInstance", "RemoteTransform", "SoftBody", "InterpolatedCamera",
+];
+
+/// A basis, as rows, and an origin.
+#[derive(Clone, Copy)]
+struct Transform {
+    basis: [[f64; 3]; 3],
+    origin: [f64; 3],
+}
+
+const IDENTITY: Transform = Transform { basis: [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]], origin: [0.0; 3] };
+
+impl Transform {
+    /// Reads a `Transform3D(...)`, or Godot 3's `Transform(...)`: the basis row by
+    /// row, then the origin.
+    fn parse(val: &str) -> Option<Self> {
+        let args = val.strip_prefix("Transform3D(").or_else(|| val.strip_prefix("Transform("))?.strip_suffix(')')?;
+        let numbers: Vec<f64> = args.split(',').map(|n| n.trim().parse()).collect::<Result<_, _>>().ok()?;
+        let [xx, xy, xz, yx, yy, yz, zx, zy, zz, ox, oy, oz] = numbers[..] else {
+            return None;
+        };
+        Some(Self { basis: [[xx, xy, xz], [yx, yy, yz], [zx, zy, zz]], origin: [ox, oy, oz] })
+    }
+
+    /// This transform applied after `local`, as a parent's is to its child's.
+    fn then(&self, local: &Transform) -> Transform {
+        let mut out = Transform { basis: [[0.0; 3]; 3], origin: self.origin };
+        for i in 0..3 {
+            for j in 0..3 {
+                out.basis[i][j] = (0..3).map(|k| self.basis[i][k] * local.basis[k][j]).sum();
+            }
+            out.origin[i] += (0..3).map(|k| self.basis[i][k] * local.origin[k]).sum::<f64>();
+        }
+        out
+    }
+}
+
+/// One Node3D and where it ends up.
+struct Placement {
+    path: String,
+    _type: String,
+    origin: [f64; 3],
+}
+
+/// Lists each Node3D of a scene with its world position: its `transform`
+/// composed with those of the Node3Ds above it, up to the scene root or the
+/// nearest `top_level` node. The distance from the origin shows up nodes that
+/// were dragged far off by accident.
+///
+/// The class reference decides which nodes are Node3Ds when it is configured;
+/// otherwise it is guessed from the node types.
+pub fn report(root: &Node, classdb: Option<&ClassDb>, out: &mut impl Write) -> io::Result<()> {
+    let mut placements = Vec::new();
+    collect(root, ".", IDENTITY, classdb, &mut placements);
+    writeln!(out, "{:>10}  {:>10}  {:>10}  {:>10}  node", "x", "y", "z", "distance")?;
+    for p in placements.iter() {
+        let label = match p._type.is_empty() {
+            true => p.path.clone(),
+            false => format!("{} ({})", p.path, p._type),
+        };
+        let distance = p.origin.iter().map(|c| c * c).sum::<f64>().sqrt();
+        writeln!(out, "{:>10.2}  {:>10.2}  {:>10.2}  {:>10.2}  {}", p.origin[0], p.origin[1], p.origin[2], distance, label)?;
+    }
+    writeln!(out, "{} 3D nodes", placements.len())?;
+    Ok(())
+}
+
+fn collect(node: &Node, path: &str, parent: Transform, classdb: Option<&ClassDb>, placements: &mut Vec<Placement>) {
+    let param = |key: &str| node.parameters.iter().find(|p| p.key == key).map(|p| p.val.as_str());
+    let local = param("transform").and_then(Transform::parse);
+    let world = match is_node_3d(node, local.is_some(), classdb) {
+        true => {
+            let local = local.unwrap_or(IDENTITY);
+            let world = match param("top_level") {
+                Some("true") => local,
+                _ => parent.then(&local),
+            };
+            let path = match path {
+                "." => node.name.clone(),
+                _ => path.to_string(),
+            };
+            placements.push(Placement { path, _type: node._type.clone(), origin: world.origin });
+            world
+        }
+        // a plain Node or a CanvasItem starts its Node3D children afresh
+        false => IDENTITY,
+    };
+    for (name, child) in node.children.iter() {
+        let child_path = match path {
+            "." => name.clone(),
+            _ => format!("{}/{}", path, name),
+        };
+        collect(child, &child_path, world, classdb, placements);
+    }
+}
+
+/// Whether a node is a Node3D, by the class reference when it knows the node's
+/// type and by the type's name, or a `transform`, otherwise.
+pub fn is_node_3d(node: &Node, has_transform: bool, classdb: Option<&ClassDb>) -> bool {
+    let _type = node._type.as_str();
+    if _type.is_empty() {
+        // an instance, whose root's type lives in the other scene
+        return has_transform;
+    }
+    match classdb.filter(|db| db.class(_type).is_some()) {
+        Some(db) => db.ancestry(_type).iter().any(|class| class == "Node3D" || class == "Spatial"),
+        None => has_transform || (_type.ends_with("3D") && _type != "NavigationAgent3D") || SPATIALS.contains(&_type),
+    }
+}
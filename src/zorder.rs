@@ -0,0 +1,125 @@
+use std::io::{self, Write};
+
+use crate::classdb::ClassDb;
+use crate::scene::Node;
+
+/// Controls that draw, under their Godot 4 and Godot 3 names; nodes of the
+/// Node2D family mostly end in `2D` and are caught by that instead.
+const CONTROLS: &[&str] = &[
+    "Control", "Container", "BoxContainer", "HBoxContainer", "VBoxContainer", "GridContainer", "MarginContainer", "CenterContainer",
+    "PanelContainer", "ScrollContainer", "TabContainer", "SplitContainer", "HSplitContainer", "VSplitContainer", "AspectRatioContainer",
+    "FlowContainer", "HFlowContainer", "VFlowContainer", "SubViewportContainer", "ViewportContainer", "Label", "RichTextLabel", "Button",
+    "CheckBox", "CheckButton", "ColorPickerButton", "MenuButton", "OptionButton", "LinkButton", "TextureButton", "LineEdit", "TextEdit",
+    "CodeEdit", "Panel", "NinePatchRect", "TextureRect", "ColorRect", "ReferenceRect", "ProgressBar", "TextureProgressBar",
+    "TextureProgress", "HSlider", "VSlider", "HScrollBar", "VScrollBar", "SpinBox", "ItemList", "Tree", "TabBar", "Tabs", "GraphEdit",
+    "GraphNode", "VideoStreamPlayer", "VideoPlayer",
+];
+
+/// Godot 3's Node2D classes without the suffix, and Godot 4's tile layers.
+const NODES_2D: &[&str] = &["Node2D", "Sprite", "AnimatedSprite", "YSort", "TileMap", "TileMapLayer", "ParallaxLayer", "CanvasModulate", "BackBufferCopy"];
+
+/// One node in the order it is drawn.
+struct Item {
+    path: String,
+    _type: String,
+    layer: i64,
+    z: i64,
+    /// Set when z_as_relative is off, so the node's z_index ignores its parents'.
+    absolute: bool,
+}
+
+/// Lists a 2D scene's CanvasItems in the order Godot draws them: by the layer of
+/// the CanvasLayer they are under (0 outside any), then by effective z index,
+/// then in tree order. A node's effective z index adds its parent's unless
+/// `z_as_relative` is off.
+///
+/// The class reference decides which nodes are CanvasItems when it is
+/// configured; otherwise it is guessed from the node types.
+pub fn report(root: &Node, classdb: Option<&ClassDb>, out: &mut impl Write) -> io::Result<()> {
+    let mut items = Vec::new();
+    collect(root, ".", 0, 0, classdb, &mut items);
+    // a stable sort keeps tree order among equals
+    items.sort_by_key(|item| (item.layer, item.z));
+    writeln!(out, "{:>5}  {:>5}  node", "layer", "z")?;
+    for item in items.iter() {
+        let label = match item._type.is_empty() {
+            true => item.path.clone(),
+            false => format!("{} ({})", item.path, item._type),
+        };
+        let note = match item.absolute {
+            true => " [z_as_relative off]",
+            false => "",
+        };
+        writeln!(out, "{:>5}  {:>5}  {}{}", item.layer, item.z, label, note)?;
+    }
+    writeln!(out, "{} canvas items", items.len())?;
+    Ok(())
+}
+
+fn collect(node: &Node, path: &str, layer: i64, z: i64, classdb: Option<&ClassDb>, items: &mut Vec<Item>) {
+    let param = |key: &str| node.parameters.iter().find(|p| p.key == key).map(|p| p.val.as_str());
+    let (mut layer, mut z) = (layer, z);
+    if is_canvas_layer(node, classdb) {
+        // ParallaxBackground sits far behind by default
+        let default = if node._type == "ParallaxBackground" { -100 } else { 1 };
+        layer = param("layer").and_then(|v| v.parse().ok()).unwrap_or(default);
+        z = 0;
+    } else if is_canvas_item(node, classdb) {
+        let z_index: i64 = param("z_index").and_then(|v| v.parse().ok()).unwrap_or(0);
+        let absolute = param("z_as_relative") == Some("false");
+        z = match absolute {
+            true => z_index,
+            false => z + z_index,
+        };
+        let path = match path {
+            "." => node.name.clone(),
+            _ => path.to_string(),
+        };
+        items.push(Item { path, _type: node._type.clone(), layer, z, absolute });
+    } else {
+        // a plain Node breaks the chain of relative z indices
+        z = 0;
+    }
+    for (name, child) in node.children.iter() {
+        let child_path = match path {
+            "." => name.clone(),
+            _ => format!("{}/{}", path, name),
+        };
+        collect(child, &child_path, layer, z, classdb, items);
+    }
+}
+
+fn is_canvas_layer(node: &Node, classdb: Option<&ClassDb>) -> bool {
+    match classdb.filter(|db| db.class(&node._type).is_some()) {
+        Some(db) => db.ancestry(&node._type).iter().any(|class| class == "CanvasLayer"),
+        None => node._type == "CanvasLayer" || node._type == "ParallaxBackground",
+    }
+}
+
+/// Whether a node is a CanvasItem, by the class reference when it knows the
+/// node's type and by the type's name otherwise.
+pub fn is_canvas_item(node: &Node, classdb: Option<&ClassDb>) -> bool {
+    let _type = node._type.as_str();
+    if _type.is_empty() {
+        // an instance: its root's type lives in the other scene, but only a
+        // CanvasItem can take these
+        return node.parameters.iter().any(|p| match p.key.as_str() {
+            "position" => p.val.starts_with("Vector2"),
+            key => matches!(key, "z_index" | "modulate" | "anchor_right" | "offset_right" | "margin_right"),
+        });
+    }
+    match classdb.filter(|db| db.class(_type).is_some()) {
+        Some(db) => db.ancestry(_type).iter().any(|class| class == "CanvasItem"),
+        None => is_node_2d(_type) || is_control(_type),
+    }
+}
+
+/// Whether a node type is a Control, going by its name.
+pub fn is_control(_type: &str) -> bool {
+    CONTROLS.contains(&_type)
+}
+
+/// Whether a node type is a Node2D, going by its name.
+pub fn is_node_2d(_type: &str) -> bool {
+    (_type.ends_with("2D") && _type != "NavigationAgent2D") || NODES_2D.contains(&_type)
+}
@@ -0,0 +1,27 @@
+use super::*;
+
+#[test]
+fn input_actions() {
+    let output = gdtree(&fixture("actions"), &["input-actions"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "undefined actions:
+  dash: res://player.gd:8, res://player.gd:9
+  pause: res://controls.gd:4
+unused actions:
+  crouch
+4 actions defined, 6 used in 2 scripts; 2 undefined, 1 unused
+"
+    );
+}
+
+#[test]
+fn all_defined_and_used() {
+    let dir = copy("actions", "actions-all");
+    let settings = fs::read_to_string(dir.join("project.godot")).unwrap();
+    let settings = settings.replace("crouch={", "pause={\n\"events\": []\n}\ndash={");
+    fs::write(dir.join("project.godot"), settings).unwrap();
+    let out = stdout(&gdtree(&dir, &["input-actions"]));
+    assert_eq!(out, "5 actions defined, 6 used in 2 scripts; 0 undefined, 0 unused\n");
+}
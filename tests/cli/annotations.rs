@@ -0,0 +1,55 @@
+use super::*;
+
+#[test]
+fn sidecar() {
+    let out = stdout(&gdtree(&fixture("annotations"), &["arena.tscn"]));
+    assert_eq!(
+        out,
+        "Arena [boss arena]
+├── Boss (CharacterBody2D) [owner: combat, cost: 2.1 ms]
+└── UI (CanvasLayer)
+    └── HealthBar (ProgressBar) [todo: replace with TextureProgressBar]
+"
+    );
+}
+
+#[test]
+fn annotations_file() {
+    let out = stdout(&gdtree(&fixture("annotations"), &["--annotations", "other.json", "arena.tscn"]));
+    assert_eq!(
+        out,
+        "Arena
+├── Boss (CharacterBody2D)
+└── UI (CanvasLayer) [overlay]
+    └── HealthBar (ProgressBar) [3]
+"
+    );
+}
+
+#[test]
+fn html() {
+    let out = stdout(&gdtree(&fixture("annotations"), &["--format", "html", "arena.tscn"]));
+    assert!(out.contains("<span class=\"name\">Boss</span> <span class=\"type\">(CharacterBody2D)</span> <span class=\"note\">[owner: combat, cost: 2.1 ms]</span>"), "{}", out);
+}
+
+#[test]
+fn bookmarked_subtree() {
+    let dir = copy("annotations", "annotations-bookmark");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    stdout(&gdtree(&dir, &["bookmark", "add", "arena.tscn", "ui", "UI"]));
+    let out = stdout(&gdtree(&dir, &["--bookmark", "ui", "arena.tscn"]));
+    assert_eq!(out, "UI\n└── HealthBar (ProgressBar) [todo: replace with TextureProgressBar]\n");
+}
+
+#[test]
+fn left_out_of_git_textconv() {
+    let out = stdout(&gdtree(&fixture("annotations"), &["--git-textconv", "arena.tscn"]));
+    assert!(!out.contains('['), "{}", out);
+}
+
+#[test]
+fn not_json() {
+    let output = gdtree(&fixture("annotations"), &["--annotations", "arena.tscn", "arena.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("gdtree: arena.tscn: expected value"));
+}
@@ -0,0 +1,255 @@
+use super::*;
+
+#[test]
+fn apply() {
+    let dir = copy("apply", "apply");
+    let plan = "operations:
+  - rename: { scene: res://main.tscn, node: \"%OK\", to: Confirm }
+  - repath: { from: res://ui/menu.tscn, to: res://ui/pause.tscn }
+  - set: { scene: res://main.tscn, node: Panel, property: visible, value: \"false\" }
+";
+    fs::write(dir.join("plan.yaml"), plan).unwrap();
+    let before = fs::read_to_string(dir.join("main.tscn")).unwrap();
+
+    let out = stdout(&gdtree(&dir, &["apply", "--dry-run", "plan.yaml"]));
+    assert_eq!(
+        out,
+        "res://main.tscn: renamed %OK to Confirm (6 lines)
+res://main.tscn: res://ui/menu.tscn -> res://ui/pause.tscn
+res://main.tscn: Panel: visible = false
+3 edits to make
+"
+    );
+    assert_eq!(fs::read_to_string(dir.join("main.tscn")).unwrap(), before);
+
+    let out = stdout(&gdtree(&dir, &["apply", "plan.yaml"]));
+    assert!(out.ends_with("made 3 edits\n"), "{}", out);
+    let after = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    for line in [
+        "[ext_resource type=\"PackedScene\" path=\"res://ui/pause.tscn\" id=\"2_menu\"]",
+        "target = NodePath(\"Panel/Confirm\")",
+        "[node name=\"Panel\" type=\"Control\" parent=\".\"]\nlayout_mode = 3\nvisible = false\n",
+        "[node name=\"Confirm\" type=\"Button\" parent=\"Panel\"]",
+        "[node name=\"Icon\" type=\"Sprite2D\" parent=\"Panel/Confirm\"]\nback = NodePath(\"..:text\")",
+        "[connection signal=\"pressed\" from=\"Panel/Confirm\" to=\".\" method=\"_on_ok_pressed\"]",
+    ] {
+        assert!(after.contains(line), "{} not in\n{}", line, after);
+    }
+    assert!(!after.contains("Panel/OK") && !after.contains("name=\"OK\""), "{}", after);
+    assert!(!dir.join("main.tscn.partial").exists());
+}
+
+#[test]
+fn json_plan() {
+    let dir = copy("apply", "apply-json");
+    let plan = r#"{"operations": [{"set": {"scene": "main.tscn", "node": ".", "property": "target", "value": "NodePath(\"Other\")"}}]}"#;
+    fs::write(dir.join("plan.json"), plan).unwrap();
+    stdout(&gdtree(&dir, &["apply", "plan.json"]));
+    let after = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    assert!(after.contains("script = ExtResource(\"1_main\")\ntarget = NodePath(\"Other\")\n\n"), "{}", after);
+}
+
+#[test]
+fn all_or_nothing() {
+    let dir = copy("apply", "apply-all-or-nothing");
+    let plan = "operations:
+  - set: { scene: res://main.tscn, node: Panel, property: visible, value: \"false\" }
+  - rename: { scene: res://main.tscn, node: Panel/Nope, to: Yes }
+";
+    fs::write(dir.join("plan.yaml"), plan).unwrap();
+    let before = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    let output = gdtree(&dir, &["apply", "plan.yaml"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "gdtree: res://main.tscn: no node at Panel/Nope\n");
+    assert_eq!(fs::read_to_string(dir.join("main.tscn")).unwrap(), before);
+}
+
+#[test]
+fn failed_write() {
+    let dir = copy("apply", "apply-failed-write");
+    let plan = "operations:
+  - set: { scene: res://main.tscn, node: Panel, property: visible, value: \"false\" }
+  - set: { scene: res://ui/menu.tscn, node: ., property: visible, value: \"false\" }
+";
+    fs::write(dir.join("plan.yaml"), plan).unwrap();
+    // nothing can be written over a directory, so the menu can't be
+    fs::create_dir(dir.join("ui/menu.tscn.partial")).unwrap();
+    let (main, menu) = (fs::read_to_string(dir.join("main.tscn")).unwrap(), fs::read_to_string(dir.join("ui/menu.tscn")).unwrap());
+    let output = gdtree(&dir, &["apply", "plan.yaml"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(fs::read_to_string(dir.join("main.tscn")).unwrap(), main);
+    assert_eq!(fs::read_to_string(dir.join("ui/menu.tscn")).unwrap(), menu);
+    assert!(!dir.join("main.tscn.partial").exists());
+}
+
+#[test]
+fn bad_renames() {
+    let dir = copy("apply", "apply-bad-renames");
+    for (to, error) in [("A/B", "A/B is not a valid node name"), ("Other", ". already has a child named Other")] {
+        let plan = format!("operations:\n  - rename: {{ scene: main.tscn, node: Panel, to: \"{}\" }}\n", to);
+        fs::write(dir.join("plan.yaml"), plan).unwrap();
+        let output = gdtree(&dir, &["apply", "plan.yaml"]);
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(String::from_utf8_lossy(&output.stderr), format!("gdtree: main.tscn: {}\n", error));
+    }
+}
+
+#[test]
+fn connections() {
+    let dir = copy("apply", "apply-connections");
+    let plan = "operations:
+  - connect: { scene: main.tscn, signal: pressed, from: Panel/OK, to: Other, method: _on_press }
+  - connect: { scene: main.tscn, signal: pressed, from: Panel/OK, to: ., method: _on_ok_pressed }
+  - disconnect: { scene: main.tscn, signal: pressed, from: Panel/OK, method: _on_gone }
+  - retarget: { scene: main.tscn, signal: pressed, from: Panel/Cancel, new_to: Other, new_method: _on_cancel }
+";
+    fs::write(dir.join("plan.yaml"), plan).unwrap();
+    let out = stdout(&gdtree(&dir, &["apply", "plan.yaml"]));
+    assert_eq!(
+        out,
+        "main.tscn: connected Panel/OK:pressed() => Other:_on_press()
+main.tscn: disconnected Panel/OK:pressed() (1 connections)
+main.tscn: retargeted Panel/Cancel:pressed() (1 connections)
+made 3 edits
+"
+    );
+    let after = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    assert!(
+        after.ends_with(
+            "[connection signal=\"pressed\" from=\"Panel/OK\" to=\".\" method=\"_on_ok_pressed\"]
+[connection signal=\"pressed\" from=\"Panel/Cancel\" to=\"Other\" method=\"_on_cancel\"]
+[connection signal=\"toggled\" from=\"Menu/Sound\" to=\".\" method=\"_on_ok_pressed\"]
+[connection signal=\"pressed\" from=\"Panel/OK\" to=\"Other\" method=\"_on_press\"]
+"
+        ),
+        "{}",
+        after
+    );
+}
+
+#[test]
+fn reconnect() {
+    let dir = copy("apply", "reconnect");
+    let out = stdout(&gdtree(&dir, &["reconnect", "main.tscn", "--signal", "ready", "--from", ".", "--to", "Other", "--method", "_on_ready"]));
+    assert_eq!(out, "main.tscn: connected .:ready() => Other:_on_ready()\n");
+    let out = stdout(&gdtree(&dir, &["reconnect", "main.tscn", "--signal", "ready", "--from", ".", "--to", "Other", "--method", "_on_ready"]));
+    assert_eq!(out, "already connected\n");
+    let out = stdout(&gdtree(&dir, &["reconnect", "main.tscn", "--signal", "ready", "--from", ".", "--new-method", "_on_main_ready", "--dry-run"]));
+    assert_eq!(out, "main.tscn: retargeted .:ready() (1 connections)\n");
+    let out = stdout(&gdtree(&dir, &["reconnect", "main.tscn", "--signal", "ready", "--from", ".", "--remove"]));
+    assert_eq!(out, "main.tscn: disconnected .:ready() (1 connections)\n");
+    assert_eq!(fs::read_to_string(dir.join("main.tscn")).unwrap(), fs::read_to_string(fixture("apply/main.tscn")).unwrap());
+
+    for (args, code, error) in [
+        (&["--from", "Panel/OK"][..], 2, "adding a connection needs --to and --method"),
+        (&["--from", "Nope", "--remove"][..], 1, "gdtree: main.tscn: no connection of Nope:pressed() to remove\n"),
+        (&["--from", "Panel/OK", "--to", "Gone", "--method", "x"][..], 1, "gdtree: main.tscn: no node at Gone\n"),
+    ] {
+        let output = gdtree(&dir, &[&["reconnect", "main.tscn", "--signal", "pressed"], args].concat());
+        assert_eq!(output.status.code(), Some(code));
+        assert!(String::from_utf8_lossy(&output.stderr).contains(error), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
+
+#[test]
+fn delete() {
+    let dir = copy("apply", "apply-delete");
+    fs::write(dir.join("plan.yaml"), "operations:\n  - delete: { scene: main.tscn, node: Panel }\n").unwrap();
+    let out = stdout(&gdtree(&dir, &["apply", "plan.yaml"]));
+    assert_eq!(out, "main.tscn: deleted Panel (3 nodes)\nmade 1 edits\n");
+    let after = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    assert!(after.ends_with(
+        "target = NodePath(\"Panel/OK\")
+
+[node name=\"Other\" type=\"Node\" parent=\".\"]
+button = NodePath(\"%OK\")
+
+[node name=\"Menu\" parent=\".\" instance=ExtResource(\"2_menu\")]
+
+[connection signal=\"toggled\" from=\"Menu/Sound\" to=\".\" method=\"_on_ok_pressed\"]
+"
+    ), "{}", after);
+
+    fs::write(dir.join("plan.yaml"), "operations:\n  - delete: { scene: main.tscn, node: . }\n").unwrap();
+    let output = gdtree(&dir, &["apply", "plan.yaml"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "gdtree: main.tscn: the root can't be deleted\n");
+}
+
+#[test]
+fn relative_targets() {
+    let dir = copy("apply", "apply-relative");
+    let plan = "operations:
+  - set: { scene: res://main.tscn, node: \"Panel/OK/..\", property: visible, value: \"false\" }
+  - repath: { from: ui/menu.tscn, to: res://ui/pause.tscn }
+";
+    fs::write(dir.join("plan.yaml"), plan).unwrap();
+    let out = stdout(&gdtree(&dir, &["apply", "plan.yaml"]));
+    assert_eq!(out, "res://main.tscn: Panel/OK/..: visible = false\nres://main.tscn: res://ui/menu.tscn -> res://ui/pause.tscn\nmade 2 edits\n");
+    let after = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    assert!(after.contains("[node name=\"Panel\" type=\"Control\" parent=\".\"]\nlayout_mode = 3\nvisible = false\n"), "{}", after);
+    assert!(after.contains("path=\"res://ui/pause.tscn\""), "{}", after);
+}
+
+#[test]
+fn animation_tracks() {
+    let dir = copy("apply", "apply-animation");
+    let scene = r#"[gd_scene load_steps=5 format=3]
+
+[sub_resource type="Animation" id="Animation_walk"]
+resource_name = "walk"
+tracks/0/type = "value"
+tracks/0/path = NodePath("Body/Sprite:position")
+tracks/1/type = "method"
+tracks/1/path = NodePath("Body")
+
+[sub_resource type="AnimationLibrary" id="AnimationLibrary_main"]
+_data = {
+"walk": SubResource("Animation_walk")
+}
+
+[sub_resource type="Animation" id="Animation_blink"]
+tracks/0/type = "value"
+tracks/0/path = NodePath("Body/Sprite:modulate")
+
+[sub_resource type="AnimationLibrary" id="AnimationLibrary_face"]
+_data = {
+"blink": SubResource("Animation_blink")
+}
+
+[node name="Player" type="Node2D"]
+
+[node name="Body" type="Node2D" parent="."]
+
+[node name="Sprite" type="Sprite2D" parent="Body"]
+
+[node name="Face" type="AnimationPlayer" parent="Body"]
+root_node = NodePath("../..")
+libraries = {
+"": SubResource("AnimationLibrary_face")
+}
+
+[node name="Animations" type="AnimationPlayer" parent="."]
+libraries = {
+"": SubResource("AnimationLibrary_main")
+}
+"#;
+    fs::write(dir.join("player.tscn"), scene).unwrap();
+    fs::write(dir.join("plan.yaml"), "operations:\n  - rename: { scene: player.tscn, node: Body, to: Torso }\n").unwrap();
+    let out = stdout(&gdtree(&dir, &["apply", "plan.yaml"]));
+    assert_eq!(out, "player.tscn: renamed Body to Torso (6 lines)\nmade 1 edits\n");
+    let after = fs::read_to_string(dir.join("player.tscn")).unwrap();
+    // the Animations player's root is its parent, and the Face player's the scene's root
+    assert_eq!(after, scene.replace("Body", "Torso"));
+}
+
+#[test]
+fn keeps_crlf() {
+    let dir = copy("apply", "apply-crlf");
+    let scene = fs::read_to_string(dir.join("main.tscn")).unwrap().replace('\n', "\r\n");
+    fs::write(dir.join("main.tscn"), &scene).unwrap();
+    fs::write(dir.join("plan.yaml"), "operations:\n  - rename: { scene: res://main.tscn, node: Other, to: Spare }\n").unwrap();
+    stdout(&gdtree(&dir, &["apply", "plan.yaml"]));
+    let after = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    assert_eq!(after, scene.replace("\"Other\"", "\"Spare\""));
+}
@@ -0,0 +1,49 @@
+use super::*;
+
+#[test]
+fn ascii() {
+    let out = stdout(&gdtree(&fixture("apply"), &["--ascii", "main.tscn"]));
+    assert_eq!(
+        out,
+        "Main
+|   * script: res://main.gd
+|   * target: NodePath(\"Panel/OK\")
+|   * connection: Panel/Cancel:pressed() => Main:_on_ok_pressed() [no node Panel/Cancel]
+|   * connection: Menu/Sound:toggled() => Main:_on_ok_pressed()
+|-- Panel (Control)
+|   |   * layout_mode: 3
+|   `-- OK (Button)
+|       |   * unique_name_in_owner: true
+|       |   * text: \"OK\"
+|       |   * connection: Panel/OK:pressed() => Main:_on_ok_pressed()
+|       |   * connection: Panel/OK:pressed() => Main:_on_gone() [no method in res://main.gd]
+|       `-- Icon (Sprite2D)
+|               * back: NodePath(\"..:text\")
+|-- Other (Node)
+|       * button: NodePath(\"%OK\")
+`-- Menu
+        * (PackedScene) res://ui/menu.tscn
+"
+    );
+}
+
+#[test]
+fn ascii_property_groups() {
+    let dir = temp("ascii-groups");
+    let scene = "[gd_scene format=3]
+
+[node name=\"Label\" type=\"Label\"]
+theme_override_colors/font_color = Color(1, 0, 0, 1)
+theme_override_colors/font_shadow_color = Color(0, 0, 0, 1)
+";
+    fs::write(dir.join("label.tscn"), scene).unwrap();
+    let out = stdout(&gdtree(&dir, &["--ascii", "label.tscn"]));
+    assert_eq!(
+        out,
+        "Label
+    * theme_override_colors
+                             |-- font_color: Color(1, 0, 0, 1)
+                             `-- font_shadow_color: Color(0, 0, 0, 1)
+"
+    );
+}
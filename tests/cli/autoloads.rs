@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn autoloads() {
+    let out = stdout(&gdtree(&fixture("autoloads"), &["autoloads"]));
+    assert_eq!(
+        out,
+        "Events (res://events.gd)
+├── died
+│   ├── connected in res://hud.tscn at Lives (res://hud.gd)
+│   └── emitted in res://player.tscn at . (res://player.gd)
+├── paused
+│   └── unused
+└── won
+    └── connected in res://hud.tscn at Lives (res://hud.gd)
+"
+    );
+}
+
+#[test]
+fn autoloads_need_a_project() {
+    let output = gdtree(&fixture("loose"), &["autoloads"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no project.godot found above ., so pass --project-root"));
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn failing_lint() {
+    let out = stdout(&gdtree(&fixture("lint"), &["badge", "menu.tscn"]));
+    assert_eq!(
+        out,
+        r#"{
+  "schemaVersion": 1,
+  "label": "menu.tscn",
+  "message": "3 nodes | lint 1 problem | complexity low",
+  "color": "red"
+}
+"#
+    );
+}
+
+#[test]
+fn built_in_checks_only() {
+    let out = stdout(&gdtree(&fixture("scenes"), &["badge", "menu.tscn"]));
+    assert!(out.contains(r#""message": "9 nodes | lint passing | complexity low","#), "{}", out);
+    assert!(out.contains(r#""color": "brightgreen""#), "{}", out);
+}
+
+#[test]
+fn complex() {
+    let dir = temp("badge");
+    let mut scene = "[gd_scene format=3]\n\n[node name=\"Crowd\" type=\"Node\"]\n".to_string();
+    for i in 0..120 {
+        scene += &format!("\n[node name=\"N{}\" type=\"Node\" parent=\".\"]\n", i);
+        scene += &format!("\n[connection signal=\"ready\" from=\"N{}\" to=\".\" method=\"_on_ready\"]\n", i);
+    }
+    fs::write(dir.join("crowd.tscn"), scene).unwrap();
+    let out = stdout(&gdtree(&dir, &["badge", "crowd.tscn"]));
+    assert!(out.contains(r#""message": "121 nodes | lint passing | complexity high","#), "{}", out);
+    assert!(out.contains(r#""color": "yellow""#), "{}", out);
+}
+
+#[test]
+fn summary() {
+    let out = stdout(&gdtree(&fixture("lint"), &["badge", "--summary", "title.tscn"]));
+    assert_eq!(
+        out,
+        r#"{
+  "scene": "res://title.tscn",
+  "nodes": 1,
+  "connections": 0,
+  "depth": 0,
+  "lint": {
+    "rules": 2,
+    "problems": 0,
+    "status": "passing"
+  },
+  "complexity": {
+    "score": 1,
+    "grade": "low"
+  }
+}
+"#
+    );
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn bookmarks() {
+    let dir = copy("reports", "bookmarks");
+    stdout(&gdtree(&dir, &["bookmark", "add", "actors/player.tscn", "hitbox", "Hitbox"]));
+    stdout(&gdtree(&dir, &["bookmark", "add", "main.tscn", "hud", "HUD"]));
+    assert_eq!(
+        fs::read_to_string(dir.join(".gdtree/bookmarks.toml")).unwrap(),
+        "[\"res://actors/player.tscn\"]\nhitbox = \"Hitbox\"\n\n[\"res://main.tscn\"]\nhud = \"HUD\"\n"
+    );
+
+    let out = stdout(&gdtree(&dir, &["--bookmark", "hitbox", "actors/player.tscn"]));
+    assert_eq!(out, "Hitbox\n└── Shape (CollisionShape2D)\n");
+    let out = stdout(&gdtree(&dir.join("actors"), &["--bookmark", "hitbox", "player.tscn"]));
+    assert_eq!(out, "Hitbox\n└── Shape (CollisionShape2D)\n");
+
+    let out = stdout(&gdtree(&dir, &["bookmark", "list"]));
+    assert_eq!(out, "res://actors/player.tscn  hitbox  Hitbox\nres://main.tscn  hud  HUD\n");
+    let out = stdout(&gdtree(&dir, &["bookmark", "list", "main.tscn"]));
+    assert_eq!(out, "res://main.tscn  hud  HUD\n");
+
+    stdout(&gdtree(&dir, &["bookmark", "remove", "main.tscn", "hud"]));
+    let out = stdout(&gdtree(&dir, &["bookmark", "list"]));
+    assert_eq!(out, "res://actors/player.tscn  hitbox  Hitbox\n");
+}
+
+#[test]
+fn unknown_bookmarks_and_nodes() {
+    let dir = copy("reports", "bookmarks-unknown");
+    let output = gdtree(&dir, &["bookmark", "add", "main.tscn", "nope", "Nope"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("main.tscn has no node at Nope"));
+
+    let output = gdtree(&dir, &["--bookmark", "nope", "main.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("main.tscn has no bookmark named nope; see gdtree bookmark list"));
+
+    let output = gdtree(&dir, &["bookmark", "remove", "main.tscn", "nope"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn bookmarked_node_gone() {
+    let dir = copy("reports", "bookmarks-gone");
+    stdout(&gdtree(&dir, &["bookmark", "add", "ui/hud.tscn", "blink", "Blink"]));
+    let hud = fs::read_to_string(dir.join("ui/hud.tscn")).unwrap();
+    fs::write(dir.join("ui/hud.tscn"), hud.replace("Blink", "Flash")).unwrap();
+    let output = gdtree(&dir, &["--bookmark", "blink", "ui/hud.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("the bookmarked node Blink is no longer in the scene"));
+}
+
+#[test]
+fn bookmarks_need_a_project() {
+    let output = gdtree(&fixture("loose"), &["bookmark", "list"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --project-root"));
+    let output = gdtree(&fixture("loose"), &["--bookmark", "hud", "hud.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+}
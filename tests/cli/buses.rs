@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn audio_buses() {
+    let output = gdtree(&fixture("buses"), &["audio-buses"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "unknown buses:
+  Ambience: res://level.tscn:Wind
+unused buses:
+  Voice
+4 buses in res://audio/buses.tres, 4 used by 4 players; 1 unknown, 1 unused
+"
+    );
+}
+
+#[test]
+fn default_layout() {
+    // without a saved layout there is only Master, which unset buses play on
+    let dir = temp("buses-default");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.join("click.tscn"), "[gd_scene format=3]\n\n[node name=\"Click\" type=\"AudioStreamPlayer\"]\n").unwrap();
+    let out = stdout(&gdtree(&dir, &["audio-buses"]));
+    assert_eq!(out, "1 buses in res://default_bus_layout.tres, 1 used by 1 players; 0 unknown, 0 unused\n");
+}
@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn well_formed() {
+    let output = gdtree(&fixture("check"), &["check", "good.tscn"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "0 problems in 0 of 1 files\n");
+}
+
+#[test]
+fn problems() {
+    let output = gdtree(&fixture("check"), &["check", "."]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "./bad.tscn:4: ExtResource id \"1_arena\" is declared twice
+./bad.tscn:7: ExtResource(\"2_gone\") refers to no ext_resource declared above it
+./bad.tscn:12: SubResource(\"RectangleShape2D_x7k\") refers to no sub_resource declared above it
+./bad.tscn:14: node Door has parent Gate, which is not declared above it
+./bad.tscn:16: node Wall is declared twice
+./bad.tscn:18: node Other has no parent, but Arena is already the root
+./bad.tscn:20: malformed: [node] lacks name
+./bad.tscn:22: malformed: unknown section [signal]
+./broken/unclosed.tscn:3: malformed: the node section header is never closed
+9 problems in 2 of 3 files
+"
+    );
+}
+
+#[test]
+fn unreadable_file() {
+    let dir = temp("check-unreadable");
+    fs::copy(fixture("check/good.tscn"), dir.join("good.tscn")).unwrap();
+    fs::write(dir.join("binary.tscn"), b"[gd_scene format=3]\n\xff\xfe\n").unwrap();
+    let output = gdtree(&dir, &["check", "."]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "./binary.tscn: could not be read: stream did not contain valid UTF-8\n1 problems in 1 of 2 files\n"
+    );
+}
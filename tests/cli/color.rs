@@ -0,0 +1,30 @@
+use super::*;
+
+#[test]
+fn color_always() {
+    let out = stdout(&gdtree(&fixture("moved"), &["--color", "always", "main.tscn"]));
+    assert_eq!(
+        out,
+        "\x1b[1;34mMain\x1b[0m
+├── \x1b[1;34mIcon\x1b[0m \x1b[36m(Sprite2D)\x1b[0m
+│       * texture: \x1b[32mres://art/icon.png\x1b[0m
+└── \x1b[1;34mHUD\x1b[0m
+        * \x1b[36m(PackedScene)\x1b[0m \x1b[32mres://ui/hud.tscn\x1b[0m
+"
+    );
+    let out = stdout(&gdtree(&fixture("diff"), &["--color", "always", "old.tscn"]));
+    assert!(out.contains("* \x1b[33mconnection: Timer:timeout() => Main:_on_timer()\x1b[0m\n"), "{}", out);
+}
+
+#[test]
+fn no_color_when_piped() {
+    let plain = stdout(&gdtree(&fixture("moved"), &["--color", "never", "main.tscn"]));
+    assert!(!plain.contains('\x1b'), "{}", plain);
+    assert_eq!(stdout(&gdtree(&fixture("moved"), &["main.tscn"])), plain);
+}
+
+#[test]
+fn only_the_tree_is_colored() {
+    let out = stdout(&gdtree(&fixture("moved"), &["--color", "always", "--format", "json", "main.tscn"]));
+    assert!(!out.contains('\x1b'), "{}", out);
+}
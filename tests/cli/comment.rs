@@ -0,0 +1,28 @@
+use super::*;
+
+#[test]
+fn gdscript() {
+    let out = stdout(&gdtree(&fixture("describe"), &["--format", "comment", "hud.tscn"]));
+    assert_eq!(out, "# HUD\n# ├── Score (Label)\n# │       * text: \"0\"\n# └── Timer\n");
+}
+
+#[test]
+fn rust() {
+    let out = stdout(&gdtree(&fixture("describe"), &["--format", "comment", "--comment-style", "rust", "hud.tscn"]));
+    assert_eq!(out, "// HUD\n// ├── Score (Label)\n// │       * text: \"0\"\n// └── Timer\n");
+}
+
+#[test]
+fn c_block() {
+    let dir = temp("comment");
+    fs::write(dir.join("note.tscn"), "[gd_scene format=3]\n\n[node name=\"Note\" type=\"Label\"]\ntext = \"a */ b\"\n").unwrap();
+    let out = stdout(&gdtree(&dir, &["--format", "comment", "--comment-style", "c", "note.tscn"]));
+    assert_eq!(out, "/*\n * Note\n *     * text: \"a * / b\"\n */\n");
+}
+
+#[test]
+fn unknown_format() {
+    let output = gdtree(&fixture("describe"), &["--format", "nope", "hud.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown format 'nope'; use tree, comment, matrix, json, dot, mermaid, html or load a plugin"));
+}
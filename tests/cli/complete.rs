@@ -0,0 +1,19 @@
+use super::*;
+
+#[test]
+fn every_path() {
+    let out = stdout(&gdtree(&fixture("scenes"), &["complete-path", "menu.tscn"]));
+    assert_eq!(out, "HealthBar\nButtons\nButtons/Play\nButtons/Label\nFooter\nFooter/Label\nHTTPRequest\n2 Lives\n");
+}
+
+#[test]
+fn paths_with_a_prefix() {
+    let out = stdout(&gdtree(&fixture("scenes"), &["complete-path", "menu.tscn", "Bu"]));
+    assert_eq!(out, "Buttons\nButtons/Play\nButtons/Label\n");
+}
+
+#[test]
+fn unique_names() {
+    assert_eq!(stdout(&gdtree(&fixture("scenes"), &["complete-path", "menu.tscn", "%"])), "%Play\n");
+    assert_eq!(stdout(&gdtree(&fixture("scenes"), &["complete-path", "menu.tscn", "%Q"])), "");
+}
@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn explain() {
+    let output = gdtree(&fixture("conflicts"), &["conflicts", "player.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "conflict 1 of 3, lines 1-5, at the top of the file:
+  only load_steps differs; Godot counts it again on saving, so either side will do
+conflict 2 of 3, lines 8-11, after [ext_resource type=\"Script\" path=\"res://player.gd\" id=\"1_script\"]:
+  feature | [ext_resource type=\"Texture2D\" path=\"res://player.png\" id=\"2_tex\"]
+conflict 3 of 3, lines 15-22, in the root node Player:
+  from HEAD (ours) to feature (theirs):
+  - .: scale: Vector2(2, 2)
+  ~ .: position: Vector2(10, 20) → Vector2(150, 250)
+"
+    );
+}
+
+#[test]
+fn no_conflicts() {
+    let output = gdtree(&fixture("apply"), &["conflicts", "main.tscn"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "no conflicts in main.tscn\n");
+}
+
+#[test]
+fn unclosed() {
+    let output = gdtree(&fixture("conflicts"), &["conflicts", "unclosed.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "gdtree: unclosed.tscn: the conflict starting on line 4 is never closed\n");
+}
@@ -0,0 +1,134 @@
+use super::*;
+
+#[test]
+fn flags_as_tags() {
+    let out = stdout(&gdtree(&fixture("connections"), &["door.tscn"]));
+    assert_eq!(
+        out,
+        "Door
+│   * connection: .:body_entered() => Door:_on_body_entered() [deferred]
+│   * connection: .:body_exited() => Sound:stop() [reference counted]
+├── Timer
+│       * connection: Timer:timeout() => Door:_on_timeout() [deferred] [oneshot]
+└── Sound (AudioStreamPlayer2D)
+        * connection: Sound:finished() => Door:_on_finished()
+"
+    );
+}
+
+#[test]
+fn filtered_by_flags() {
+    let out = stdout(&gdtree(&fixture("connections"), &["--connection-flags", "deferred", "door.tscn"]));
+    assert!(out.contains("_on_body_entered() [deferred]\n"), "{}", out);
+    assert!(out.contains("_on_timeout() [deferred] [oneshot]\n"), "{}", out);
+    assert!(!out.contains("stop()") && !out.contains("_on_finished()"), "{}", out);
+
+    let out = stdout(&gdtree(&fixture("connections"), &["--connection-flags", "deferred,oneshot", "door.tscn"]));
+    assert_eq!(out.matches("* connection:").count(), 1, "{}", out);
+}
+
+#[test]
+fn lint_rules_see_flags() {
+    let output = gdtree(&fixture("connections"), &["lint", "--rules", "rules", "door.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stdout).starts_with("door.tscn: Timer: oneshot connection to _on_timeout [oneshot]\n"));
+}
+
+#[test]
+fn matrix() {
+    let out = stdout(&gdtree(&fixture("connections"), &["--format", "matrix", "door.tscn"]));
+    assert_eq!(
+        out,
+        "       Door          Sound
+Door   body_entered  body_exited
+Timer  timeout       -
+Sound  finished      -
+"
+    );
+    let out = stdout(&gdtree(&fixture("connections"), &["--format", "matrix", "--connection-flags", "deferred", "door.tscn"]));
+    assert_eq!(out, "       Door\nDoor   body_entered\nTimer  timeout\n");
+}
+
+#[test]
+fn matrix_csv() {
+    let out = stdout(&gdtree(&fixture("connections"), &["--format", "matrix", "--matrix-style", "csv", "door.tscn"]));
+    assert_eq!(out, ",Door,Sound\nDoor,body_entered,body_exited\nTimer,timeout,\nSound,finished,\n");
+
+    let dir = temp("matrix-csv");
+    fs::write(
+        dir.join("button.tscn"),
+        "[gd_scene format=3]
+
+[node name=\"Button\" type=\"Button\"]
+
+[connection signal=\"pressed\" from=\".\" to=\".\" method=\"_on_pressed\"]
+[connection signal=\"toggled\" from=\".\" to=\".\" method=\"_on_toggled\"]
+",
+    )
+    .unwrap();
+    let out = stdout(&gdtree(&dir, &["--format", "matrix", "--matrix-style", "csv", "button.tscn"]));
+    assert_eq!(out, ",Button\nButton,\"pressed, toggled\"\n");
+}
+
+#[test]
+fn connections_only() {
+    let out = stdout(&gdtree(&fixture("connections"), &["--connections-only", "door.tscn"]));
+    assert_eq!(
+        out,
+        ".:body_entered() => Door:_on_body_entered() [deferred]
+.:body_exited() => Sound:stop() [reference counted]
+Sound:finished() => Door:_on_finished()
+Timer:timeout() => Door:_on_timeout() [deferred] [oneshot]
+"
+    );
+    let out = stdout(&gdtree(&fixture("connections"), &["--connections-only", "--connection-flags", "deferred", "door.tscn"]));
+    assert_eq!(out, ".:body_entered() => Door:_on_body_entered() [deferred]\nTimer:timeout() => Door:_on_timeout() [deferred] [oneshot]\n");
+}
+
+#[test]
+fn connections_only_sorted_by_method() {
+    let out = stdout(&gdtree(&fixture("apply"), &["--connections-only", "main.tscn"]));
+    assert_eq!(
+        out,
+        "Menu/Sound:toggled() => Main:_on_ok_pressed()
+Panel/Cancel:pressed() => Main:_on_ok_pressed() [no node Panel/Cancel]
+Panel/OK:pressed() => Main:_on_gone() [no method in res://main.gd]
+Panel/OK:pressed() => Main:_on_ok_pressed()
+"
+    );
+}
+
+#[test]
+fn missing_nodes_are_linted() {
+    let output = gdtree(&fixture("apply"), &["lint", "."]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(
+        String::from_utf8_lossy(&output.stdout).contains("res://main.tscn: .: connection of pressed to _on_ok_pressed() leads to Panel/Cancel, which is not in the scene [connections]\n"),
+        "{:?}",
+        output
+    );
+}
+
+#[test]
+fn missing_methods() {
+    let out = stdout(&gdtree(&fixture("methods"), &["--connections-only", "door.tscn"]));
+    assert_eq!(
+        out,
+        "Timer:timeout() => Door:_on_closed() [no method in res://door.gd]
+Timer:timeout() => Door:_on_locked()
+Timer:timeout() => Door:_on_opened()
+Timer:timeout() => Door:queue_free()
+Timer:timeout() => Gate:_on_opened()
+Timer:timeout() => Gate:_on_shut() [no method in res://gate.gd]
+"
+    );
+    let output = gdtree(&fixture("methods"), &["lint", "."]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "res://door.tscn: Timer: connection of timeout calls _on_closed() on Door, but res://door.gd has no such function [connections]
+res://door.tscn: Timer: connection of timeout calls _on_shut() on Gate, but res://gate.gd has no such function [connections]
+2 problems in 1 scenes checked by 1 rules
+"
+    );
+}
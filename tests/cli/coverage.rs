@@ -0,0 +1,17 @@
+use super::*;
+
+#[test]
+fn script_coverage() {
+    let out = stdout(&gdtree(&fixture("files"), &["--script-coverage", "main.tscn"]));
+    assert_eq!(
+        out,
+        "Main: 1 of 2 nodes have scripts (50.0%)
+
+AudioStreamPlayer (0/1, 0.0%)
+  - Music
+
+Node (1/1, 100.0%)
+  + Main
+"
+    );
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+#[test]
+fn deprecated() {
+    let output = gdtree(&fixture("godot3"), &["deprecated", "."]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "./level.tscn
+  . (Spatial): renamed to Node3D
+  Player (KinematicBody): renamed to CharacterBody3D
+  Player/Camera (Camera): renamed to Camera3D
+  Player/Lens (ClippedCamera): removed, use SpringArm3D with a Camera3D child
+4 deprecated nodes in 1 of 2 scenes
+"
+    );
+}
+
+#[test]
+fn nothing_deprecated() {
+    let out = stdout(&gdtree(&fixture("godot3"), &["deprecated", "hud.tscn"]));
+    assert_eq!(out, "0 deprecated nodes in 0 of 1 scenes\n");
+}
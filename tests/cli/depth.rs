@@ -0,0 +1,25 @@
+use super::*;
+
+#[test]
+fn depth() {
+    let out = stdout(&gdtree(&fixture("reports"), &["-L", "1", "actors/player.tscn"]));
+    assert_eq!(
+        out,
+        "Player
+│   * script: res://actors/player.gd
+├── Sprite (Sprite2D)
+├── Hitbox (Area2D)
+└── Cooldown (Timer)
+
+1 nodes below depth 1 hidden
+"
+    );
+    let out = stdout(&gdtree(&fixture("reports"), &["--depth", "0", "actors/player.tscn"]));
+    assert_eq!(out, "Player\n    * script: res://actors/player.gd\n\n4 nodes below depth 0 hidden\n");
+}
+
+#[test]
+fn deep_enough() {
+    let out = stdout(&gdtree(&fixture("reports"), &["-L", "2", "actors/player.tscn"]));
+    assert_eq!(out, stdout(&gdtree(&fixture("reports"), &["actors/player.tscn"])));
+}
@@ -0,0 +1,94 @@
+use super::*;
+
+/// Runs gdtree with `cache` as its cache directory.
+fn cached(cache: &Path, dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_gdtree"))
+        .args(args)
+        .current_dir(dir)
+        .env("XDG_CACHE_HOME", cache)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn describe() {
+    let out = stdout(&gdtree(&fixture("describe"), &["--describe", "hud.tscn"]));
+    assert_eq!(
+        out,
+        "HUD — Base class for all GUI controls. Adapts its position and size based on its parent control.
+├── Score (Label) — A control for displaying plain text, unlike RichTextLabel & bbcode.
+│       * text: \"0\"
+└── Timer
+"
+    );
+}
+
+#[test]
+fn describe_needs_the_docs() {
+    let output = gdtree(&fixture("scenes"), &["--describe", "menu.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("docs = \"path/to/doc/classes\" in .gdtree/config.toml"));
+}
+
+#[test]
+fn inheritance() {
+    let out = stdout(&gdtree(&fixture("describe"), &["--inheritance", "hud.tscn"]));
+    assert_eq!(
+        out,
+        "HUD (Control < CanvasItem < Node < Object)
+├── Score (Label < Control < CanvasItem < Node < Object)
+│       * text: \"0\"
+└── Timer (Timer)
+"
+    );
+}
+
+#[test]
+fn check_properties() {
+    let out = stdout(&gdtree(&fixture("describe"), &["--check-properties", "props.tscn"]));
+    assert_eq!(
+        out,
+        "Score
+    * text: \"0\"
+    * uppercase: false [default]
+    * modulate: Color( 1, 1, 1.0, 1 ) [default]
+    * visible: false
+    * font_colour: Color(1, 0, 0, 1) [unknown property]
+"
+    );
+}
+
+#[test]
+fn classdb_fetch_list_remove() {
+    let dir = temp("classdb");
+    let classes = dir.join("godot-4.2-stable/doc/classes");
+    fs::create_dir_all(&classes).unwrap();
+    for class in ["Node", "CanvasItem", "Control", "Label"] {
+        fs::copy(fixture(&format!("describe/docs/{}.xml", class)), classes.join(format!("{}.xml", class))).unwrap();
+    }
+    let status = Command::new("tar")
+        .args(["-czf", "godot.tar.gz", "godot-4.2-stable"])
+        .current_dir(&dir)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let cache = dir.join("cache");
+
+    let out = stdout(&cached(&cache, &dir, &["classdb", "fetch", "4.2", "--source", "godot.tar.gz"]));
+    assert_eq!(out, "cached 4 classes for Godot 4.2\n");
+    assert_eq!(stdout(&cached(&cache, &dir, &["classdb", "list"])), "4.2\n");
+
+    let project = dir.join("project");
+    fs::create_dir_all(project.join(".gdtree")).unwrap();
+    fs::write(project.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(project.join(".gdtree/config.toml"), "godot = \"4.2\"\n").unwrap();
+    fs::copy(fixture("describe/hud.tscn"), project.join("hud.tscn")).unwrap();
+    let out = stdout(&cached(&cache, &project, &["--describe", "hud.tscn"]));
+    assert!(out.starts_with("HUD — Base class for all GUI controls."), "{}", out);
+
+    stdout(&cached(&cache, &dir, &["classdb", "remove", "4.2"]));
+    assert_eq!(stdout(&cached(&cache, &dir, &["classdb", "list"])), "");
+    let output = cached(&cache, &project, &["--describe", "hud.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no class reference cached for Godot 4.2; run gdtree classdb fetch 4.2"));
+}
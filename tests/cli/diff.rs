@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn diff() {
+    let output = gdtree(&fixture("diff"), &["diff", "old.tscn", "new.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "> Enemies/Boss (CharacterBody2D) → Arena/Boss
++ Arena (Node2D)
+~ Arena/Boss: speed: 10 → 20
+~ Timer: type Timer → Node
+- connection: Timer:timeout() => Main:_on_timer()
+"
+    );
+}
+
+#[test]
+fn diff_summary() {
+    let output = gdtree(&fixture("diff"), &["diff", "old.tscn", "new.tscn", "--summary"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "Added 1 node under the root, moved 1 node, changed the type of Timer, changed 1 property on Arena/Boss, removed 1 connection\n"
+    );
+}
+
+#[test]
+fn no_changes() {
+    assert_eq!(stdout(&gdtree(&fixture("diff"), &["diff", "old.tscn", "old.tscn"])), "");
+    assert_eq!(stdout(&gdtree(&fixture("diff"), &["diff", "old.tscn", "old.tscn", "--summary"])), "No changes\n");
+}
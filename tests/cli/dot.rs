@@ -0,0 +1,64 @@
+use super::*;
+
+#[test]
+fn dot() {
+    let out = stdout(&gdtree(&fixture("connections"), &["--format", "dot", "door.tscn"]));
+    assert_eq!(
+        out,
+        r#"digraph "Door" {
+    node [shape=box];
+    "." [label="Door\n(Area2D)"];
+    "Timer" [label="Timer\n(Timer)"];
+    "Sound" [label="Sound\n(AudioStreamPlayer2D)"];
+    "." -> "Timer";
+    "." -> "Sound";
+    "." -> "." [style=dashed, color=blue, fontcolor=blue, label="body_entered → _on_body_entered() [deferred]"];
+    "." -> "Sound" [style=dashed, color=blue, fontcolor=blue, label="body_exited → stop() [reference counted]"];
+    "Timer" -> "." [style=dashed, color=blue, fontcolor=blue, label="timeout → _on_timeout() [deferred] [oneshot]"];
+    "Sound" -> "." [style=dashed, color=blue, fontcolor=blue, label="finished → _on_finished()"];
+}
+"#
+    );
+
+    let out = stdout(&gdtree(&fixture("connections"), &["--format", "dot", "--connection-flags", "oneshot", "door.tscn"]));
+    assert_eq!(out.matches("style=dashed").count(), 1, "{}", out);
+}
+
+#[test]
+fn instances() {
+    let out = stdout(&gdtree(&fixture("editable"), &["--format", "dot", "level.tscn"]));
+    assert!(out.contains("    \"Enemy\" [label=\"Enemy\\nres://enemy.tscn\"];\n"), "{}", out);
+    assert!(out.contains("    \"Enemy/Sprite\" [label=\"Sprite\"];\n"), "{}", out);
+    assert!(out.contains("    \"Boss/Sprite\" -> \"Boss/Sprite/Glow\";\n"), "{}", out);
+}
+
+#[test]
+fn dependencies() {
+    let out = stdout(&gdtree(&fixture("reports"), &["deps", "--format", "dot", "."]));
+    assert_eq!(
+        out,
+        r#"digraph dependencies {
+    node [shape=box];
+    "res://arena.tscn" [label="arena.tscn"];
+    "res://main.tscn" [label="main.tscn"];
+    subgraph cluster_1 {
+        label="res://actors/";
+        "res://actors/player.tscn" [label="player.tscn"];
+        "res://actors/player.gd" [label="player.gd"];
+    }
+    subgraph cluster_2 {
+        label="res://ui/";
+        "res://ui/hud.tscn" [label="hud.tscn"];
+    }
+    "res://actors/player.tscn" -> "res://actors/player.gd";
+    "res://arena.tscn" -> "res://actors/player.tscn";
+    "res://main.tscn" -> "res://actors/player.tscn";
+    "res://main.tscn" -> "res://ui/hud.tscn";
+}
+"#
+    );
+
+    let out = stdout(&gdtree(&fixture("reports"), &["deps", "--format", "dot", "--kind", "scenes", "."]));
+    assert!(!out.contains("player.gd"), "{}", out);
+    assert_eq!(out.matches(" -> ").count(), 3, "{}", out);
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn dropped_nodes() {
+    let out = stdout(&gdtree(&fixture("editable"), &["level.tscn"]));
+    assert_eq!(
+        out,
+        "Level
+├── Enemy
+│   │   * (PackedScene) res://enemy.tscn
+│   ├── Health (Node)
+│   └── Sprite
+│       │   * modulate: Color(1, 0, 0, 1)
+│       └── Glow (PointLight2D) [dropped on load: instance children not editable]
+└── Boss
+    │   * (PackedScene) res://enemy.tscn
+    └── Sprite
+        └── Glow (PointLight2D)
+"
+    );
+}
+
+#[test]
+fn lint_rules_see_dropped_nodes() {
+    let output = gdtree(&fixture("editable"), &["lint", "--rules", "rules", "level.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "level.tscn: Enemy/Sprite/Glow: dropped [dropped]\n1 problems in 1 scenes checked by 2 rules\n"
+    );
+}
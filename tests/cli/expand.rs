@@ -0,0 +1,71 @@
+use super::*;
+
+#[test]
+fn expand_instances() {
+    let out = stdout(&gdtree(&fixture("reports"), &["--expand-instances", "main.tscn"]));
+    assert_eq!(
+        out,
+        "Main
+├── Player (CharacterBody2D)
+│   │   * (PackedScene) res://actors/player.tscn
+│   │   * script: res://actors/player.gd
+│   ├── Sprite (Sprite2D)
+│   ├── Hitbox (Area2D)
+│   │   └── Shape (CollisionShape2D)
+│   └── Cooldown (Timer)
+└── HUD (CanvasLayer)
+    │   * (PackedScene) res://ui/hud.tscn
+    └── Blink (Timer)
+"
+    );
+    assert_eq!(out, stdout(&gdtree(&fixture("reports"), &["--recursive", "main.tscn"])));
+}
+
+#[test]
+fn overrides() {
+    let dir = temp("expand-overrides");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    let enemy = "[gd_scene format=3]
+
+[node name=\"Enemy\" type=\"Node2D\"]
+speed = 10
+health = 3
+
+[node name=\"Sprite\" type=\"Sprite2D\" parent=\".\"]
+modulate = Color(1, 1, 1, 1)
+";
+    let level = "[gd_scene load_steps=3 format=3]
+
+[ext_resource type=\"PackedScene\" path=\"res://enemy.tscn\" id=\"1\"]
+[ext_resource type=\"PackedScene\" path=\"res://level.tscn\" id=\"2\"]
+
+[node name=\"Level\" type=\"Node\"]
+
+[node name=\"Boss\" parent=\".\" instance=ExtResource(\"1\")]
+health = 30
+
+[node name=\"Crown\" type=\"Sprite2D\" parent=\"Boss\"]
+
+[node name=\"Sprite\" parent=\"Boss\"]
+modulate = Color(1, 0, 0, 1)
+
+[node name=\"Again\" parent=\".\" instance=ExtResource(\"2\")]
+";
+    fs::write(dir.join("enemy.tscn"), enemy).unwrap();
+    fs::write(dir.join("level.tscn"), level).unwrap();
+    let out = stdout(&gdtree(&dir, &["--expand-instances", "level.tscn"]));
+    assert_eq!(
+        out,
+        "Level
+├── Boss (Node2D)
+│   │   * (PackedScene) res://enemy.tscn
+│   │   * speed: 10
+│   │   * health: 30
+│   ├── Sprite (Sprite2D)
+│   │       * modulate: Color(1, 0, 0, 1)
+│   └── Crown (Sprite2D)
+└── Again
+        * (PackedScene) res://level.tscn
+"
+    );
+}
@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn expect() {
+    let out = stdout(&gdtree(&fixture("reports"), &["expect", "actors/player.tscn"]));
+    assert_eq!(
+        out,
+        "# gdtree expect res://actors/player.tscn
+. (CharacterBody2D)
+Sprite (Sprite2D)
+Hitbox (Area2D)
+Hitbox/Shape (CollisionShape2D)
+Cooldown (Timer)
+"
+    );
+}
+
+#[test]
+fn verify_expect() {
+    let dir = copy("reports", "verify-expect");
+    fs::create_dir_all(dir.join("tests")).unwrap();
+    stdout(&gdtree(&dir, &["expect", "actors/player.tscn", "-o", "tests/player.txt"]));
+    stdout(&gdtree(&dir, &["verify-expect", "tests/player.txt"]));
+
+    let scene = fs::read_to_string(dir.join("actors/player.tscn")).unwrap();
+    let scene = scene.replace("name=\"Cooldown\" type=\"Timer\"", "name=\"Cooldown\" type=\"Node\"").replace("[node name=\"Shape\"", "[node name=\"Body\"");
+    fs::write(dir.join("actors/player.tscn"), scene).unwrap();
+    // lines may be deleted, and comments added
+    let expected = fs::read_to_string(dir.join("tests/player.txt")).unwrap().replace("Sprite (Sprite2D)\n", "# the sprite is optional\n");
+    fs::write(dir.join("tests/player.txt"), expected).unwrap();
+    let output = gdtree(&dir.join("tests"), &["verify-expect", "player.txt"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "res://actors/player.tscn: missing Hitbox/Shape (CollisionShape2D)\nres://actors/player.tscn: Cooldown is now a Node, not a Timer\n"
+    );
+}
+
+#[test]
+fn not_an_expectations_file() {
+    let output = gdtree(&fixture("reports"), &["verify-expect", "main.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "gdtree: main.tscn: the first line should be \"# gdtree expect <scene>\"; write it with gdtree expect\n"
+    );
+}
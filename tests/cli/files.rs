@@ -0,0 +1,16 @@
+use super::*;
+
+#[test]
+fn check_files() {
+    let out = stdout(&gdtree(&fixture("files"), &["--check-files", "main.tscn"]));
+    assert!(out.contains("* script: res://main.gd [13 bytes, modified "), "{}", out);
+    assert!(out.contains(" UTC]\n"), "{}", out);
+    assert!(out.contains("* stream: res://music.ogg [missing]\n"), "{}", out);
+}
+
+#[test]
+fn check_files_needs_a_project() {
+    let output = gdtree(&fixture("loose"), &["--check-files", "hud.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--check-files needs a project root"));
+}
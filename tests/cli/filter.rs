@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn filter() {
+    let out = stdout(&gdtree(&fixture("reports"), &["--filter", "S*", "actors/player.tscn"]));
+    assert_eq!(
+        out,
+        "Player
+│   * script: res://actors/player.gd
+├── Sprite (Sprite2D)
+└── Hitbox (Area2D)
+    └── Shape (CollisionShape2D)
+"
+    );
+    let out = stdout(&gdtree(&fixture("reports"), &["--filter", "Nope", "actors/player.tscn"]));
+    assert_eq!(out, "Player\n    * script: res://actors/player.gd\n");
+}
+
+#[test]
+fn regex() {
+    let out = stdout(&gdtree(&fixture("reports"), &["--regex", "^Co", "actors/player.tscn"]));
+    assert_eq!(out, "Player\n│   * script: res://actors/player.gd\n└── Cooldown (Timer)\n");
+
+    for args in [&["--regex", "("][..], &["--regex", "a", "--filter", "b"][..]] {
+        let output = gdtree(&fixture("reports"), &[args, &["actors/player.tscn"]].concat());
+        assert_eq!(output.status.code(), Some(2));
+    }
+}
+
+#[test]
+fn nodes_of_an_instance() {
+    // Frame belongs to the instanced scene and is only named as a parent here
+    let dir = temp("filter-instance");
+    fs::write(
+        dir.join("arena.tscn"),
+        "[gd_scene load_steps=2 format=3]\n\n[ext_resource type=\"PackedScene\" path=\"res://door.tscn\" id=\"1\"]\n\n[node name=\"Arena\" type=\"Node2D\"]\n\n[node name=\"Door\" parent=\".\" instance=ExtResource(\"1\")]\n\n[node name=\"Hinge\" type=\"Node2D\" parent=\"Door/Frame\"]\n",
+    )
+    .unwrap();
+    let out = stdout(&gdtree(&dir, &["--filter", "Frame", "arena.tscn"]));
+    assert_eq!(out, "Arena\n└── Door\n    │   * (PackedScene) res://door.tscn\n    └── Frame\n");
+}
@@ -0,0 +1,87 @@
+use super::*;
+
+/// A font holding just a name table, with the given legacy and typographic families.
+fn font(family: &str, typographic: Option<&str>) -> Vec<u8> {
+    let names: Vec<(u16, Vec<u8>)> = [(1, Some(family)), (16, typographic)]
+        .into_iter()
+        .filter_map(|(id, name)| Some((id, name?.encode_utf16().flat_map(u16::to_be_bytes).collect())))
+        .collect();
+    let mut table = Vec::new();
+    table.extend(0u16.to_be_bytes());
+    table.extend((names.len() as u16).to_be_bytes());
+    table.extend((6 + names.len() as u16 * 12).to_be_bytes());
+    let mut strings: Vec<u8> = Vec::new();
+    for (id, name) in names.iter() {
+        for field in [3, 1, 0x409, *id, name.len() as u16, strings.len() as u16] {
+            table.extend(field.to_be_bytes());
+        }
+        strings.extend(name);
+    }
+    table.extend(strings);
+
+    let mut font = vec![0, 1, 0, 0, 0, 1, 0, 16, 0, 0, 0, 0];
+    font.extend(b"name\0\0\0\0");
+    font.extend(28u32.to_be_bytes());
+    font.extend((table.len() as u32).to_be_bytes());
+    font.extend(table);
+    font
+}
+
+#[test]
+fn fonts() {
+    let dir = temp("fonts");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.join("body.ttf"), font("Inter", None)).unwrap();
+    fs::write(dir.join("heading.otf"), font("Inter Display Bold", Some("Inter Display"))).unwrap();
+    fs::write(dir.join("emoji.ttf"), [0; 4]).unwrap();
+    fs::write(
+        dir.join("menu.tscn"),
+        "[gd_scene load_steps=6 format=3]
+
+[ext_resource type=\"FontFile\" path=\"res://body.ttf\" id=\"1_body\"]
+[ext_resource type=\"FontFile\" path=\"res://heading.otf\" id=\"2_heading\"]
+[ext_resource type=\"FontFile\" path=\"res://emoji.ttf\" id=\"3_emoji\"]
+
+[sub_resource type=\"FontVariation\" id=\"FontVariation_title\"]
+base_font = ExtResource(\"2_heading\")
+fallbacks = Array[Font]([ExtResource(\"1_body\"), ExtResource(\"3_emoji\")])
+variation_embolden = 0.5
+spacing_glyph = 2
+
+[sub_resource type=\"SystemFont\" id=\"SystemFont_mono\"]
+font_names = PackedStringArray(\"Menlo\", \"Consolas\")
+font_italic = true
+
+[node name=\"Menu\" type=\"Control\"]
+
+[node name=\"Title\" type=\"Label\" parent=\".\"]
+theme_override_fonts/font = SubResource(\"FontVariation_title\")
+theme_override_font_sizes/font_size = 48
+
+[node name=\"Code\" type=\"Label\" parent=\".\"]
+theme_override_fonts/font = SubResource(\"SystemFont_mono\")
+
+[node name=\"Body\" type=\"Label\" parent=\".\"]
+theme_override_fonts/font = ExtResource(\"1_body\")
+",
+    )
+    .unwrap();
+
+    let out = stdout(&gdtree(&dir, &["menu.tscn"]));
+    assert_eq!(
+        out,
+        "Menu
+├── Title (Label)
+│       * theme_override_fonts
+│                               └── font: FontVariation Inter Display, embolden 0.5, glyph spacing 2, fallbacks: Inter, res://emoji.ttf
+│       * theme_override_font_sizes
+│                                    └── font_size: 48
+├── Code (Label)
+│       * theme_override_fonts
+│                               └── font: SystemFont Menlo, Consolas, italic
+└── Body (Label)
+        * theme_override_fonts
+                                └── font: res://body.ttf (Inter)
+"
+    );
+}
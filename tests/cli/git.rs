@@ -0,0 +1,131 @@
+use super::*;
+
+const V1: &str = "[gd_scene format=3]\n\n[node name=\"Level\" type=\"Node2D\"]\n";
+
+/// An empty git repository of the test's own.
+fn repo(test: &str) -> PathBuf {
+    let dir = temp(test);
+    assert!(Command::new("git").args(["init", "-q"]).current_dir(&dir).status().unwrap().success());
+    dir
+}
+
+/// Commits everything in `dir` as `author` on `date`.
+fn commit(dir: &Path, author: &str, date: &str, subject: &str) {
+    for args in [&["add", "-A"][..], &["commit", "-q", "-m", subject]] {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", author)
+            .env("GIT_AUTHOR_EMAIL", "dev@example.com")
+            .env("GIT_AUTHOR_DATE", format!("{}T12:00:00Z", date))
+            .env("GIT_COMMITTER_NAME", author)
+            .env("GIT_COMMITTER_EMAIL", "dev@example.com")
+            .env("GIT_COMMITTER_DATE", format!("{}T12:00:00Z", date))
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+}
+
+/// The abbreviated hash of the commit with this subject.
+fn hash(dir: &Path, subject: &str) -> String {
+    let output = Command::new("git").args(["log", "--format=%H", "--grep", subject]).current_dir(dir).output().unwrap();
+    String::from_utf8(output.stdout).unwrap()[..8].to_string()
+}
+
+#[test]
+fn blame() {
+    let dir = repo("blame");
+    fs::write(dir.join("old.tscn"), V1).unwrap();
+    commit(&dir, "Ada", "2024-03-01", "Add level");
+    let enemy = "\n[node name=\"Enemy\" type=\"Sprite2D\" parent=\".\"]\n";
+    fs::write(dir.join("old.tscn"), format!("{}{}position = Vector2(1, 1)\nscale = Vector2(2, 2)\n", V1, enemy)).unwrap();
+    commit(&dir, "Ada", "2024-03-02", "Add enemy");
+    // renamed since, which the history follows
+    fs::remove_file(dir.join("old.tscn")).unwrap();
+    fs::write(dir.join("level.tscn"), format!("{}{}position = Vector2(5, 5)\nscale = Vector2(2, 2)\n", V1, enemy)).unwrap();
+    commit(&dir, "Bo", "2024-03-09", "Move enemy");
+    fs::write(dir.join("level.tscn"), format!("{}{}position = Vector2(5, 5)\nscale = Vector2(2, 2)\nrotation = 1.5\ntooltip = \"first\nsecond\"\n", V1, enemy)).unwrap();
+
+    let out = stdout(&gdtree(&dir, &["blame", "level.tscn", "Enemy"]));
+    assert_eq!(
+        out,
+        format!(
+            "Enemy (Sprite2D)
+  added: {}  2024-03-02  Ada  Add enemy
+  position = Vector2(5, 5): {}  2024-03-09  Bo  Move enemy
+  scale = Vector2(2, 2): {}  2024-03-02  Ada  Add enemy
+  rotation = 1.5: not committed yet
+  tooltip = \"first … (1 more lines): not committed yet
+",
+            hash(&dir, "Add enemy"),
+            hash(&dir, "Move enemy"),
+            hash(&dir, "Add enemy")
+        )
+    );
+}
+
+#[test]
+fn no_such_node() {
+    let output = gdtree(&fixture("scenes"), &["blame", "menu.tscn", "Nope"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("menu.tscn has no node Nope"));
+}
+
+/// A scene of a root with `n` Timers under it.
+fn timers(n: usize) -> String {
+    let mut scene = V1.to_string();
+    for i in 0..n {
+        scene += &format!("\n[node name=\"T{}\" type=\"Timer\" parent=\".\"]\nwait_time = 2.0\n", i);
+    }
+    scene
+}
+
+#[test]
+fn history() {
+    let dir = repo("history");
+    fs::write(dir.join("level.tscn"), V1).unwrap();
+    commit(&dir, "Ada", "2024-01-08", "Add level");
+    fs::write(dir.join("level.tscn"), timers(40)).unwrap();
+    commit(&dir, "Bo", "2024-02-19", "Lots of timers");
+    fs::write(dir.join("level.tscn"), "[gd_scene format=3]\n\n[node name=\"Level\"").unwrap();
+    commit(&dir, "Bo", "2024-02-20", "Break it");
+    fs::write(dir.join("level.tscn"), timers(2)).unwrap();
+    commit(&dir, "Ada", "2024-03-01", "Fewer timers");
+
+    let out = stdout(&gdtree(&dir, &["history", "level.tscn"]));
+    assert_eq!(
+        out,
+        format!(
+            "date        commit     nodes  depth   props   sub  conns        size  subject
+2024-01-08  {}       1      0       0     0      0    55 bytes  Add level
+2024-02-19  {}      41      1      40     0      0     2.3 KiB  Lots of timers
+2024-02-20  {}       -      -       -     -      -    39 bytes  Break it (failed to parse)
+2024-03-01  {}       3      1       2     0      0   171 bytes  Fewer timers
+largest growth: +40 nodes by {} 2024-02-19 (Lots of timers)
+",
+            hash(&dir, "Add level"),
+            hash(&dir, "Lots of timers"),
+            hash(&dir, "Break it"),
+            hash(&dir, "Fewer timers"),
+            hash(&dir, "Lots of timers")
+        )
+    );
+
+    let out = stdout(&gdtree(&dir, &["history", "--samples", "2", "level.tscn"]));
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines.len(), 5, "{}", out);
+    assert!(lines[1].ends_with("Add level") && lines[2].ends_with("Fewer timers"), "{}", out);
+    assert_eq!(lines[3..], [format!("largest growth: +2 nodes by {} 2024-03-01 (Fewer timers)", hash(&dir, "Fewer timers")).as_str(), "2 of 4 commits sampled"]);
+}
+
+#[test]
+fn no_history() {
+    let dir = repo("history-none");
+    fs::write(dir.join("old.tscn"), V1).unwrap();
+    commit(&dir, "Ada", "2024-01-08", "Add level");
+    fs::write(dir.join("level.tscn"), V1).unwrap();
+    let output = gdtree(&dir, &["history", "level.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("level.tscn has no git history"));
+}
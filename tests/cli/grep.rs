@@ -0,0 +1,51 @@
+use super::*;
+
+#[test]
+fn grep_value() {
+    let out = stdout(&gdtree(&fixture("apply"), &["grep-value", "OK"]));
+    assert_eq!(
+        out,
+        "res://main.tscn:8: Main: target = NodePath(\"Panel/OK\")
+res://main.tscn:15: Panel/OK: text = \"OK\"
+res://main.tscn:21: Other: button = NodePath(\"%OK\")
+3 matches in 1 of 2 files
+"
+    );
+}
+
+#[test]
+fn sub_resources() {
+    let out = stdout(&gdtree(&fixture("tres"), &["grep-value", "Color", "theme.tres"]));
+    assert_eq!(
+        out,
+        "theme.tres:4: sub_resource StyleBoxFlat_1 (StyleBoxFlat): bg_color = Color(0.1, 0.1, 0.1, 1)
+theme.tres:15: sub_resource LabelSettings_1 (LabelSettings): shadow_color = Color(0, 0, 0, 0.5)
+2 matches in 1 of 1 files
+"
+    );
+}
+
+#[test]
+fn through_ext_resources() {
+    let out = stdout(&gdtree(&fixture("moved"), &["grep-value", "icon.png"]));
+    assert_eq!(out, "res://main.tscn:9: Icon: texture = ExtResource(\"1_icon\") (res://icon.png)\n1 matches in 1 of 2 files\n");
+}
+
+#[test]
+fn no_matches() {
+    let output = gdtree(&fixture("tres"), &["grep-value", "nothing-here"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0 matches in 0 of 2 files\n");
+}
+
+#[test]
+fn skips_files_that_do_not_parse() {
+    let dir = copy("apply", "grep-broken");
+    fs::write(dir.join("broken.tscn"), "[gd_scene format=3]\n\n[node name=\"Broken\"\n").unwrap();
+    let output = gdtree(&dir, &["grep-value", "OK"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with("3 matches in 1 of 3 files\n"), "{:?}", output);
+    let err = String::from_utf8_lossy(&output.stderr);
+    assert!(err.starts_with("gdtree: res://broken.tscn: "), "{}", err);
+    assert!(err.ends_with("gdtree: 1 of 3 files could not be searched\n"), "{}", err);
+}
@@ -0,0 +1,29 @@
+use super::*;
+
+#[test]
+fn html() {
+    let out = stdout(&gdtree(&fixture("connections"), &["--format", "html", "door.tscn"]));
+    assert!(out.starts_with("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Door</title>\n"), "{}", out);
+    assert!(out.contains("<input id=\"search\" type=\"search\" placeholder=\"Search node names\">\n"), "{}", out);
+    let tree = &out[out.find("<ul class=\"tree\">").unwrap()..out.find("<script>").unwrap()];
+    assert_eq!(
+        tree,
+        r#"<ul class="tree">
+<li data-name="Door"><details open><summary title="body_entered() =&gt; Door:_on_body_entered() [deferred]&#10;body_exited() =&gt; Sound:stop() [reference counted]"><span class="name">Door</span> <span class="type">(Area2D)</span></summary>
+<ul>
+<li data-name="Timer"><div class="leaf" title="timeout() =&gt; Door:_on_timeout() [deferred] [oneshot]"><span class="name">Timer</span> <span class="type">(Timer)</span></div></li>
+<li data-name="Sound"><div class="leaf" title="finished() =&gt; Door:_on_finished()"><span class="name">Sound</span> <span class="type">(AudioStreamPlayer2D)</span></div></li>
+</ul></details></li>
+</ul>
+"#
+    );
+    assert!(out.ends_with("</script>\n</body>\n</html>\n"), "{}", out);
+}
+
+#[test]
+fn tooltips() {
+    let out = stdout(&gdtree(&fixture("describe"), &["--format", "html", "hud.tscn"]));
+    assert!(out.contains("<div class=\"leaf\" title=\"text: &quot;0&quot;\"><span class=\"name\">Score</span>"), "{}", out);
+    let out = stdout(&gdtree(&fixture("editable"), &["--format", "html", "level.tscn"]));
+    assert!(out.contains("<summary title=\"instance of res://enemy.tscn\"><span class=\"name\">Enemy</span></summary>"), "{}", out);
+}
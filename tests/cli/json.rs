@@ -0,0 +1,123 @@
+use super::*;
+
+fn schema_file(version: u32) -> String {
+    fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join(format!("schema/scene-v{}.schema.json", version))).unwrap()
+}
+
+#[test]
+fn schema() {
+    let out = stdout(&gdtree(&fixture("loose"), &["schema"]));
+    assert_eq!(out, schema_file(2));
+    let schema: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(schema["properties"]["format_version"]["const"], 2);
+}
+
+#[test]
+fn older_schema() {
+    let out = stdout(&gdtree(&fixture("loose"), &["schema", "--format-version", "1"]));
+    assert_eq!(out, schema_file(1));
+}
+
+#[test]
+fn stats_schema() {
+    let out = stdout(&gdtree(&fixture("loose"), &["schema", "--stats"]));
+    assert_eq!(out, fs::read_to_string(Path::new(env!("CARGO_MANIFEST_DIR")).join("schema/stats-v1.schema.json")).unwrap());
+    let schema: serde_json::Value = serde_json::from_str(&out).unwrap();
+    // every key stats prints is one the schema requires
+    let stats: serde_json::Value = serde_json::from_str(stdout(&gdtree(&fixture("reports"), &["stats", "--ndjson", "--limit", "1"])).trim()).unwrap();
+    let keys: Vec<&str> = stats.as_object().unwrap().keys().map(|key| key.as_str()).collect();
+    assert_eq!(schema["required"], serde_json::json!(keys));
+}
+
+#[test]
+fn unknown_format_version() {
+    let output = gdtree(&fixture("loose"), &["schema", "--format-version", "0"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+const SCENE: &str = "[gd_scene load_steps=3 format=3]
+
+[ext_resource type=\"Texture2D\" path=\"res://icon.png\" id=\"1_icon\"]
+
+[sub_resource type=\"CircleShape2D\" id=\"CircleShape2D_1\"]
+radius = 4.0
+
+[node name=\"Coin\" type=\"Area2D\"]
+
+[node name=\"Sprite\" type=\"Sprite2D\" parent=\".\"]
+texture = ExtResource(\"1_icon\")
+
+[node name=\"Shape\" type=\"CollisionShape2D\" parent=\".\"]
+shape = SubResource(\"CircleShape2D_1\")
+
+[connection signal=\"body_entered\" from=\".\" to=\".\" method=\"_on_body_entered\" flags=3]
+";
+
+#[test]
+fn json() {
+    let dir = temp("json");
+    fs::write(dir.join("coin.tscn"), SCENE).unwrap();
+    let out = stdout(&gdtree(&dir, &["--format", "json", "coin.tscn"]));
+    let scene: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(
+        scene,
+        serde_json::json!({
+            "format_version": 2,
+            "root": {
+                "name": "Coin",
+                "type": "Area2D",
+                "path": ".",
+                "parent": "",
+                "instance": null,
+                "properties": [],
+                "connections": [{ "signal": "body_entered", "from": ".", "to": "Coin", "method": "_on_body_entered", "flags": 3 }],
+                "children": [
+                    {
+                        "name": "Sprite",
+                        "type": "Sprite2D",
+                        "path": "Sprite",
+                        "parent": ".",
+                        "instance": null,
+                        "properties": [{
+                            "key": "texture",
+                            "value": "res://icon.png",
+                            "resource": { "path": "res://icon.png", "type": "Texture2D" },
+                            "sub_resource": null,
+                        }],
+                        "connections": [],
+                        "children": [],
+                    },
+                    {
+                        "name": "Shape",
+                        "type": "CollisionShape2D",
+                        "path": "Shape",
+                        "parent": ".",
+                        "instance": null,
+                        "properties": [{
+                            "key": "shape",
+                            "value": "CircleShape2D",
+                            "resource": null,
+                            "sub_resource": { "type": "CircleShape2D", "properties": [{ "key": "radius", "value": "4.0" }] },
+                        }],
+                        "connections": [],
+                        "children": [],
+                    },
+                ],
+            },
+            "ext_resources": [{ "id": "1_icon", "path": "res://icon.png", "type": "Texture2D" }],
+            "sub_resources": [{ "id": "CircleShape2D_1", "type": "CircleShape2D", "properties": [{ "key": "radius", "value": "4.0" }] }],
+        })
+    );
+}
+
+#[test]
+fn json_version_1() {
+    let dir = temp("json-v1");
+    fs::write(dir.join("coin.tscn"), SCENE).unwrap();
+    let out = stdout(&gdtree(&dir, &["--format", "json", "--format-version", "1", "coin.tscn"]));
+    let scene: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(scene["format_version"], 1);
+    assert!(scene.get("ext_resources").is_none());
+    assert!(scene["root"].get("parent").is_none());
+    assert_eq!(scene["root"]["connections"][0], serde_json::json!({ "signal": "body_entered", "from": ".", "to": "Coin", "method": "_on_body_entered" }));
+}
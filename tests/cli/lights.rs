@@ -0,0 +1,17 @@
+use super::*;
+
+#[test]
+fn lights() {
+    let out = stdout(&gdtree(&fixture("lights"), &["lights"]));
+    assert_eq!(
+        out,
+        "res://l.tscn: 3 lights, 2 casting shadows
+  Sun (DirectionalLight3D)  energy 1  shadows on
+  Lamp (OmniLight3D)  energy 2.5  shadows on  range 12.0
+  Spot (SpotLight3D)  energy 1  shadows off  range 5
+res://attic.tscn: 1 light, 0 casting shadows
+  Bulb (OmniLight3D)  energy 1  shadows off  range 5
+4 lights in 2 of 3 scenes, 2 casting shadows
+"
+    );
+}
@@ -0,0 +1,96 @@
+use super::*;
+
+#[test]
+fn lint() {
+    let output = gdtree(&fixture("lint"), &["lint"]);
+    assert_eq!(output.status.code(), Some(1));
+    let out = String::from_utf8_lossy(&output.stdout);
+    assert!(out.contains("menu.tscn: Play: Button has no pressed connection [buttons]\n"), "{}", out);
+    assert!(!out.contains("Quit"), "{}", out);
+    assert!(out.ends_with("1 problems in 2 scenes checked by 2 rules\n"), "{}", out);
+}
+
+#[test]
+fn lint_passes() {
+    let out = stdout(&gdtree(&fixture("lint"), &["lint", "title.tscn"]));
+    assert_eq!(out, "0 problems in 1 scenes checked by 2 rules\n");
+}
+
+#[test]
+fn lint_rule_that_does_not_compile() {
+    let output = gdtree(&fixture("lint"), &["lint", "--rules", "broken"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("gdtree: broken/unclosed.rhai: "));
+}
+
+#[test]
+fn budgets() {
+    let dir = copy("reports", "lint-budgets");
+    fs::create_dir_all(dir.join(".gdtree/rules")).unwrap();
+    let config = "[budget]
+max_nodes = 3
+max_file_size = 300
+max_instances = 1
+
+[scene_budgets.\"res://actors/player.tscn\"]
+max_nodes = 5
+max_depth = 1
+";
+    fs::write(dir.join(".gdtree/config.toml"), config).unwrap();
+    let output = gdtree(&dir, &["lint"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "res://actors/player.tscn: .: 2 levels deep, over the budget of 1 [budget]
+res://actors/player.tscn: .: 376 bytes file, over the budget of 300 bytes [budget]
+res://arena.tscn: .: 2 instanced scenes, over the budget of 1 [budget]
+res://main.tscn: .: 2 instanced scenes, over the budget of 1 [budget]
+res://main.tscn: .: 322 bytes file, over the budget of 300 bytes [budget]
+5 problems in 4 scenes checked by 2 rules
+"
+    );
+
+    let output = gdtree(&dir.join("actors"), &["lint", "player.tscn"]);
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with("2 problems in 1 scenes checked by 2 rules\n"));
+}
+
+#[test]
+fn ui_checks() {
+    let output = gdtree(&fixture("ui"), &["lint", "hud.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "hud.tscn: Box/Score: sets offset_left inside Box (VBoxContainer), which lays it out and ignores them [ui]
+hud.tscn: Divider: has no height, from its anchors and offsets, so draws nothing [ui]
+hud.tscn: World/Tag: is a Control under World (Node2D), so its anchors have no parent rectangle to follow [ui]
+3 problems in 1 scenes checked by 1 rules
+"
+    );
+}
+
+#[test]
+fn findings_in_path_order_on_any_number_of_threads() {
+    let dir = copy("lint", "lint-threads");
+    let menu = fs::read_to_string(dir.join("menu.tscn")).unwrap();
+    for i in 0..24 {
+        fs::write(dir.join(format!("menu{:02}.tscn", i)), &menu).unwrap();
+    }
+    let output = gdtree(&dir, &["lint", "--threads", "1"]);
+    let one = String::from_utf8_lossy(&output.stdout).to_string();
+    let scenes: Vec<&str> = one.lines().filter_map(|line| line.split_once(": ").map(|(scene, _)| scene)).collect();
+    let mut sorted = scenes.clone();
+    sorted.sort();
+    assert_eq!(scenes, sorted);
+    assert!(one.ends_with("25 problems in 26 scenes checked by 2 rules\n"), "{}", one);
+    let output = gdtree(&dir, &["lint", "--threads", "8"]);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), one);
+}
+
+#[test]
+fn scene_that_does_not_parse() {
+    let dir = copy("lint", "lint-unparsed");
+    fs::write(dir.join("broken.tscn"), "[gd_scene format=3]\n\n[node name=\"Broken\"").unwrap();
+    let output = gdtree(&dir, &["lint"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "gdtree: line 3, column 1: the node section header is never closed\n");
+}
@@ -0,0 +1,118 @@
+//! The gdtree binary, run on the scenes and projects in tests/fixtures.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+mod actions;
+mod annotations;
+mod apply;
+mod ascii;
+mod autoloads;
+mod badge;
+mod bookmarks;
+mod buses;
+mod check;
+mod color;
+mod comment;
+mod complete;
+mod conflicts;
+mod connections;
+mod coverage;
+mod depth;
+mod deprecated;
+mod describe;
+mod diff;
+mod dot;
+mod editable;
+mod expect;
+mod expand;
+mod files;
+mod filter;
+mod fonts;
+mod git;
+mod grep;
+mod html;
+mod json;
+mod lights;
+mod lint;
+mod media;
+mod mermaid;
+mod migrate;
+mod orphans;
+mod output;
+mod owners;
+#[cfg(target_os = "linux")]
+mod pager;
+mod physics;
+mod plugin;
+mod preview;
+mod project;
+mod properties;
+mod reports;
+mod resolve;
+#[cfg(unix)]
+mod serve;
+mod several;
+mod site;
+mod snippet;
+mod stdin;
+mod template;
+mod textconv;
+mod threads;
+mod timings;
+mod tool;
+mod tres;
+// on a pseudo-terminal from util-linux script
+#[cfg(target_os = "linux")]
+mod tui;
+mod units;
+mod values;
+mod version;
+mod viewports;
+mod watch;
+mod weights;
+mod world;
+mod zorder;
+
+fn fixture(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(path)
+}
+
+/// Runs gdtree in `dir`.
+fn gdtree(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_gdtree")).args(args).current_dir(dir).output().unwrap()
+}
+
+/// What a run that must succeed printed.
+fn stdout(output: &Output) -> String {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+/// An empty directory of the test's own in the temp directory.
+fn temp(test: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("gdtree-{}-{}", test, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// A copy of a fixture directory that the test may change.
+fn copy(path: &str, test: &str) -> PathBuf {
+    fn copy_dir(from: &Path, to: &Path) {
+        fs::create_dir_all(to).unwrap();
+        for entry in fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            match entry.file_type().unwrap().is_dir() {
+                true => copy_dir(&entry.path(), &to.join(entry.file_name())),
+                false => {
+                    fs::copy(entry.path(), to.join(entry.file_name())).unwrap();
+                }
+            }
+        }
+    }
+    let dir = temp(test);
+    copy_dir(&fixture(path), &dir);
+    dir
+}
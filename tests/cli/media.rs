@@ -0,0 +1,153 @@
+use super::*;
+
+/// A silent 16-bit PCM WAV file.
+fn wav(rate: u32, channels: u16, frames: u32) -> Vec<u8> {
+    let block = channels as u32 * 2;
+    let data = frames * block;
+    let mut wav = Vec::new();
+    wav.extend(b"RIFF");
+    wav.extend((36 + data).to_le_bytes());
+    wav.extend(b"WAVEfmt ");
+    wav.extend(16u32.to_le_bytes());
+    wav.extend(1u16.to_le_bytes());
+    wav.extend(channels.to_le_bytes());
+    wav.extend(rate.to_le_bytes());
+    wav.extend((rate * block).to_le_bytes());
+    wav.extend((block as u16).to_le_bytes());
+    wav.extend(16u16.to_le_bytes());
+    wav.extend(b"data");
+    wav.extend(data.to_le_bytes());
+    wav.resize(wav.len() + data as usize, 0);
+    wav
+}
+
+/// An Ogg page holding one packet.
+fn ogg_page(granule: u64, packet: &[u8]) -> Vec<u8> {
+    let mut page = Vec::new();
+    page.extend(b"OggS\0\0");
+    page.extend(granule.to_le_bytes());
+    page.extend([0; 12]);
+    page.push(1);
+    page.push(packet.len() as u8);
+    page.extend(packet);
+    page
+}
+
+/// The first and last pages of an Ogg Vorbis stream.
+fn vorbis(rate: u32, channels: u8, samples: u64) -> Vec<u8> {
+    let mut ident = b"\x01vorbis\0\0\0\0".to_vec();
+    ident.push(channels);
+    ident.extend(rate.to_le_bytes());
+    ident.extend([0; 16]);
+    let mut ogg = ogg_page(0, &ident);
+    ogg.extend(ogg_page(samples, &[0; 8]));
+    ogg
+}
+
+/// The first and last pages of an Ogg Opus stream.
+fn opus(channels: u8, pre_skip: u16, granule: u64) -> Vec<u8> {
+    let mut head = b"OpusHead\x01".to_vec();
+    head.push(channels);
+    head.extend(pre_skip.to_le_bytes());
+    head.extend(48000u32.to_le_bytes());
+    head.extend([0; 3]);
+    let mut ogg = ogg_page(0, &head);
+    ogg.extend(ogg_page(granule, &[0; 8]));
+    ogg
+}
+
+#[test]
+fn audio() {
+    let dir = temp("media");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.join("jump.wav"), wav(8000, 1, 4000)).unwrap();
+    fs::write(dir.join("theme.ogg"), vorbis(44100, 2, 44100 * 75)).unwrap();
+    fs::write(dir.join("voice.opus"), opus(6, 312, 312 + 48000 * 3 / 2)).unwrap();
+    fs::write(
+        dir.join("main.tscn"),
+        "[gd_scene load_steps=4 format=3]
+
+[ext_resource type=\"AudioStream\" path=\"res://jump.wav\" id=\"1\"]
+[ext_resource type=\"AudioStream\" path=\"res://theme.ogg\" id=\"2\"]
+[ext_resource type=\"AudioStream\" path=\"res://voice.opus\" id=\"3\"]
+
+[node name=\"Main\" type=\"Node\"]
+
+[node name=\"Jump\" type=\"AudioStreamPlayer\" parent=\".\"]
+stream = ExtResource(\"1\")
+
+[node name=\"Theme\" type=\"AudioStreamPlayer\" parent=\".\"]
+stream = ExtResource(\"2\")
+
+[node name=\"Voice\" type=\"AudioStreamPlayer\" parent=\".\"]
+stream = ExtResource(\"3\")
+",
+    )
+    .unwrap();
+
+    let out = stdout(&gdtree(&dir, &["--check-files", "main.tscn"]));
+    assert!(out.contains(" UTC, 0.50 s, 8000 Hz, mono]\n"), "{}", out);
+    assert!(out.contains(" UTC, 1:15.00, 44100 Hz, stereo]\n"), "{}", out);
+    assert!(out.contains(" UTC, 1.50 s, 48000 Hz, 6 channels]\n"), "{}", out);
+}
+
+/// The signature and IHDR chunk of a PNG.
+fn png(width: u32, height: u32) -> Vec<u8> {
+    let mut png = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+    png.extend(width.to_be_bytes());
+    png.extend(height.to_be_bytes());
+    png.extend([8, 6, 0, 0, 0, 0, 0, 0, 0]);
+    png
+}
+
+/// A JPEG with an APP0 segment ahead of its baseline start-of-frame.
+fn jpeg(width: u16, height: u16) -> Vec<u8> {
+    let mut jpeg = vec![0xff, 0xd8, 0xff, 0xe0, 0, 4, 0, 0, 0xff, 0xc0, 0, 11, 8];
+    jpeg.extend(height.to_be_bytes());
+    jpeg.extend(width.to_be_bytes());
+    jpeg.extend([1, 1, 0x11, 0]);
+    jpeg
+}
+
+/// A lossless WebP header.
+fn webp(width: u32, height: u32) -> Vec<u8> {
+    let mut webp = b"RIFF\0\0\0\0WEBPVP8L\0\0\0\0\x2f".to_vec();
+    webp.extend(((width - 1) | (height - 1) << 14).to_le_bytes());
+    webp.resize(30, 0);
+    webp
+}
+
+#[test]
+fn images() {
+    let dir = temp("media-images");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.join("icon.png"), png(1024, 512)).unwrap();
+    fs::write(dir.join("photo.jpg"), jpeg(640, 480)).unwrap();
+    fs::write(dir.join("tile.webp"), webp(16, 32)).unwrap();
+    fs::write(
+        dir.join("main.tscn"),
+        "[gd_scene load_steps=4 format=3]
+
+[ext_resource type=\"Texture2D\" path=\"res://icon.png\" id=\"1\"]
+[ext_resource type=\"Texture2D\" path=\"res://photo.jpg\" id=\"2\"]
+[ext_resource type=\"Texture2D\" path=\"res://tile.webp\" id=\"3\"]
+
+[node name=\"Main\" type=\"Node2D\"]
+
+[node name=\"Icon\" type=\"Sprite2D\" parent=\".\"]
+texture = ExtResource(\"1\")
+
+[node name=\"Photo\" type=\"Sprite2D\" parent=\".\"]
+texture = ExtResource(\"2\")
+
+[node name=\"Tile\" type=\"Sprite2D\" parent=\".\"]
+texture = ExtResource(\"3\")
+",
+    )
+    .unwrap();
+
+    let out = stdout(&gdtree(&dir, &["--check-files", "main.tscn"]));
+    assert!(out.contains(" UTC, 1024x512, ~2 MiB VRAM]\n"), "{}", out);
+    assert!(out.contains(" UTC, 640x480, ~1.2 MiB VRAM]\n"), "{}", out);
+    assert!(out.contains(" UTC, 16x32, ~2 KiB VRAM]\n"), "{}", out);
+}
@@ -0,0 +1,41 @@
+use super::*;
+
+#[test]
+fn mermaid() {
+    let out = stdout(&gdtree(&fixture("connections"), &["--format", "mermaid", "door.tscn"]));
+    assert_eq!(
+        out,
+        r#"graph TD
+    n0["Door (Area2D)"]
+    n1["Timer (Timer)"]
+    n2["Sound (AudioStreamPlayer2D)"]
+    n0 --> n1
+    n0 --> n2
+    n0 -.->|"body_entered → _on_body_entered() [deferred]"| n0
+    n0 -.->|"body_exited → stop() [reference counted]"| n2
+    n1 -.->|"timeout → _on_timeout() [deferred] [oneshot]"| n0
+    n2 -.->|"finished → _on_finished()"| n0
+"#
+    );
+}
+
+#[test]
+fn instances() {
+    let out = stdout(&gdtree(&fixture("editable"), &["--format", "mermaid", "level.tscn"]));
+    assert!(out.contains("    n1[\"Enemy<br/>res://enemy.tscn\"]\n"), "{}", out);
+    assert!(out.contains("    n3[\"Sprite\"]\n"), "{}", out);
+}
+
+#[test]
+fn missing_nodes() {
+    let dir = temp("mermaid-missing");
+    let scene = "[gd_scene format=3]
+
+[node name=\"Main\" type=\"Node\"]
+
+[connection signal=\"timeout\" from=\"Gone\" to=\".\" method=\"_on_timeout\"]
+";
+    fs::write(dir.join("main.tscn"), scene).unwrap();
+    let out = stdout(&gdtree(&dir, &["--format", "mermaid", "main.tscn"]));
+    assert_eq!(out, "graph TD\n    n0[\"Main (Node)\"]\n");
+}
@@ -0,0 +1,60 @@
+use super::*;
+
+#[test]
+fn migrate_properties() {
+    let dir = copy("godot3", "migrate");
+    let output = gdtree(&dir, &["migrate-properties", "hud.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "hud.tscn
+  .: rect_min_size -> custom_minimum_size
+  Icon: margin_right -> offset_right
+  Label: custom_colors/font_color -> theme_override_colors/font_color
+3 renamed properties in 1 of 1 scenes
+"
+    );
+
+    let out = stdout(&gdtree(&dir, &["migrate-properties", "--write", "hud.tscn"]));
+    assert!(out.ends_with("renamed 3 properties in 1 of 1 scenes\n"), "{}", out);
+    let expected = fs::read_to_string(fixture("godot3/hud.tscn"))
+        .unwrap()
+        .replace("rect_min_size", "custom_minimum_size")
+        .replace("margin_right", "offset_right")
+        .replace("custom_colors/", "theme_override_colors/");
+    assert_eq!(fs::read_to_string(dir.join("hud.tscn")).unwrap(), expected);
+    assert!(!dir.join("hud.tscn.partial").exists());
+
+    // nothing is left to rename
+    let out = stdout(&gdtree(&dir, &["migrate-properties", "hud.tscn"]));
+    assert_eq!(out, "0 renamed properties in 0 of 1 scenes\n");
+}
+
+#[test]
+fn keeps_line_endings() {
+    let dir = temp("migrate-crlf");
+    let scene = "[gd_scene format=2]\r\n\r\n[node name=\"Tip\" type=\"Label\"]\r\nhint_tooltip = \"a\"\r\nalign = 1\r\n";
+    fs::write(dir.join("tip.tscn"), scene).unwrap();
+    stdout(&gdtree(&dir, &["migrate-properties", "--write", "tip.tscn"]));
+    assert_eq!(
+        fs::read_to_string(dir.join("tip.tscn")).unwrap(),
+        "[gd_scene format=2]\r\n\r\n[node name=\"Tip\" type=\"Label\"]\r\ntooltip_text = \"a\"\r\nhorizontal_alignment = 1\r\n"
+    );
+}
+
+#[test]
+fn configured_renames() {
+    let dir = copy("godot3", "migrate-config");
+    fs::write(dir.join("project.godot"), "config_version=4\n").unwrap();
+    fs::create_dir_all(dir.join(".gdtree")).unwrap();
+    fs::write(
+        dir.join(".gdtree/config.toml"),
+        "[renamed_properties]\n\"TextureRect.margin_right\" = \"right_edge\"\ntext = \"caption\"\n",
+    )
+    .unwrap();
+    let output = gdtree(&dir, &["migrate-properties", "hud.tscn"]);
+    let out = String::from_utf8_lossy(&output.stdout);
+    assert!(out.contains("  Icon: margin_right -> right_edge\n"), "{}", out);
+    assert!(out.contains("  Label: text -> caption\n"), "{}", out);
+    assert!(out.ends_with("4 renamed properties in 1 of 1 scenes\n"), "{}", out);
+}
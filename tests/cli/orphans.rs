@@ -0,0 +1,58 @@
+use super::*;
+
+fn project(test: &str) -> PathBuf {
+    let dir = temp(test);
+    fs::write(
+        dir.join("project.godot"),
+        "config_version=5\n\n[application]\n\nrun/main_scene=\"uid://bmain\"\n\n[autoload]\n\nMusic=\"*res://music.tscn\"\n",
+    )
+    .unwrap();
+    let scenes = [
+        ("main.tscn", "[gd_scene load_steps=2 format=3 uid=\"uid://bmain\"]\n\n[ext_resource type=\"PackedScene\" path=\"res://levels/level.tscn\" id=\"1\"]\n\n[node name=\"Main\" type=\"Node\"]\n\n[node name=\"Level\" parent=\".\" instance=ExtResource(\"1\")]\n"),
+        ("levels/level.tscn", "[gd_scene format=3]\n\n[node name=\"Level\" type=\"Node2D\"]\n"),
+        ("levels/old.tscn", "[gd_scene format=3]\n\n[node name=\"Old\" type=\"Node2D\"]\n"),
+        ("music.tscn", "[gd_scene format=3]\n\n[node name=\"Music\" type=\"AudioStreamPlayer\"]\n"),
+        ("loop.tscn", "[gd_scene load_steps=2 format=3]\n\n[ext_resource type=\"PackedScene\" path=\"res://loop.tscn\" id=\"1\"]\n\n[node name=\"Loop\" type=\"Node\"]\n"),
+    ];
+    fs::create_dir_all(dir.join("levels")).unwrap();
+    for (name, text) in scenes {
+        fs::write(dir.join(name), text).unwrap();
+    }
+    dir
+}
+
+#[test]
+fn orphans() {
+    let dir = project("orphans");
+    let output = gdtree(&dir, &["orphans"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "res://levels/old.tscn\nres://loop.tscn\n2 of 5 scenes are never instanced, autoloaded or run as the main scene\n"
+    );
+
+    fs::remove_file(dir.join("levels/old.tscn")).unwrap();
+    fs::remove_file(dir.join("loop.tscn")).unwrap();
+    let out = stdout(&gdtree(&dir.join("levels"), &["orphans"]));
+    assert_eq!(out, "0 of 3 scenes are never instanced, autoloaded or run as the main scene\n");
+}
+
+#[test]
+fn needs_a_project() {
+    let output = gdtree(&temp("orphans-no-project"), &["orphans"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no project.godot found above ., so pass --project-root"));
+}
+
+#[test]
+fn broken_scene() {
+    let dir = project("orphans-broken");
+    fs::write(dir.join("levels/broken.tscn"), "[gd_scene format=3]\n\n[node name=\"Broken\"\n").unwrap();
+    let output = gdtree(&dir, &["orphans"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "res://levels/broken.tscn\nres://levels/old.tscn\nres://loop.tscn\n3 of 6 scenes are never instanced, autoloaded or run as the main scene\n"
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("gdtree: skipping "), "{:?}", output);
+}
@@ -0,0 +1,45 @@
+use super::*;
+
+#[test]
+fn output_file() {
+    let dir = temp("output");
+    let file = dir.join("tree.txt");
+    let out = stdout(&gdtree(&fixture("moved"), &["main.tscn", "-o", file.to_str().unwrap()]));
+    assert_eq!(out, "");
+    assert_eq!(fs::read_to_string(&file).unwrap(), stdout(&gdtree(&fixture("moved"), &["main.tscn"])));
+}
+
+#[test]
+fn output_export() {
+    let dir = temp("output-export");
+    let file = dir.join("main.json");
+    stdout(&gdtree(&fixture("moved"), &["--format", "json", "main.tscn", "--output", file.to_str().unwrap()]));
+    assert_eq!(fs::read_to_string(&file).unwrap(), stdout(&gdtree(&fixture("moved"), &["--format", "json", "main.tscn"])));
+}
+
+#[test]
+fn colors_only_when_asked_for() {
+    let dir = temp("output-color");
+    let file = dir.join("tree.txt");
+    stdout(&gdtree(&fixture("moved"), &["main.tscn", "-o", file.to_str().unwrap()]));
+    assert!(!fs::read_to_string(&file).unwrap().contains('\x1b'));
+    stdout(&gdtree(&fixture("moved"), &["--color", "always", "main.tscn", "-o", file.to_str().unwrap()]));
+    assert!(fs::read_to_string(&file).unwrap().contains("\x1b[1;34mMain\x1b[0m"));
+}
+
+#[test]
+fn one_file_only() {
+    let dir = temp("output-several");
+    let output = gdtree(&fixture("physics"), &[".", "-o", dir.join("tree.txt").to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--output takes a single scene, not 2 files"));
+}
+
+#[test]
+fn unwritable() {
+    let dir = temp("output-unwritable");
+    let file = dir.join("missing/tree.txt");
+    let output = gdtree(&fixture("moved"), &["main.tscn", "-o", file.to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with(&format!("gdtree: {}: ", file.display())));
+}
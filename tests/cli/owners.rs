@@ -0,0 +1,69 @@
+use super::*;
+
+/// The lint fixture as a repository of its own, with a second menu under ui/.
+fn repo(test: &str) -> PathBuf {
+    let dir = copy("lint", test);
+    // the search for CODEOWNERS stops at the top of the repository
+    fs::create_dir_all(dir.join(".git")).unwrap();
+    fs::create_dir_all(dir.join("ui")).unwrap();
+    fs::copy(dir.join("menu.tscn"), dir.join("ui/pause.tscn")).unwrap();
+    dir
+}
+
+const CODEOWNERS: &str = "# scenes
+*.tscn @everyone
+/ui/ @ui @ux-review
+menu.tscn
+";
+
+#[test]
+fn codeowners() {
+    let dir = repo("owners-codeowners");
+    fs::create_dir_all(dir.join(".github")).unwrap();
+    fs::write(dir.join(".github/CODEOWNERS"), CODEOWNERS).unwrap();
+    let output = gdtree(&dir, &["lint"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "res://menu.tscn: Play: Button has no pressed connection [buttons]
+res://ui/pause.tscn: Play: Button has no pressed connection [buttons] @ui @ux-review
+problems by owner: unowned 1, @ui 1, @ux-review 1
+2 problems in 3 scenes checked by 2 rules
+"
+    );
+
+    let out = stdout(&gdtree(&dir, &["stats"]));
+    assert_eq!(
+        out,
+        "scene                 nodes        size  depth  refs   ext   sub  conns  owners
+res://menu.tscn           3   227 bytes      1     0     0     0      1  
+res://title.tscn          1    54 bytes      0     0     0     0      0  @everyone
+res://ui/pause.tscn       3   227 bytes      1     0     0     0      1  @ui @ux-review
+3 scenes
+"
+    );
+}
+
+#[test]
+fn config_owners() {
+    let dir = repo("owners-config");
+    fs::write(dir.join("CODEOWNERS"), CODEOWNERS).unwrap();
+    fs::create_dir_all(dir.join(".gdtree")).unwrap();
+    fs::write(dir.join(".gdtree/config.toml"), "[owners]\n\"res://*\" = [\"@all\"]\n\"res://ui/*\" = [\"@ui\"]\n").unwrap();
+    let output = gdtree(&dir, &["lint"]);
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "res://menu.tscn: Play: Button has no pressed connection [buttons] @all
+res://ui/pause.tscn: Play: Button has no pressed connection [buttons] @ui
+problems by owner: @all 1, @ui 1
+2 problems in 3 scenes checked by 2 rules
+"
+    );
+}
+
+#[test]
+fn no_owners() {
+    let dir = repo("owners-none");
+    let output = gdtree(&dir, &["lint"]);
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with("[buttons]\n2 problems in 3 scenes checked by 2 rules\n"));
+}
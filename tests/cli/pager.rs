@@ -0,0 +1,39 @@
+use std::process::Stdio;
+
+use super::*;
+
+/// Runs gdtree with `args` in `dir` on a pseudo-terminal `rows` lines tall,
+/// with `PAGER` set to `pager`, and returns what reached the terminal.
+fn on_terminal(dir: &Path, rows: usize, pager: &str, args: &str) -> String {
+    let command = format!("stty cols 100 rows {}; exec '{}' {}", rows, env!("CARGO_BIN_EXE_gdtree"), args);
+    let output = Command::new("script")
+        .args(["-qfec", &command, "/dev/null"])
+        .current_dir(dir)
+        .env("PAGER", pager)
+        .stdin(Stdio::null())
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).replace("\r\n", "\n")
+}
+
+#[test]
+fn pages_a_tall_tree() {
+    let out = on_terminal(&fixture("apply"), 4, "sed s/^/paged:/", "--color never main.tscn");
+    assert!(out.starts_with("paged:Main\npaged:"), "{:?}", out);
+}
+
+#[test]
+fn prints_a_short_tree() {
+    let out = on_terminal(&fixture("apply"), 24, "sed s/^/paged:/", "--color never main.tscn");
+    assert!(out.starts_with("Main\n"), "{:?}", out);
+}
+
+#[test]
+fn no_pager() {
+    let out = on_terminal(&fixture("apply"), 4, "sed s/^/paged:/", "--color never --no-pager main.tscn");
+    assert!(out.starts_with("Main\n"), "{:?}", out);
+    let out = on_terminal(&fixture("apply"), 4, "cat", "--color never main.tscn");
+    assert!(out.starts_with("Main\n"), "{:?}", out);
+    let out = on_terminal(&fixture("apply"), 4, "", "--color never main.tscn");
+    assert!(out.starts_with("Main\n"), "{:?}", out);
+}
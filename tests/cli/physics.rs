@@ -0,0 +1,24 @@
+use super::*;
+
+#[test]
+fn physics() {
+    let output = gdtree(&fixture("physics"), &["physics"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "res://player.tscn
+  Player (CharacterBody2D)  layer 1  mask 1,3  1 shape
+  Hurtbox (Area2D)  layer 4  mask none  0 shapes [no collision shape]
+  Crate (RigidBody2D)  layer 1  mask 1  2 shapes (1 disabled)
+res://wall.tscn
+  Wall (StaticBody3D)  layer 1  mask 1  1 shape
+4 bodies and areas in 2 of 2 scenes, 1 without collision shapes
+"
+    );
+}
+
+#[test]
+fn physics_with_shapes() {
+    let out = stdout(&gdtree(&fixture("physics"), &["physics", "wall.tscn"]));
+    assert!(out.ends_with("1 bodies and areas in 1 of 1 scenes, 0 without collision shapes\n"), "{}", out);
+}
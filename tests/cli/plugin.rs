@@ -0,0 +1,56 @@
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::sync::OnceLock;
+
+use super::*;
+
+/// The fixture plugin, built once for all the tests that load it.
+fn plugin() -> &'static str {
+    static BUILT: OnceLock<String> = OnceLock::new();
+    BUILT.get_or_init(|| {
+        let lib = temp("plugin").join(format!("{}shout{}", DLL_PREFIX, DLL_SUFFIX));
+        let rustc = std::env::var("RUSTC").unwrap_or("rustc".to_string());
+        let status = Command::new(rustc)
+            .args(["--edition", "2021", "--crate-type", "cdylib", "-o"])
+            .arg(&lib)
+            .arg(fixture("plugin/plugin.rs"))
+            .status()
+            .unwrap();
+        assert!(status.success());
+        lib.to_string_lossy().to_string()
+    })
+}
+
+#[test]
+fn render() {
+    let out = stdout(&gdtree(&fixture("plugin"), &["--plugin", plugin(), "--format", "shout", "scene.tscn"]));
+    assert!(out.contains(r#""NAME":"PATH","TYPE":"LINE2D""#), "{}", out);
+}
+
+#[test]
+fn decode() {
+    let out = stdout(&gdtree(&fixture("plugin"), &["--plugin", plugin(), "scene.tscn"]));
+    assert!(out.contains("* width_curve: a curve, decoded\n"), "{}", out);
+}
+
+#[test]
+fn lint() {
+    let output = gdtree(&fixture("plugin"), &["lint", "--plugin", plugin(), "scene.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "scene.tscn: .: too quiet [shout]\n1 problems in 1 scenes checked by 2 rules\n");
+}
+
+#[test]
+fn not_a_plugin() {
+    let output = gdtree(&fixture("plugin"), &["--plugin", "scene.tscn", "scene.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("gdtree: plugin scene.tscn: "));
+}
+
+#[test]
+fn render_gets_the_format_version() {
+    let out = stdout(&gdtree(&fixture("plugin"), &["--plugin", plugin(), "--format", "shout", "scene.tscn"]));
+    assert!(out.starts_with(r#"{"FORMAT_VERSION":2,"#), "{}", out);
+    let out = stdout(&gdtree(&fixture("plugin"), &["--plugin", plugin(), "--format", "shout", "--format-version", "1", "scene.tscn"]));
+    assert!(out.starts_with(r#"{"FORMAT_VERSION":1,"#), "{}", out);
+    assert!(!out.contains("SUB_RESOURCES"), "{}", out);
+}
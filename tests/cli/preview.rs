@@ -0,0 +1,118 @@
+use super::*;
+
+#[test]
+fn curves_and_gradients() {
+    let out = stdout(&gdtree(&fixture("preview"), &["trail.tscn"]));
+    assert_eq!(
+        out,
+        "Trail
+│   * width_curve: Curve ▁▁▂▂▃▃▄▄▅▅▆▆▇▇██ (2 points, 0..1)
+│   * texture: GradientTexture1D of Gradient [░░░░            ] (#ff0000 → #00000000)
+└── Steps (Line2D)
+        * gradient: Gradient [████████        ] (#ffffff → #000000)
+"
+    );
+}
+
+#[test]
+fn shapes() {
+    let out = stdout(&gdtree(&fixture("preview"), &["level.tscn"]));
+    assert_eq!(
+        out,
+        "Level
+├── Wall (CollisionShape2D)
+│       * shape: RectangleShape2D 32×48
+├── Door (CollisionShape2D)
+│       * shape: RectangleShape2D 16×32
+├── Coin (CollisionShape2D)
+│       * shape: CircleShape2D radius 10
+├── Ramp (CollisionShape2D)
+│       * shape: ConvexPolygonShape2D polygon with 3 points
+├── Crate (CollisionShape3D)
+│       * shape: BoxShape3D 1×1×1
+└── Floor (NavigationRegion2D)
+        * navigation_polygon: NavigationPolygon 4 vertices, 2 polygons, 1 outline
+"
+    );
+}
+
+#[test]
+fn replication() {
+    let out = stdout(&gdtree(&fixture("preview"), &["lobby.tscn"]));
+    assert_eq!(
+        out,
+        "Lobby
+├── Spawner (MultiplayerSpawner)
+│       * _spawnable_scenes: res://player.tscn, res://pickup.tscn
+│       * spawn_path: NodePath(\"..\")
+├── Sync (MultiplayerSynchronizer)
+│       * replication_config: SceneReplicationConfig .:position (spawn, always), Sprite:frame (on change)
+└── OldSync (MultiplayerSynchronizer)
+        * replication_config: SceneReplicationConfig .:health (spawn, sync)
+"
+    );
+}
+
+#[test]
+fn particle_groups() {
+    let out = stdout(&gdtree(&fixture("preview"), &["sparks.tscn"]));
+    assert_eq!(
+        out,
+        "Sparks
+    * amount: 32
+    * process_material: ParticleProcessMaterial
+                        ├── emission: shape box, box extents Vector3(1, 0, 1)
+                        ├── direction: Vector3(0, 1, 0), spread 30.0, gravity Vector3(0, -9.8, 0)
+                        ├── velocity: initial velocity 2.0..5.0
+                        ├── scale: 0.5..1.5
+                        ├── color: color ramp GradientTexture1D of Gradient [██▓▓▓▓▒▒▒▒░░░░  ] (#ffffff → #000000)
+                        ├── turbulence: enabled
+                        └── other: lifetime randomness 0.2
+"
+    );
+}
+
+#[test]
+fn environment_groups() {
+    let out = stdout(&gdtree(&fixture("preview"), &["world.tscn"]));
+    assert_eq!(
+        out,
+        "World
+    * environment: Environment
+                   ├── background: mode sky
+                   ├── sky: Sky of ProceduralSkyMaterial
+                   ├── ambient light: source sky, energy 0.5
+                   ├── tonemap: mode filmic
+                   ├── glow: enabled, intensity 0.8, blend mode screen
+                   └── fog: enabled, density 0.02
+"
+    );
+}
+
+#[test]
+fn nested_groups() {
+    let out = stdout(&gdtree(&fixture("preview"), &["groups.tscn"]));
+    assert_eq!(
+        out,
+        "Sign
+│   * theme_override_colors
+                             ├── font_color: Color(1, 0, 0, 1)
+                             └── font_outline_color: Color(0, 0, 0, 1)
+│   * theme_override_constants
+                                └── outline_size: 2
+│   * text: \"Open\"
+└── Player (AnimationPlayer)
+        * autoplay: \"blink\"
+        * libraries
+                     └── blink: Animation
+                         ├── length: 0.5
+                         └── tracks
+                             ├── 0
+                             │   ├── type: \"value\"
+                             │   └── path: NodePath(\".:visible\")
+                             └── 1
+                                 ├── type: \"method\"
+                                 └── path: NodePath(\".\")
+"
+    );
+}
@@ -0,0 +1,40 @@
+use super::*;
+
+#[test]
+fn stale_paths_follow_their_uids() {
+    let out = stdout(&gdtree(&fixture("moved"), &["main.tscn"]));
+    assert!(out.contains("* texture: res://art/icon.png\n"), "{}", out);
+    assert!(out.contains("* (PackedScene) res://ui/hud.tscn\n"), "{}", out);
+}
+
+#[test]
+fn project_root_overrides_the_search() {
+    // there is no project.godot here, so the paths stay as the scene wrote them
+    let out = stdout(&gdtree(&fixture("moved"), &["--project-root", "ui", "main.tscn"]));
+    assert!(out.contains("* texture: res://icon.png\n"), "{}", out);
+}
+
+#[test]
+fn absolute_paths() {
+    let out = stdout(&gdtree(&fixture("moved"), &["--absolute-paths", "main.tscn"]));
+    let icon = fixture("moved").canonicalize().unwrap().join("art/icon.png");
+    assert!(out.contains(&format!("* texture: {}\n", icon.display())), "{}", out);
+}
+
+#[test]
+fn absolute_paths_need_a_project() {
+    let output = gdtree(&fixture("loose"), &["--absolute-paths", "hud.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pass --project-root"));
+}
+
+#[test]
+fn project_alias() {
+    // a scene kept outside the project still resolves against it
+    let dir = temp("project-alias");
+    fs::copy(fixture("reports/main.tscn"), dir.join("main.tscn")).unwrap();
+    let project = fixture("reports");
+    let out = stdout(&gdtree(&dir, &["--project", project.to_str().unwrap(), "--expand-instances", "main.tscn"]));
+    assert!(out.contains("│   └── Cooldown (Timer)\n"), "{}", out);
+    assert_eq!(out, stdout(&gdtree(&dir, &["--project-root", project.to_str().unwrap(), "--expand-instances", "main.tscn"])));
+}
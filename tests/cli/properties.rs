@@ -0,0 +1,48 @@
+use super::*;
+
+const STRUCTURE: &str = "Player
+├── Sprite (Sprite2D)
+├── Hitbox (Area2D)
+│   └── Shape (CollisionShape2D)
+└── Cooldown (Timer)
+";
+
+#[test]
+fn no_properties() {
+    let out = stdout(&gdtree(&fixture("reports"), &["--no-properties", "actors/player.tscn"]));
+    assert_eq!(out, STRUCTURE);
+    // instances and connections stay
+    let out = stdout(&gdtree(&fixture("reports"), &["--no-properties", "main.tscn"]));
+    assert!(out.contains("│       * (PackedScene) res://actors/player.tscn\n"), "{}", out);
+    let out = stdout(&gdtree(&fixture("connections"), &["--no-properties", "door.tscn"]));
+    assert_eq!(out.matches("* connection:").count(), 4, "{}", out);
+}
+
+#[test]
+fn structure_only_config() {
+    let dir = copy("reports", "structure-only");
+    fs::create_dir_all(dir.join(".gdtree")).unwrap();
+    fs::write(dir.join(".gdtree/config.toml"), "structure_only = true\n").unwrap();
+    let full = stdout(&gdtree(&fixture("reports"), &["actors/player.tscn"]));
+    assert_eq!(stdout(&gdtree(&dir, &["actors/player.tscn"])), STRUCTURE);
+    assert_eq!(stdout(&gdtree(&dir, &["--properties", "actors/player.tscn"])), full);
+    // the last of the two flags wins
+    assert_eq!(stdout(&gdtree(&dir, &["--properties", "--no-properties", "actors/player.tscn"])), STRUCTURE);
+}
+
+#[test]
+fn structure_only() {
+    let out = stdout(&gdtree(&fixture("apply"), &["--structure-only", "main.tscn"]));
+    assert_eq!(
+        out,
+        "Main
+├── Panel (Control)
+│   └── OK (Button)
+│       └── Icon (Sprite2D)
+├── Other (Node)
+└── Menu
+"
+    );
+    let output = gdtree(&fixture("apply"), &["--structure-only", "--weights", "main.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,334 @@
+use super::*;
+
+#[test]
+fn stats() {
+    let out = stdout(&gdtree(&fixture("reports"), &["stats"]));
+    assert_eq!(
+        out,
+        "scene                      nodes        size  depth  refs   ext   sub  conns
+res://actors/player.tscn       5   376 bytes      2     2     1     0      0
+res://arena.tscn               3   262 bytes      1     0     1     0      0
+res://main.tscn                3   322 bytes      1     0     2     0      0
+res://ui/hud.tscn              2   103 bytes      1     1     0     0      0
+4 scenes
+"
+    );
+}
+
+const COUNTED: &str = "[gd_scene load_steps=3 format=3]
+
+[ext_resource type=\"PackedScene\" path=\"res://actors/player.tscn\" id=\"1\"]
+
+[sub_resource type=\"RectangleShape2D\" id=\"2\"]
+
+[node name=\"Arena\" type=\"Node2D\"]
+
+[node name=\"Wall\" type=\"StaticBody2D\" parent=\".\"]
+
+[node name=\"Shape\" type=\"CollisionShape2D\" parent=\"Wall\"]
+shape = SubResource(\"2\")
+
+[node name=\"Floor\" type=\"StaticBody2D\" parent=\".\"]
+
+[node name=\"Player\" parent=\".\" instance=ExtResource(\"1\")]
+
+[connection signal=\"body_entered\" from=\"Player\" to=\".\" method=\"_on_entered\"]
+";
+
+#[test]
+fn stats_of_one_scene() {
+    let dir = temp("stats-one");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.join("arena.tscn"), COUNTED).unwrap();
+    let out = stdout(&gdtree(&dir, &["stats", "arena.tscn"]));
+    assert_eq!(
+        out,
+        "scene              nodes        size  depth  refs   ext   sub  conns
+res://arena.tscn       5   513 bytes      2     0     1     1      1
+
+  StaticBody2D                   2
+  Node2D                         1
+  CollisionShape2D               1
+  res://actors/player.tscn       1
+1 scenes
+"
+    );
+}
+
+#[test]
+fn stats_json() {
+    let dir = temp("stats-json");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.join("arena.tscn"), COUNTED).unwrap();
+    let out = stdout(&gdtree(&dir, &["stats", "--json", "."]));
+    let stats: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(
+        stats,
+        serde_json::json!([{
+            "format_version": 1,
+            "scene": "res://arena.tscn",
+            "nodes": 5,
+            "size": 513,
+            "depth": 2,
+            "refs": 0,
+            "ext_resources": 1,
+            "sub_resources": 1,
+            "connections": 1,
+            "owners": [],
+            "types": {"StaticBody2D": 2, "Node2D": 1, "CollisionShape2D": 1, "res://actors/player.tscn": 1},
+        }])
+    );
+}
+
+#[test]
+fn stats_sorted_and_limited() {
+    let out = stdout(&gdtree(&fixture("reports"), &["stats", "--sort-by", "size", "--limit", "2"]));
+    assert_eq!(
+        out,
+        "scene                      nodes        size  depth  refs   ext   sub  conns
+res://actors/player.tscn       5   376 bytes      2     2     1     0      0
+res://main.tscn                3   322 bytes      1     0     2     0      0
+2 scenes
+"
+    );
+}
+
+#[test]
+fn refs_count_the_whole_project() {
+    let out = stdout(&gdtree(&fixture("reports"), &["stats", "ui"]));
+    assert!(out.contains("res://ui/hud.tscn       2   103 bytes      1     1     0     0      0\n"), "{}", out);
+}
+
+#[test]
+fn deps() {
+    let out = stdout(&gdtree(&fixture("reports"), &["deps", "main.tscn"]));
+    assert_eq!(out, "res://main.tscn\n  res://actors/player.tscn (PackedScene)\n  res://ui/hud.tscn (PackedScene)\n");
+}
+
+#[test]
+fn deps_by_kind() {
+    let dir = temp("deps-by-kind");
+    let scene = "[gd_scene load_steps=6 format=3]
+
+[ext_resource type=\"PackedScene\" path=\"res://enemy.tscn\" id=\"1\"]
+[ext_resource type=\"Script\" path=\"res://level.gd\" id=\"2\"]
+[ext_resource type=\"Texture2D\" path=\"res://sky.png\" id=\"3\"]
+[ext_resource type=\"AudioStreamOggVorbis\" path=\"res://theme.ogg\" id=\"4\"]
+[ext_resource path=\"res://hit.wav\" id=\"5\"]
+[ext_resource path=\"res://Ui.CS\" id=\"6\"]
+
+[node name=\"Level\" type=\"Node2D\"]
+";
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    fs::write(dir.join("level.tscn"), scene).unwrap();
+    for (kind, listed) in [
+        ("scenes", "  res://enemy.tscn (PackedScene)\n"),
+        ("scripts", "  res://level.gd (Script)\n  res://Ui.CS\n"),
+        ("textures", "  res://sky.png (Texture2D)\n"),
+        ("audio", "  res://theme.ogg (AudioStreamOggVorbis)\n  res://hit.wav\n"),
+    ] {
+        let out = stdout(&gdtree(&dir, &["deps", "--kind", kind, "level.tscn"]));
+        assert_eq!(out, format!("res://level.tscn\n{}", listed), "{}", kind);
+    }
+    let out = stdout(&gdtree(&dir, &["deps", "level.tscn"]));
+    assert_eq!(out.lines().count(), 7, "{}", out);
+}
+
+#[test]
+fn deps_between_scenes() {
+    let out = stdout(&gdtree(&fixture("reports"), &["deps", "--from", "arena.tscn", "--to", "actors/player.tscn"]));
+    assert_eq!(
+        out,
+        "res://arena.tscn [Player1] -> res://actors/player.tscn
+res://arena.tscn [Player2] -> res://actors/player.tscn
+2 paths from res://arena.tscn to res://actors/player.tscn
+"
+    );
+    let output = gdtree(&fixture("reports"), &["deps", "--from", "ui/hud.tscn", "--to", "actors/player.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0 paths from res://ui/hud.tscn to res://actors/player.tscn\n");
+}
+
+#[test]
+fn deps_through_several_scenes() {
+    let dir = temp("deps-chains");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    let scenes = [
+        ("main.tscn", "[gd_scene load_steps=2 format=3]\n\n[ext_resource type=\"PackedScene\" path=\"res://dungeon.tscn\" id=\"1\"]\n\n[node name=\"Main\" type=\"Node\"]\n\n[node name=\"World\" parent=\".\" instance=ExtResource(\"1\")]\n"),
+        ("dungeon.tscn", "[gd_scene load_steps=2 format=3]\n\n[ext_resource type=\"PackedScene\" path=\"res://boss_room.tscn\" id=\"1\"]\n\n[node name=\"Dungeon\" type=\"Node\"]\n\n[node name=\"Rooms\" type=\"Node\" parent=\".\"]\n\n[node name=\"Boss\" parent=\"Rooms\" instance=ExtResource(\"1\")]\n\n[node name=\"Spawner\" type=\"Node\" parent=\".\"]\nroom = ExtResource(\"1\")\n"),
+        // leads back to the start, which mustn't loop forever
+        ("boss_room.tscn", "[gd_scene load_steps=2 format=3]\n\n[ext_resource type=\"PackedScene\" path=\"res://main.tscn\" id=\"1\"]\n\n[node name=\"BossRoom\" type=\"Node\"]\n\n[node name=\"Exit\" type=\"Node\" parent=\".\"]\nnext = ExtResource(\"1\")\n"),
+    ];
+    for (name, text) in scenes {
+        fs::write(dir.join(name), text).unwrap();
+    }
+    let out = stdout(&gdtree(&dir, &["deps", "--from", "main.tscn", "--to", "boss_room.tscn"]));
+    assert_eq!(
+        out,
+        "res://main.tscn [World] -> res://dungeon.tscn [Rooms/Boss] -> res://boss_room.tscn
+res://main.tscn [World] -> res://dungeon.tscn [Spawner:room] -> res://boss_room.tscn
+2 paths from res://main.tscn to res://boss_room.tscn
+"
+    );
+    let out = stdout(&gdtree(&dir, &["deps", "--from", "boss_room.tscn", "--to", "dungeon.tscn"]));
+    assert_eq!(out, "res://boss_room.tscn [Exit:next] -> res://main.tscn [World] -> res://dungeon.tscn\n1 path from res://boss_room.tscn to res://dungeon.tscn\n");
+}
+
+#[test]
+fn values() {
+    let dir = temp("values");
+    let scenes = [
+        ("a.tscn", "[gd_scene format=3]\n\n[node name=\"A\" type=\"Node2D\"]\ntexture_filter = 0\n\n[node name=\"B\" type=\"Sprite2D\" parent=\".\"]\ntexture_filter = 0\n\n[node name=\"C\" type=\"Sprite2D\" parent=\".\"]\ntexture_filter = 2\n"),
+        ("b.tscn", "[gd_scene format=3]\n\n[node name=\"D\" type=\"Node2D\"]\ntexture_filter = 0\n"),
+        ("c.tscn", "[gd_scene format=3]\n\n[node name=\"E\" type=\"Node2D\"]\n"),
+    ];
+    for (name, text) in scenes {
+        fs::write(dir.join(name), text).unwrap();
+    }
+    let out = stdout(&gdtree(&dir, &["values", "--key", "texture_filter"]));
+    assert_eq!(
+        out,
+        "value   nodes  scenes
+0           3       2
+2           1       1
+texture_filter set on 4 nodes in 2 of 3 scenes, 2 distinct values
+"
+    );
+    let output = gdtree(&dir, &["values", "--key", "z_index"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "z_index set on 0 nodes in 0 of 3 scenes, 0 distinct values\n");
+}
+
+#[test]
+fn find() {
+    let out = stdout(&gdtree(&fixture("reports"), &["find", ".", "*Timer"]));
+    assert_eq!(
+        out,
+        "res://actors/player.tscn
+  Cooldown (Timer)
+res://ui/hud.tscn
+  Blink (Timer)
+2 matching nodes in 4 scenes
+"
+    );
+    let out = stdout(&gdtree(&fixture("reports"), &["find", ".", "Player?"]));
+    assert_eq!(out, "res://arena.tscn\n  Player1\n  Player2\n2 matching nodes in 4 scenes\n");
+}
+
+#[test]
+fn find_nothing() {
+    let output = gdtree(&fixture("reports"), &["find", ".", "Nope"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "0 matching nodes in 4 scenes\n");
+}
+
+#[test]
+fn find_paths_only() {
+    let out = stdout(&gdtree(&fixture("reports"), &["find", "actors/player.tscn", "S*", "--paths-only"]));
+    assert_eq!(out, "Sprite\nHitbox/Shape\n");
+    let out = stdout(&gdtree(&fixture("reports"), &["find", ".", "*Timer", "--paths-only"]));
+    assert_eq!(out, "res://actors/player.tscn\tCooldown\nres://ui/hud.tscn\tBlink\n");
+    let output = gdtree(&fixture("reports"), &["find", ".", "Nope", "--paths-only"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+}
+
+/// The scenes of `stats --ndjson` with the given paging options, in order.
+fn page(args: &[&str]) -> Vec<String> {
+    let out = stdout(&gdtree(&fixture("reports"), &[&["stats", "--ndjson"], args].concat()));
+    out.lines()
+        .map(|line| {
+            let stats: serde_json::Value = serde_json::from_str(line).unwrap();
+            assert_eq!(stats["format_version"], 1);
+            stats["scene"].as_str().unwrap().to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn ndjson() {
+    let out = stdout(&gdtree(&fixture("reports"), &["stats", "--ndjson", "--limit", "1"]));
+    assert_eq!(
+        out,
+        "{\"format_version\":1,\"scene\":\"res://actors/player.tscn\",\"nodes\":5,\"size\":376,\"depth\":2,\"refs\":2,\"ext_resources\":1,\"sub_resources\":0,\"connections\":0,\"types\":{\"CharacterBody2D\":1,\"Sprite2D\":1,\"Area2D\":1,\"CollisionShape2D\":1,\"Timer\":1},\"owners\":[]}\n"
+    );
+    let output = gdtree(&fixture("reports"), &["stats", "--json", "--ndjson"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn paging() {
+    let all = ["res://actors/player.tscn", "res://arena.tscn", "res://main.tscn", "res://ui/hud.tscn"];
+    assert_eq!(page(&[]), all);
+    assert_eq!(page(&["--limit", "2"]), all[..2]);
+    assert_eq!(page(&["--limit", "2", "--offset", "1"]), all[1..3]);
+    assert_eq!(page(&["--limit", "2", "--offset", "3"]), all[3..]);
+    assert!(page(&["--offset", "4"]).is_empty());
+    // a cursor carries on after the scene it names
+    assert_eq!(page(&["--limit", "2", "--cursor", "res://actors/player.tscn"]), all[1..3]);
+    assert_eq!(page(&["--cursor", "res://main.tscn"]), all[3..]);
+    assert_eq!(stdout(&gdtree(&fixture("reports"), &["deps", "--offset", "3"])), "res://ui/hud.tscn\n");
+    // sorted pages, by a measure of the file, of the references or of the tree
+    assert_eq!(page(&["--sort-by", "size", "--limit", "2"]), ["res://actors/player.tscn", "res://main.tscn"]);
+    assert_eq!(page(&["--sort-by", "size", "--cursor", "res://main.tscn"]), ["res://arena.tscn", "res://ui/hud.tscn"]);
+    assert_eq!(page(&["--sort-by", "refs", "--limit", "2"]), ["res://actors/player.tscn", "res://ui/hud.tscn"]);
+    assert_eq!(page(&["--sort-by", "nodes", "--limit", "2"]), ["res://actors/player.tscn", "res://arena.tscn"]);
+
+    let output = gdtree(&fixture("reports"), &["stats", "--cursor", "res://gone.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "gdtree: the cursor res://gone.tscn is not among the scenes\n");
+    let output = gdtree(&fixture("reports"), &["stats", "--offset", "1", "--cursor", "res://main.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn deps_recursive() {
+    let out = stdout(&gdtree(&fixture("reports"), &["deps", "-r", "--kind", "scripts", "main.tscn"]));
+    assert_eq!(
+        out,
+        "res://main.tscn
+  res://actors/player.tscn (PackedScene)
+    res://actors/player.gd (Script)
+  res://ui/hud.tscn (PackedScene)
+"
+    );
+    let out = stdout(&gdtree(&fixture("reports"), &["deps", "-r", "--format", "dot", "main.tscn"]));
+    assert!(out.contains("    \"res://actors/player.tscn\" -> \"res://actors/player.gd\";\n"), "{}", out);
+}
+
+#[test]
+fn deps_recursive_cycle() {
+    let dir = temp("deps-cycle");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    let instancing = |other: &str| format!("[gd_scene load_steps=2 format=3]\n\n[ext_resource type=\"PackedScene\" path=\"res://{}.tscn\" id=\"1\"]\n\n[node name=\"Root\" type=\"Node\"]\n\n[node name=\"Other\" parent=\".\" instance=ExtResource(\"1\")]\n", other);
+    fs::write(dir.join("a.tscn"), instancing("b")).unwrap();
+    fs::write(dir.join("b.tscn"), instancing("a")).unwrap();
+    let out = stdout(&gdtree(&dir, &["deps", "-r", "a.tscn"]));
+    assert_eq!(out, "res://a.tscn\n  res://b.tscn (PackedScene)\n    res://a.tscn (cycle back up the chain)\n");
+}
+
+#[test]
+fn refs_skip_broken_scenes() {
+    let dir = copy("reports", "refs-broken");
+    fs::write(dir.join("broken.tscn"), "[gd_scene format=3]\n\n[node name=\"Broken\"\n").unwrap();
+    let output = gdtree(&dir, &["stats", "ui/hud.tscn"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stdout).contains("res://ui/hud.tscn       2   103 bytes      1     1"), "{:?}", output);
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("gdtree: skipping "), "{:?}", output);
+    // a scene asked about must parse
+    let output = gdtree(&dir, &["stats", "broken.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn page_skips_broken_scenes_off_it() {
+    let dir = copy("reports", "page-broken");
+    fs::write(dir.join("zzz.tscn"), "[gd_scene format=3]\n\n[node name=\"Broken\"\n").unwrap();
+    // the broken scene is only read for its references, so the page is fine
+    let output = gdtree(&dir, &["stats", "--ndjson", "--limit", "1"]);
+    assert!(output.status.success(), "{:?}", output);
+    assert_eq!(stdout(&output).lines().count(), 1);
+    assert!(String::from_utf8_lossy(&output.stderr).starts_with("gdtree: skipping "), "{:?}", output);
+    let output = gdtree(&dir, &["stats", "--ndjson", "--offset", "4"]);
+    assert_eq!(output.status.code(), Some(1));
+}
@@ -0,0 +1,33 @@
+use super::*;
+
+#[test]
+fn resolve() {
+    let output = gdtree(&fixture("resolve"), &["lint", "--rules", "rules", "main.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "main.tscn: .: . -> Panel/OK = Panel/OK [resolve]
+main.tscn: .: . -> . = . [resolve]
+main.tscn: .: Panel/OK/Icon -> .. = Panel/OK [resolve]
+main.tscn: .: Panel/OK/Icon -> ../../.. = . [resolve]
+main.tscn: .: Other -> %OK = Panel/OK [resolve]
+main.tscn: .: Other -> %OK/Icon = Panel/OK/Icon [resolve]
+main.tscn: .: Panel/OK/Icon -> ..:text = Panel/OK [resolve]
+main.tscn: .: . -> panel =  [resolve]
+main.tscn: .: . -> %Icon =  [resolve]
+main.tscn: .: . -> .. =  [resolve]
+main.tscn: .: . -> /root/Main/Panel =  [resolve]
+main.tscn: .: . -> Panel/Cancel =  [resolve]
+main.tscn: Panel: has no width or height, from its anchors and offsets, so draws nothing [ui]
+13 problems in 1 scenes checked by 2 rules
+"
+    );
+}
+
+#[test]
+fn bookmarks_follow_unique_names() {
+    let dir = copy("resolve", "resolve-bookmarks");
+    fs::write(dir.join("project.godot"), "config_version=5\n").unwrap();
+    stdout(&gdtree(&dir, &["bookmark", "add", "main.tscn", "ok", "%OK"]));
+    assert_eq!(stdout(&gdtree(&dir, &["--bookmark", "ok", "main.tscn"])), "OK\n│   * unique_name_in_owner: true\n│   * text: \"OK\"\n└── Icon (Sprite2D)\n        * back: NodePath(\"..:text\")\n");
+}
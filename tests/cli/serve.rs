@@ -0,0 +1,123 @@
+use std::io::{BufRead, BufReader, Write};
+use std::os::unix::net::UnixStream;
+use std::process::{Child, Stdio};
+use std::thread;
+use std::time::Duration;
+
+use super::*;
+
+/// A `gdtree serve` running on a project, killed when dropped.
+struct Server(Child);
+
+impl Server {
+    fn start(dir: &Path) -> Server {
+        let child = Command::new(env!("CARGO_BIN_EXE_gdtree")).arg("serve").current_dir(dir).stderr(Stdio::null()).spawn().unwrap();
+        let socket = dir.join(".gdtree/serve.sock");
+        for _ in 0..50 {
+            if UnixStream::connect(&socket).is_ok() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+        Server(child)
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Sends each request on one connection and returns the answers.
+fn ask(dir: &Path, requests: &[&str]) -> Vec<serde_json::Value> {
+    let stream = UnixStream::connect(dir.join(".gdtree/serve.sock")).unwrap();
+    let mut answers = BufReader::new(&stream).lines();
+    requests
+        .iter()
+        .map(|request| {
+            writeln!(&stream, "{}", request).unwrap();
+            serde_json::from_str(&answers.next().unwrap().unwrap()).unwrap()
+        })
+        .collect()
+}
+
+#[test]
+fn answers() {
+    let dir = copy("apply", "serve");
+    let _server = Server::start(&dir);
+    let answers = ask(&dir, &[r#"{"scene": "res://main.tscn"}"#, r#"{"scene": "res://gone.tscn"}"#, "{}", "nonsense"]);
+    assert_eq!(answers[0]["scene"]["root"]["name"], "Main");
+    assert!(answers[1]["error"].as_str().unwrap().starts_with("res://gone.tscn: "), "{}", answers[1]);
+    assert_eq!(answers[2]["error"], "bad request: expected {\"scene\": \"res://...\"}");
+    assert!(answers[3]["error"].as_str().unwrap().starts_with("bad request: "), "{}", answers[3]);
+
+    // --format json asks the server, and gets the same as parsing itself
+    let served = stdout(&gdtree(&dir, &["--format", "json", "main.tscn"]));
+    let parsed = stdout(&gdtree(&fixture("apply"), &["--format", "json", "main.tscn"]));
+    assert_eq!(served, parsed);
+}
+
+#[test]
+fn parses_a_changed_scene_again() {
+    let dir = copy("apply", "serve-changed");
+    let _server = Server::start(&dir);
+    let before = ask(&dir, &[r#"{"scene": "res://ui/menu.tscn"}"#]);
+    assert_eq!(before[0]["scene"]["root"]["name"], "Menu");
+    // far enough apart for the modification time to change
+    thread::sleep(Duration::from_millis(20));
+    let text = fs::read_to_string(dir.join("ui/menu.tscn")).unwrap();
+    fs::write(dir.join("ui/menu.tscn"), text.replace("name=\"Menu\"", "name=\"Options\"")).unwrap();
+    let after = ask(&dir, &[r#"{"scene": "res://ui/menu.tscn"}"#]);
+    assert_eq!(after[0]["scene"]["root"]["name"], "Options");
+}
+
+#[test]
+fn one_server_per_project() {
+    let dir = copy("apply", "serve-twice");
+    let _server = Server::start(&dir);
+    let output = gdtree(&dir, &["serve"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("a server is already running on "), "{:?}", output);
+}
+
+#[test]
+fn refuses_bad_requests() {
+    let dir = copy("apply", "serve-refuses");
+    let outside = temp("serve-outside");
+    fs::copy(dir.join("ui/menu.tscn"), outside.join("menu.tscn")).unwrap();
+    let _server = Server::start(&dir);
+    let answers = ask(
+        &dir,
+        &[
+            &format!(r#"{{"scene": "{}"}}"#, outside.join("menu.tscn").display()),
+            &format!(r#"{{"scene": "res://../gdtree-serve-outside-{}/menu.tscn"}}"#, std::process::id()),
+            r#"{"scene": "res://main.tscn", "format_version": 0}"#,
+            r#"{"scene": "res://main.tscn", "format_version": 4294967297}"#,
+            r#"{"scene": "res://main.tscn", "format_version": 1}"#,
+        ],
+    );
+    for answer in answers[..2].iter() {
+        assert!(answer["error"].as_str().unwrap().contains(": not in the project at "), "{}", answer);
+    }
+    assert!(answers[2]["error"].as_str().unwrap().starts_with("bad request: format_version must be from 1 to "), "{}", answers[2]);
+    assert!(answers[3]["error"].as_str().unwrap().ends_with(", not 4294967297"), "{}", answers[3]);
+    assert_eq!(answers[4]["scene"]["root"]["name"], "Main");
+}
+
+#[test]
+fn parses_again_when_an_instanced_scene_moves() {
+    let dir = copy("apply", "serve-moved");
+    let main = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    fs::write(dir.join("main.tscn"), main.replace("path=\"res://ui/menu.tscn\"", "uid=\"uid://bmenu\" path=\"res://ui/menu.tscn\"")).unwrap();
+    let menu = fs::read_to_string(dir.join("ui/menu.tscn")).unwrap();
+    fs::write(dir.join("ui/menu.tscn"), menu.replace("[gd_scene format=3]", "[gd_scene format=3 uid=\"uid://bmenu\"]")).unwrap();
+    let _server = Server::start(&dir);
+    let before = ask(&dir, &[r#"{"scene": "res://main.tscn"}"#]);
+    assert_eq!(before[0]["scene"]["ext_resources"][1]["path"], "res://ui/menu.tscn");
+    // main.tscn itself is left as it was; its uid leads to the moved menu
+    fs::rename(dir.join("ui/menu.tscn"), dir.join("ui/options.tscn")).unwrap();
+    let after = ask(&dir, &[r#"{"scene": "res://main.tscn"}"#]);
+    assert_eq!(after[0]["scene"]["ext_resources"][1]["path"], "res://ui/options.tscn");
+}
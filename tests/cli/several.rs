@@ -0,0 +1,46 @@
+use super::*;
+
+#[test]
+fn several_files() {
+    let output = gdtree(&fixture("physics"), &[".", "nope.tscn", "wall.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    let out = String::from_utf8_lossy(&output.stdout);
+    assert!(out.starts_with("==> ./player.tscn <==\nPlayer\n"), "{}", out);
+    assert!(out.contains("\n\n==> ./wall.tscn <==\nWall\n└── Shape (CollisionShape3D)\n\n==> nope.tscn <==\n\n==> wall.tscn <==\n"), "{}", out);
+    let err = String::from_utf8_lossy(&output.stderr);
+    assert!(err.starts_with("gdtree: nope.tscn: "), "{}", err);
+    assert!(err.ends_with("gdtree: 1 of 4 files could not be printed\n"), "{}", err);
+}
+
+#[test]
+fn empty_directory() {
+    let dir = temp("several-empty");
+    let output = gdtree(&dir, &["."]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no .tscn or .tres files found in ."));
+}
+
+#[test]
+fn watch_takes_one_scene() {
+    let output = gdtree(&fixture("physics"), &["--watch", "."]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--watch takes a single scene, not 2 files"));
+}
+
+#[test]
+fn reader_goes_away() {
+    for args in [&["."][..], &["--connections-only", "."], &["--draw-order", "."], &["--world-transforms", "."]] {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_gdtree"))
+            .args(args)
+            .current_dir(fixture("physics"))
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap();
+        // as head does once it has read enough
+        drop(child.stdout.take());
+        let output = child.wait_with_output().unwrap();
+        assert_eq!(output.status.code(), Some(0), "{:?}", args);
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "", "{:?}", args);
+    }
+}
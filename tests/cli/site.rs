@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn report() {
+    let out = temp("site");
+    let written = stdout(&gdtree(&fixture("apply"), &["report", "-o", out.to_str().unwrap()]));
+    assert_eq!(written, format!("wrote 5 pages to {}\n", out.display()));
+
+    let index = fs::read_to_string(out.join("index.html")).unwrap();
+    assert!(index.contains("<title>Apply</title>"), "{}", index);
+    assert!(index.contains("<p>2 scenes, 8 nodes, 3 lint problems</p>"), "{}", index);
+    assert!(index.contains("<tr><td><a href=\"scenes/ui/menu.tscn.html\">res://ui/menu.tscn</a></td><td class=\"number\">2</td>"), "{}", index);
+
+    let menu = fs::read_to_string(out.join("scenes/ui/menu.tscn.html")).unwrap();
+    assert!(menu.contains("<nav><a href=\"../../index.html\">Apply</a></nav>\n<h1>res://ui/menu.tscn</h1>"), "{}", menu);
+    assert!(menu.contains("<ul class=\"tree\">"), "{}", menu);
+
+    let lint = fs::read_to_string(out.join("lint.html")).unwrap();
+    assert!(lint.contains("<tr><td>Panel</td><td>has no width or height, from its anchors and offsets, so draws nothing</td><td>ui</td></tr>"), "{}", lint);
+
+    let deps = fs::read_to_string(out.join("deps.html")).unwrap();
+    assert!(deps.contains("<li><a href=\"#res://ui/menu.tscn\">res://ui/menu.tscn</a> <span class=\"type\">(PackedScene)</span></li>"), "{}", deps);
+    let dot = fs::read_to_string(out.join("deps.dot")).unwrap();
+    assert!(dot.contains("\"res://main.tscn\" -> \"res://ui/menu.tscn\";"), "{}", dot);
+}
+
+#[test]
+fn needs_a_project() {
+    let output = gdtree(&fixture("ui"), &["report", "-o", temp("site-no-project").to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no project.godot found above ., so pass --project-root"));
+}
@@ -0,0 +1,28 @@
+use super::*;
+
+#[test]
+fn gdscript() {
+    let out = stdout(&gdtree(&fixture("scenes"), &["--emit-snippet", "gdscript", "menu.tscn"]));
+    assert_eq!(
+        out,
+        "@onready var health_bar := $HealthBar
+@onready var buttons := $Buttons
+@onready var play := %Play
+@onready var buttons_label := $Buttons/Label
+@onready var footer := $Footer
+@onready var footer_label := $Footer/Label
+@onready var http_request := $HTTPRequest
+@onready var _2_lives := $\"2 Lives\"
+"
+    );
+}
+
+#[test]
+fn csharp() {
+    let out = stdout(&gdtree(&fixture("scenes"), &["--emit-snippet", "csharp", "menu.tscn"]));
+    assert!(out.starts_with("private ProgressBar HealthBar => GetNode<ProgressBar>(\"HealthBar\");\n"), "{}", out);
+    assert!(out.contains("private Button Play => GetNode<Button>(\"%Play\");\n"), "{}", out);
+    assert!(out.contains("private Label FooterLabel => GetNode<Label>(\"Footer/Label\");\n"), "{}", out);
+    assert!(out.contains("private HTTPRequest HttpRequest => GetNode<HTTPRequest>(\"HTTPRequest\");\n"), "{}", out);
+    assert!(out.ends_with("private Label _2Lives => GetNode<Label>(\"2 Lives\");\n"), "{}", out);
+}
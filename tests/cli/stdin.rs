@@ -0,0 +1,46 @@
+use std::io::Write;
+use std::process::Stdio;
+
+use super::*;
+
+/// Runs gdtree in `dir` with `input` on its stdin.
+fn gdtree_reading(dir: &Path, args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_gdtree"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn scene_from_stdin() {
+    // res:// paths resolve against the project of the current directory
+    let scene = fs::read_to_string(fixture("moved/main.tscn")).unwrap();
+    let out = stdout(&gdtree_reading(&fixture("moved"), &["-"], &scene));
+    assert_eq!(out, stdout(&gdtree(&fixture("moved"), &["main.tscn"])));
+}
+
+#[test]
+fn among_other_files() {
+    let scene = fs::read_to_string(fixture("physics/wall.tscn")).unwrap();
+    let out = stdout(&gdtree_reading(&fixture("physics"), &["wall.tscn", "-"], &scene));
+    assert_eq!(out, "==> wall.tscn <==\nWall\n└── Shape (CollisionShape3D)\n\n==> standard input <==\nWall\n└── Shape (CollisionShape3D)\n");
+}
+
+#[test]
+fn git_textconv_of_a_broken_scene() {
+    let broken = "[gd_scene format=3]\n\n[node name=\"Broken\"";
+    assert_eq!(stdout(&gdtree_reading(&fixture("physics"), &["--git-textconv", "-"], broken)), broken);
+}
+
+#[test]
+fn watch_needs_a_file() {
+    let output = gdtree_reading(&fixture("physics"), &["--watch", "-"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--watch needs the scene's file, not standard input"));
+}
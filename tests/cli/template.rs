@@ -0,0 +1,39 @@
+use super::*;
+
+#[test]
+fn new_from_template() {
+    let dir = copy("template", "template");
+    stdout(&gdtree(&dir, &["new", "--template", "enemy.tscn.tmpl", "--var", "name=Goblin", "--var", "health=3", "-o", "goblin.tscn"]));
+    assert_eq!(
+        fs::read_to_string(dir.join("goblin.tscn")).unwrap(),
+        "[gd_scene load_steps=2 format=3]
+
+[ext_resource type=\"Texture2D\" path=\"res://enemies/Goblin.png\" id=\"1\"]
+
+[node name=\"Goblin\" type=\"CharacterBody2D\"]
+health = 3
+
+[node name=\"Sprite\" type=\"Sprite2D\" parent=\".\"]
+texture = ExtResource(\"1\")
+"
+    );
+
+    // the last value of a name wins
+    let out = stdout(&gdtree(&dir, &["new", "--template", "enemy.tscn.tmpl", "--var", "name=Orc", "--var", "health=3", "--var", "health=5"]));
+    assert!(out.contains("[node name=\"Orc\" type=\"CharacterBody2D\"]\nhealth = 5\n"), "{}", out);
+}
+
+#[test]
+fn template_errors() {
+    for (vars, error) in [
+        (&[][..], "gdtree: enemy.tscn.tmpl: no value for name, health; pass --var NAME=VALUE\n"),
+        (&["--var", "name=Goblin", "--var", "health=[1"][..], "gdtree: enemy.tscn.tmpl: the rendered scene doesn't parse: line 8, column 1: expected `,` or `]`\n"),
+    ] {
+        let output = gdtree(&fixture("template"), &[&["new", "--template", "enemy.tscn.tmpl"], vars].concat());
+        assert_eq!(output.status.code(), Some(1));
+        assert_eq!(String::from_utf8_lossy(&output.stderr), error);
+    }
+    let output = gdtree(&fixture("template"), &["new", "--template", "enemy.tscn.tmpl", "--var", "=x"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("expected NAME=VALUE, not =x"));
+}
@@ -0,0 +1,23 @@
+use super::*;
+
+#[test]
+fn git_textconv() {
+    // the uids are left unresolved, as they would be in git's temporary copy
+    let out = stdout(&gdtree(&fixture("moved"), &["--git-textconv", "main.tscn"]));
+    assert_eq!(
+        out,
+        "Main
+├── Icon (Sprite2D)
+│       * texture: res://icon.png
+└── HUD
+        * (PackedScene) res://hud.tscn
+"
+    );
+}
+
+#[test]
+fn git_textconv_of_a_broken_scene() {
+    let out = stdout(&gdtree(&fixture("values"), &["--git-textconv", "unclosed.tscn"]));
+    assert_eq!(out, fs::read_to_string(fixture("values/unclosed.tscn")).unwrap());
+    assert!(!gdtree(&fixture("values"), &["unclosed.tscn"]).status.success());
+}
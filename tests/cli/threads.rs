@@ -0,0 +1,24 @@
+use super::*;
+
+#[test]
+fn same_output_on_any_number_of_threads() {
+    let one = stdout(&gdtree(&fixture("reports"), &["stats", "--threads", "1"]));
+    assert_eq!(stdout(&gdtree(&fixture("reports"), &["stats", "--threads", "4"])), one);
+    assert_eq!(stdout(&gdtree(&fixture("reports"), &["stats"])), one);
+}
+
+#[test]
+fn scenes_timed_in_order() {
+    let output = gdtree(&fixture("reports"), &["stats", "--threads", "2", "--timings"]);
+    let err = String::from_utf8_lossy(&output.stderr);
+    let parses: Vec<&str> = err.lines().filter_map(|line| line.split_whitespace().nth(1).filter(|_| line.starts_with("parse "))).collect();
+    let names: Vec<&str> = parses.iter().map(|p| p.rsplit("reports/").next().unwrap()).collect();
+    // once for the references and once for the page, in order both times
+    assert_eq!(names, ["actors/player.tscn", "arena.tscn", "main.tscn", "ui/hud.tscn"].repeat(2));
+}
+
+#[test]
+fn at_least_one_thread() {
+    let output = gdtree(&fixture("reports"), &["stats", "--threads", "0"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,43 @@
+use super::*;
+
+/// The step names --timings printed, in order.
+fn steps(output: &Output) -> Vec<String> {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let mut lines = stderr.lines();
+    assert_eq!(lines.next(), Some(""));
+    lines
+        .map(|line| {
+            assert!(line.ends_with(" ms"), "{}", line);
+            line.rsplitn(3, ' ').nth(2).unwrap().trim_end().to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn timings() {
+    let dir = fixture("reports");
+    let output = gdtree(&dir, &["--timings", "main.tscn"]);
+    assert_eq!(stdout(&output), stdout(&gdtree(&dir, &["main.tscn"])));
+    assert_eq!(steps(&output), ["load plugins", "resolve project", "parse", "filter", "render", "other", "total"]);
+}
+
+#[test]
+fn per_scene() {
+    let dir = fixture("reports");
+    let output = gdtree(&dir, &["find", "--timings", ".", "Nope"]);
+    // the exit status is kept
+    assert_eq!(output.status.code(), Some(1));
+    let steps = steps(&output);
+    // every scene is read for its references, and then those on the page in full
+    assert_eq!(steps.len(), 12, "{:?}", steps);
+    assert!(steps[1].starts_with("parse ") && steps[1].ends_with("player.tscn"), "{:?}", steps);
+    assert_eq!(steps[5], "sort");
+    assert!(steps[6].starts_with("parse ") && steps[6].ends_with("player.tscn"), "{:?}", steps);
+    assert_eq!(steps[10..], ["other", "total"]);
+}
+
+#[test]
+fn off_by_default() {
+    let output = gdtree(&fixture("reports"), &["main.tscn"]);
+    assert!(output.stderr.is_empty());
+}
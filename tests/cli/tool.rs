@@ -0,0 +1,21 @@
+use super::*;
+
+#[test]
+fn tool_scripts() {
+    let out = stdout(&gdtree(&fixture("tool"), &["--tool-scripts", "main.tscn"]));
+    assert_eq!(
+        out,
+        "Main [tool]
+│   * script: res://editor.gd
+├── Old (Node2D) [tool]
+│       * script: res://old.gd
+├── Gizmo (Node3D) [tool]
+│       * script: res://Gizmo.cs
+├── Plain (Node)
+│       * script: res://plain.gd
+└── Bare (Node)
+"
+    );
+    let out = stdout(&gdtree(&fixture("tool"), &["main.tscn"]));
+    assert!(!out.contains("[tool]"), "{}", out);
+}
@@ -0,0 +1,31 @@
+use super::*;
+
+#[test]
+fn resource_tree() {
+    let out = stdout(&gdtree(&fixture("tres"), &["theme.tres"]));
+    assert_eq!(
+        out,
+        "Theme
+    * default_font_size: 16
+    * Button
+              └── styles
+                  └── normal: StyleBoxFlat
+                      ├── bg_color: Color(0.1, 0.1, 0.1, 1)
+                      ├── corner_radius_top_left: 4
+                      └── content_margin_left: 8.0
+    * Label
+             └── label_settings: LabelSettings
+                 ├── font: FontVariation
+                 │   └── variation_embolden: 0.5
+                 ├── font_size: 24
+                 └── shadow_color: Color(0, 0, 0, 0.5)
+"
+    );
+}
+
+#[test]
+fn resource_with_previews() {
+    let out = stdout(&gdtree(&fixture("tres"), &["material.tres"]));
+    assert!(out.starts_with("ShaderMaterial\n    * shader: res://glow.gdshader\n"), "{}", out);
+    assert!(out.contains("└── ramp: GradientTexture1D of Gradient ["), "{}", out);
+}
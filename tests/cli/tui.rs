@@ -0,0 +1,199 @@
+use std::io::Write;
+use std::process::Stdio;
+use std::thread;
+use std::time::Duration;
+
+use super::*;
+
+const COLUMNS: usize = 100;
+const ROWS: usize = 24;
+
+/// Runs `gdtree tui` in `dir` on a pseudo-terminal, typing each of `keys` in
+/// turn, and returns its exit status and the screen it left behind.
+fn tui(dir: &Path, file: &str, keys: &[&str]) -> (Option<i32>, Vec<String>) {
+    let command = format!("stty cols {} rows {}; exec '{}' tui '{}'", COLUMNS, ROWS, env!("CARGO_BIN_EXE_gdtree"), file);
+    let mut child = Command::new("script")
+        .args(["-qfec", &command, "/dev/null"])
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    // until the explorer has switched the terminal to raw mode, keys would
+    // wait for a newline
+    thread::sleep(Duration::from_millis(800));
+    for key in keys {
+        stdin.write_all(key.as_bytes()).unwrap();
+        stdin.flush().unwrap();
+        thread::sleep(Duration::from_millis(100));
+    }
+    let output = child.wait_with_output().unwrap();
+    (output.status.code(), screen(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Plays what a program wrote to the terminal onto a blank screen, following
+/// the cursor moves and ignoring colors and modes.
+fn screen(written: &str) -> Vec<String> {
+    let mut cells = vec![vec![' '; COLUMNS]; ROWS];
+    let (mut row, mut col) = (0, 0);
+    let mut chars = written.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1b' => match chars.next() {
+                Some('[') => {
+                    let mut params = String::new();
+                    let mut end = ' ';
+                    for c in chars.by_ref() {
+                        if ('\x40'..='\x7e').contains(&c) {
+                            end = c;
+                            break;
+                        }
+                        params.push(c);
+                    }
+                    if end == 'H' {
+                        let mut at = params.split(';').map(|n| n.parse::<usize>().unwrap_or(1));
+                        row = at.next().unwrap_or(1).saturating_sub(1);
+                        col = at.next().unwrap_or(1).saturating_sub(1);
+                    }
+                }
+                _ => continue,
+            },
+            '\r' => col = 0,
+            '\n' => row += 1,
+            c => {
+                if row < ROWS && col < COLUMNS {
+                    cells[row][col] = c;
+                }
+                col += 1;
+            }
+        }
+    }
+    cells.into_iter().map(|line| line.into_iter().collect::<String>().trim_end().to_string()).collect()
+}
+
+/// The tree pane's lines, without its border.
+fn tree(screen: &[String]) -> Vec<String> {
+    screen[1..ROWS - 2].iter().map(|line| line.chars().skip(1).take(COLUMNS / 2 - 2).collect::<String>().trim_end().to_string()).filter(|line| !line.is_empty()).collect()
+}
+
+/// The details pane's lines, without its border.
+fn details(screen: &[String]) -> Vec<String> {
+    screen[1..ROWS - 2].iter().map(|line| line.chars().skip(COLUMNS / 2 + 1).take(COLUMNS / 2 - 2).collect::<String>().trim_end().to_string()).collect()
+}
+
+const UP: &str = "\x1b[A";
+const DOWN: &str = "\x1b[B";
+const LEFT: &str = "\x1b[D";
+
+#[test]
+fn explore() {
+    let (status, screen) = tui(&fixture("apply"), "main.tscn", &["q"]);
+    assert_eq!(status, Some(0));
+    assert_eq!(tree(&screen), ["▾ Main (Node)", "  ▾ Panel (Control)", "    ▾ OK (Button)", "        Icon (Sprite2D)", "    Other (Node)", "    Menu * res://ui/menu.tscn"]);
+    let details = details(&screen);
+    assert_eq!(
+        details[..9],
+        [".", "type: Node", "", "properties", "  script = res://main.gd", "  target = NodePath(\"Panel/OK\")", "", "connections out", "  pressed() => Main:_on_ok_pressed() [no node"]
+    );
+    assert!(screen[ROWS - 1].starts_with("↑↓ move  ←→ fold"), "{:?}", screen);
+}
+
+#[test]
+fn move_and_fold() {
+    let (_, screen) = tui(&fixture("apply"), "main.tscn", &[DOWN, DOWN, "\r", UP, LEFT, "q"]);
+    assert_eq!(tree(&screen), ["▾ Main (Node)", "  ▸ Panel (Control)", "    Other (Node)", "    Menu * res://ui/menu.tscn"]);
+    assert_eq!(details(&screen)[..4], ["Panel", "type: Control", "", "properties"]);
+}
+
+#[test]
+fn search() {
+    let (_, screen) = tui(&fixture("apply"), "main.tscn", &["/", "ic", "\r", "q"]);
+    assert_eq!(details(&screen)[..2], ["Panel/OK/Icon", "type: Sprite2D"]);
+    // OK, Icon and Other match; n goes round from the last to the first, N the other way
+    let (_, screen) = tui(&fixture("apply"), "main.tscn", &["/", "o", "\r", "n", "n", "q"]);
+    assert_eq!(details(&screen)[0], "Other");
+    let (_, screen) = tui(&fixture("apply"), "main.tscn", &["/", "o", "\r", "n", "n", "n", "q"]);
+    assert_eq!(details(&screen)[0], "Panel/OK");
+    let (_, screen) = tui(&fixture("apply"), "main.tscn", &["/", "o", "\r", "N", "q"]);
+    assert_eq!(details(&screen)[0], "Other");
+    let (_, screen) = tui(&fixture("apply"), "main.tscn", &["/", "zzz", "\r", "q"]);
+    assert!(screen[ROWS - 1].starts_with("no node matches zzz"), "{:?}", screen);
+}
+
+#[test]
+fn open_instance() {
+    let (_, screen) = tui(&fixture("apply"), "main.tscn", &["G", "i", "q"]);
+    assert_eq!(screen[ROWS - 1], "opened res://ui/menu.tscn");
+    assert_eq!(tree(&screen)[5..], ["  ▾ Menu (VBoxContainer)", "      Sound (CheckButton)"]);
+    let (_, screen) = tui(&fixture("apply"), "main.tscn", &["i", "q"]);
+    assert_eq!(screen[ROWS - 1], "not an instance");
+}
+
+#[test]
+fn needs_a_terminal() {
+    let output = gdtree(&fixture("apply"), &["tui", "main.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "gdtree: gdtree tui needs a terminal\n");
+}
+
+#[test]
+fn rename() {
+    let dir = copy("apply", "tui-rename");
+    let (_, screen) = tui(&dir, "main.tscn", &[DOWN, DOWN, "r", "\x7f", "\x7f", "Confirm", "\r", "q"]);
+    assert_eq!(screen[ROWS - 1], "renamed Panel/OK to Confirm (6 lines); u to undo");
+    assert_eq!(tree(&screen)[2], "    ▾ Confirm (Button)");
+    let after = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    assert!(after.contains("[node name=\"Confirm\" type=\"Button\" parent=\"Panel\"]"), "{}", after);
+    assert!(after.contains("[node name=\"Icon\" type=\"Sprite2D\" parent=\"Panel/Confirm\"]"), "{}", after);
+    assert!(after.contains("from=\"Panel/Confirm\""), "{}", after);
+    assert!(after.contains("target = NodePath(\"Panel/Confirm\")"), "{}", after);
+}
+
+#[test]
+fn visible_and_undo() {
+    let dir = copy("apply", "tui-visible");
+    let before = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    let (_, screen) = tui(&dir, "main.tscn", &[DOWN, "v", "q"]);
+    assert_eq!(screen[ROWS - 1], "Panel: visible = false; u to undo");
+    assert!(fs::read_to_string(dir.join("main.tscn")).unwrap().contains("[node name=\"Panel\" type=\"Control\" parent=\".\"]\nlayout_mode = 3\nvisible = false\n"));
+    let (_, screen) = tui(&dir, "main.tscn", &[DOWN, "v", "u", "q"]);
+    assert_eq!(screen[ROWS - 1], "undid Panel: visible = true");
+    assert_eq!(fs::read_to_string(dir.join("main.tscn")).unwrap().replace("visible = false\n", ""), before);
+    let (_, screen) = tui(&dir, "main.tscn", &["u", "q"]);
+    assert_eq!(screen[ROWS - 1], "nothing to undo");
+}
+
+#[test]
+fn delete() {
+    let dir = copy("apply", "tui-delete");
+    let before = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    let (_, screen) = tui(&dir, "main.tscn", &[DOWN, DOWN, "d", "n", "q"]);
+    assert_eq!(screen[ROWS - 1], "not deleted");
+    assert_eq!(fs::read_to_string(dir.join("main.tscn")).unwrap(), before);
+    let (_, screen) = tui(&dir, "main.tscn", &["d", "q"]);
+    assert_eq!(screen[ROWS - 1], "the root can't be deleted");
+    let (_, screen) = tui(&dir, "main.tscn", &[DOWN, DOWN, "d", "y", "q"]);
+    assert_eq!(screen[ROWS - 1], "deleted Panel/OK (2 nodes); u to undo");
+    assert_eq!(tree(&screen), ["▾ Main (Node)", "    Panel (Control)", "    Other (Node)", "    Menu * res://ui/menu.tscn"]);
+    let after = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    assert!(!after.contains("name=\"OK\"") && !after.contains("name=\"Icon\""), "{}", after);
+}
+
+#[test]
+fn only_canvas_items_and_node_3ds_hide() {
+    let dir = copy("apply", "tui-visible-types");
+    let before = fs::read_to_string(dir.join("main.tscn")).unwrap();
+    let (_, screen) = tui(&dir, "main.tscn", &["G", UP, "v", "q"]);
+    assert_eq!(screen[ROWS - 1], "Other is not a CanvasItem or Node3D, so it can't be hidden");
+    assert_eq!(fs::read_to_string(dir.join("main.tscn")).unwrap(), before);
+    // an instance goes by the root of its scene, a VBoxContainer
+    let (_, screen) = tui(&dir, "main.tscn", &["G", "v", "q"]);
+    assert_eq!(screen[ROWS - 1], "Menu: visible = false; u to undo");
+}
+
+#[test]
+fn values_over_several_lines() {
+    let (_, screen) = tui(&fixture("values"), "shader.tscn", &["q"]);
+    assert_eq!(details(&screen)[3..5], ["properties", "  meta = { … (3 more lines)"]);
+}
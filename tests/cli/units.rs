@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn angles_in_degrees() {
+    let out = stdout(&gdtree(&fixture("units"), &["--units", "main.tscn"]));
+    assert_eq!(
+        out,
+        "Main
+│   * rotation: 1.5708 (90°)
+│   * skew: -0.0 (0°)
+├── Icon (Sprite2D)
+│       * texture: res://icon.png
+└── Arm (Node3D)
+        * rotation: Vector3(0, 3.14159, -0.785398) (0°, 180°, -45°)
+        * position: Vector3(1, 2, 3)
+"
+    );
+}
+
+#[test]
+fn human_sizes() {
+    let out = stdout(&gdtree(&fixture("units"), &["--units", "--check-files", "main.tscn"]));
+    assert!(out.contains("* texture: res://icon.png [1.5 KiB, modified "), "{}", out);
+    let out = stdout(&gdtree(&fixture("units"), &["--check-files", "main.tscn"]));
+    assert!(out.contains("* texture: res://icon.png [1536 bytes, modified "), "{}", out);
+}
@@ -0,0 +1,20 @@
+use super::*;
+
+#[test]
+fn values_over_several_lines() {
+    let out = stdout(&gdtree(&fixture("values"), &["shader.tscn"]));
+    assert_eq!(out, "Main\n    * meta: { … (3 more lines)\n    * text: \"say \\\"hi\\\" [ok]\"\n");
+
+    // JSON has all of it
+    let out = stdout(&gdtree(&fixture("values"), &["--format", "json", "shader.tscn"]));
+    let scene: serde_json::Value = serde_json::from_str(&out).unwrap();
+    assert_eq!(scene["root"]["properties"][0]["value"], "{\n\"a\": [1, 2],\n\"b\": \"x\\\"]y\"\n}");
+    assert_eq!(scene["sub_resources"][0]["properties"][0]["value"], "\"shader_type canvas_item;\nvoid fragment() {\n\tCOLOR = vec4(1.0);\n}\n\"");
+}
+
+#[test]
+fn syntax_errors() {
+    let output = gdtree(&fixture("values"), &["unclosed.tscn"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(String::from_utf8_lossy(&output.stderr), "gdtree: line 5, column 1: the node section header is never closed\n");
+}
@@ -0,0 +1,36 @@
+use super::*;
+
+#[test]
+fn from_the_header() {
+    let out = stdout(&gdtree(&fixture("godot3"), &["--detect-version", "hud.tscn"]));
+    assert_eq!(out, "Godot 3.x\n  format=2\n  Godot 3 property names: rect_min_size, margin_right, custom_colors/font_color\n");
+}
+
+#[test]
+fn from_a_uid() {
+    let out = stdout(&gdtree(&fixture("version"), &["--detect-version", "uid.tscn"]));
+    assert_eq!(out, "Godot 4.x\n  uid=\"uid://b7aqhcq8o7fcm\"\n  Godot 4 class names: Node3D\n");
+}
+
+#[test]
+fn from_the_names_in_use() {
+    let out = stdout(&gdtree(&fixture("version"), &["--detect-version", "headerless.tscn"]));
+    assert_eq!(
+        out,
+        "Godot 3.x
+  Godot 3 class names: Spatial
+  Godot 3 property names: rect_min_size
+  Godot 4 property names: layout_mode
+"
+    );
+}
+
+#[test]
+fn settings_read_by_version() {
+    let out = stdout(&gdtree(&fixture("version"), &["old_world.tscn"]));
+    assert!(out.contains("└── tonemap: mode aces fitted\n"), "{}", out);
+    assert!(out.contains("* shape: CapsuleShape2D radius 10, height 20\n"), "{}", out);
+    let out = stdout(&gdtree(&fixture("version"), &["new_world.tscn"]));
+    assert!(out.contains("└── tonemap: mode agx\n"), "{}", out);
+    assert!(out.contains("* shape: CapsuleShape2D radius 10, height 30\n"), "{}", out);
+}
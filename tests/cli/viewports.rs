@@ -0,0 +1,18 @@
+use super::*;
+
+#[test]
+fn viewports() {
+    let out = stdout(&gdtree(&fixture("viewports"), &["--viewports", "level.tscn"]));
+    assert_eq!(
+        out,
+        "node                       type                  settings
+Cam                        Camera3D              current=true, fov=60.0
+Other                      Camera3D              current=true
+Container                  SubViewportContainer  (defaults)
+Container/Minimap          SubViewport           size=Vector2i(256, 256), render_target_update_mode=4
+Container/Minimap/TopDown  Camera3D              current=true, projection=1
+HUD                        CanvasLayer           layer=2
+warning: 2 3D cameras are marked current: Cam, Other
+"
+    );
+}
@@ -0,0 +1,172 @@
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Stdio};
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::Duration;
+
+use super::*;
+
+/// gdtree running in the background, with its output arriving line by line.
+struct Watching {
+    child: Child,
+    lines: Receiver<String>,
+}
+
+impl Watching {
+    fn start(dir: &Path, args: &[&str]) -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_gdtree"))
+            .args(args)
+            .current_dir(dir)
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        let (send, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines() {
+                if send.send(line.unwrap()).is_err() {
+                    break;
+                }
+            }
+        });
+        Self { child, lines }
+    }
+
+    /// Saves the scene once the watcher has had time to look at the previous version.
+    fn save(&self, path: &Path, text: &str) {
+        thread::sleep(Duration::from_millis(500));
+        fs::write(path, text).unwrap();
+    }
+
+    /// The next `n` lines printed.
+    fn read(&self, n: usize) -> Vec<String> {
+        (0..n).map(|_| self.lines.recv_timeout(Duration::from_secs(10)).unwrap()).collect()
+    }
+}
+
+impl Drop for Watching {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+const BEFORE: &str = "[gd_scene format=3]
+
+[node name=\"Main\" type=\"Node\"]
+
+[node name=\"Timer\" type=\"Timer\" parent=\".\"]
+wait_time = 2.0
+
+[node name=\"Old\" type=\"Node\" parent=\".\"]
+
+[connection signal=\"timeout\" from=\"Timer\" to=\".\" method=\"_on_timeout\"]
+";
+
+const AFTER: &str = "[gd_scene format=3]
+
+[node name=\"Main\" type=\"Node\"]
+
+[node name=\"Timer\" type=\"Timer\" parent=\".\"]
+wait_time = 5.0
+autostart = true
+
+[node name=\"New\" type=\"Label\" parent=\".\"]
+";
+
+#[test]
+fn reprints_on_save() {
+    let dir = temp("watch");
+    fs::write(dir.join("main.tscn"), BEFORE).unwrap();
+    let watching = Watching::start(&dir, &["--watch", "main.tscn"]);
+    assert_eq!(watching.read(1), ["Main"]);
+    watching.read(4);
+
+    watching.save(&dir.join("main.tscn"), AFTER);
+    let lines = watching.read(7);
+    assert_eq!(lines[0], "");
+    assert!(lines[1].starts_with("== ") && lines[1].ends_with(" UTC =="), "{:?}", lines);
+    assert_eq!(lines[2..], ["Main", "├── Timer", "│       * wait_time: 5.0", "│       * autostart: true", "└── New (Label)"]);
+}
+
+#[test]
+fn reprints_when_saved_over() {
+    // editors that save by writing a new file and renaming it over the scene
+    let dir = temp("watch-rename");
+    fs::write(dir.join("main.tscn"), BEFORE).unwrap();
+    let watching = Watching::start(&dir, &["--watch", "main.tscn"]);
+    watching.read(5);
+
+    watching.save(&dir.join("main.tscn.tmp"), AFTER);
+    fs::rename(dir.join("main.tscn.tmp"), dir.join("main.tscn")).unwrap();
+    assert_eq!(watching.read(7)[2..], ["Main", "├── Timer", "│       * wait_time: 5.0", "│       * autostart: true", "└── New (Label)"]);
+}
+
+#[test]
+fn diff() {
+    let dir = temp("watch-diff");
+    fs::write(dir.join("main.tscn"), BEFORE).unwrap();
+    let watching = Watching::start(&dir, &["--watch", "--diff", "main.tscn"]);
+    watching.read(5);
+
+    watching.save(&dir.join("main.tscn"), AFTER);
+    let lines = watching.read(7);
+    assert_eq!(
+        lines[2..],
+        [
+            "- Old (Node)",
+            "~ Timer: wait_time: 2.0 → 5.0",
+            "+ Timer: autostart: true",
+            "+ New (Label)",
+            "- connection: Timer:timeout() => Main:_on_timeout()",
+        ]
+    );
+
+    watching.save(&dir.join("main.tscn"), &format!("{}\n", AFTER));
+    assert_eq!(watching.read(3)[2], "no changes");
+}
+
+#[test]
+fn diff_needs_watch() {
+    let output = gdtree(&fixture("scenes"), &["--diff", "menu.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn diff_summary() {
+    let dir = temp("watch-summary");
+    fs::write(dir.join("main.tscn"), BEFORE).unwrap();
+    let watching = Watching::start(&dir, &["--watch", "--diff", "--summary", "main.tscn"]);
+    watching.read(5);
+
+    watching.save(&dir.join("main.tscn"), AFTER);
+    assert_eq!(
+        watching.read(3)[2],
+        "Added 1 node under the root, removed 1 node under the root, changed 2 properties on Timer, removed 1 connection"
+    );
+
+    // a new subtree counts toward the node it was added under
+    let nested = format!("{}\n[node name=\"Box\" type=\"Node\" parent=\"New\"]\n\n[node name=\"Icon\" type=\"Sprite2D\" parent=\"New/Box\"]\n", AFTER);
+    watching.save(&dir.join("main.tscn"), &nested);
+    assert_eq!(watching.read(3)[2], "Added 2 nodes under New");
+
+    watching.save(&dir.join("main.tscn"), &format!("{}\n", nested));
+    assert_eq!(watching.read(3)[2], "No changes");
+}
+
+#[test]
+fn summary_needs_diff() {
+    let output = gdtree(&fixture("scenes"), &["--watch", "--summary", "menu.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn keeps_the_filter() {
+    let dir = temp("watch-filter");
+    fs::write(dir.join("main.tscn"), BEFORE).unwrap();
+    let watching = Watching::start(&dir, &["--watch", "--filter", "Tim*", "main.tscn"]);
+    assert_eq!(watching.read(4)[..3], ["Main", "└── Timer", "        * wait_time: 2.0"]);
+
+    watching.save(&dir.join("main.tscn"), AFTER);
+    assert_eq!(watching.read(6)[2..], ["Main", "└── Timer", "        * wait_time: 5.0", "        * autostart: true"]);
+}
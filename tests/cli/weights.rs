@@ -0,0 +1,47 @@
+use super::*;
+
+#[test]
+fn weights() {
+    let out = stdout(&gdtree(&fixture("preview"), &["--weights", "level.tscn"]));
+    assert!(out.starts_with("Level [35 bytes, 1.3 KiB with children]\n├── Wall (CollisionShape2D) [190 bytes]\n"), "{}", out);
+    assert!(out.contains("└── Floor (NavigationRegion2D) [379 bytes]\n"), "{}", out);
+}
+
+#[test]
+fn shared_sub_resources_count_for_each_node() {
+    let dir = temp("weights");
+    let shape = "[sub_resource type=\"CircleShape2D\" id=\"1\"]\nradius = 4.0\n\n";
+    let node = |name: &str| format!("[node name=\"{}\" type=\"CollisionShape2D\" parent=\".\"]\nshape = SubResource(\"1\")\n\n", name);
+    let scene = format!("[gd_scene format=3]\n\n{}[node name=\"Coins\" type=\"Node2D\"]\n\n{}{}", shape, node("A"), node("B"));
+    fs::write(dir.join("coins.tscn"), scene).unwrap();
+    let out = stdout(&gdtree(&dir, &["--weights", "coins.tscn"]));
+    assert_eq!(
+        out,
+        "Coins [35 bytes, 303 bytes with children]
+├── A (CollisionShape2D) [134 bytes]
+│       * shape: CircleShape2D radius 4
+└── B (CollisionShape2D) [134 bytes]
+        * shape: CircleShape2D radius 4
+"
+    );
+}
+
+#[test]
+fn top() {
+    let out = stdout(&gdtree(&fixture("preview"), &["--weights", "--top", "3", "level.tscn"]));
+    assert_eq!(
+        out,
+        "      size   share  node
+ 379 bytes   27.7%  Floor (NavigationRegion2D)
+ 229 bytes   16.7%  Ramp (CollisionShape2D)
+ 192 bytes   14.0%  Door (CollisionShape2D)
+1.3 KiB of 1.3 KiB in nodes
+"
+    );
+}
+
+#[test]
+fn top_needs_weights() {
+    let output = gdtree(&fixture("preview"), &["--top", "3", "level.tscn"]);
+    assert_eq!(output.status.code(), Some(2));
+}
@@ -0,0 +1,32 @@
+use super::*;
+
+#[test]
+fn world_transforms() {
+    let out = stdout(&gdtree(&fixture("world"), &["--world-transforms", "level.tscn"]));
+    assert_eq!(
+        out,
+        "         x           y           z    distance  node
+      0.00        0.00        0.00        0.00  Level (Node3D)
+     10.00        0.00        0.00       10.00  Arm (Node3D)
+      8.00        0.00        0.00        8.00  Arm/Hand (MeshInstance3D)
+   5008.00        0.00        0.00     5008.00  Arm/Hand/Lost (Marker3D)
+      0.00        3.00        0.00        3.00  Arm/Fx (GPUParticles3D)
+     10.00        1.00        0.00       10.05  Arm/Tree
+      1.00        2.00        2.00        3.00  Logic/Spawn (Marker3D)
+7 3D nodes
+"
+    );
+}
+
+#[test]
+fn godot_3_transforms() {
+    let out = stdout(&gdtree(&fixture("world"), &["--world-transforms", "old.tscn"]));
+    assert_eq!(
+        out,
+        "         x           y           z    distance  node
+      3.00        4.00        0.00        5.00  Root (Spatial)
+      3.00        4.00        0.00        5.00  Light (OmniLight)
+2 3D nodes
+"
+    );
+}
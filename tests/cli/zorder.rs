@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn draw_order() {
+    let out = stdout(&gdtree(&fixture("zorder"), &["--draw-order", "level.tscn"]));
+    assert_eq!(
+        out,
+        "layer      z  node
+ -100      0  Bg/Sky (Sprite2D)
+    0     -1  World/Player/Shadow (Sprite2D) [z_as_relative off]
+    0      0  Level (Node2D)
+    0      0  World/Logic/Marker (Marker2D)
+    0      1  World (Node2D)
+    0      1  World/Coin
+    0      3  World/Player (CharacterBody2D)
+    1      4  HUD/Score (Label)
+8 canvas items
+"
+    );
+}
+
+#[test]
+fn draw_order_from_the_class_reference() {
+    let out = stdout(&gdtree(&fixture("describe"), &["--draw-order", "hud.tscn"]));
+    assert_eq!(out, "layer      z  node\n    0      0  HUD (Control)\n    0      0  Score (Label)\n2 canvas items\n");
+}
@@ -0,0 +1,7 @@
+extends Node
+
+func _process(_delta):
+	if Input.is_action_just_pressed("pause"):
+		get_tree().paused = true
+	if Input.is_action_pressed(&"ui_accept"):
+		pass
@@ -0,0 +1,10 @@
+extends CharacterBody2D
+
+func _physics_process(_delta):
+	velocity.x = Input.get_axis("left", "right") * 200.0
+	if Input.is_action_pressed("jump"):  # Input.is_action_pressed("fly")
+		velocity.y = -400.0
+	var event = InputEventAction.new()
+	event.action = "dash"
+	if Input.get_action_strength("dash") > 0.5:
+		pass
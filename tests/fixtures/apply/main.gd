@@ -0,0 +1,4 @@
+extends Node
+
+func _on_ok_pressed():
+	pass
@@ -0,0 +1,5 @@
+extends Node
+
+signal died
+signal won
+signal paused
@@ -0,0 +1,5 @@
+extends Control
+
+func _ready():
+	Events.died.connect(_on_died)
+	Events.connect("won", _on_won)
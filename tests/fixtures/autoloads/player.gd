@@ -0,0 +1,4 @@
+extends CharacterBody2D
+
+func die():
+	Events.died.emit()
@@ -0,0 +1 @@
+extends Node
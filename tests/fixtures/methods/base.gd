@@ -0,0 +1,5 @@
+class_name Hinged
+extends Node2D
+
+func _on_opened():
+	pass
@@ -0,0 +1,4 @@
+extends Hinged
+
+static func _on_locked():
+	pass
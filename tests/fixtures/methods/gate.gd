@@ -0,0 +1 @@
+extends "res://base.gd"
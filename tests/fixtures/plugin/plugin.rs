@@ -0,0 +1,57 @@
+//! A plugin with every hook, built by the plugin tests.
+
+use std::ffi::{c_char, CStr, CString};
+
+#[repr(C)]
+pub struct PluginV1 {
+    abi_version: u32,
+    name: *const c_char,
+    render: Option<unsafe extern "C" fn(*const c_char) -> *mut c_char>,
+    decode: Option<unsafe extern "C" fn(*const c_char, *const c_char) -> *mut c_char>,
+    lint: Option<unsafe extern "C" fn(*const c_char) -> *mut c_char>,
+    free: unsafe extern "C" fn(*mut c_char),
+}
+
+// the table only holds pointers to statics
+unsafe impl Sync for PluginV1 {}
+
+static TABLE: PluginV1 = PluginV1 {
+    abi_version: 1,
+    name: c"shout".as_ptr(),
+    render: Some(render),
+    decode: Some(decode),
+    lint: Some(lint),
+    free,
+};
+
+fn text(s: *const c_char) -> String {
+    unsafe { CStr::from_ptr(s) }.to_string_lossy().to_string()
+}
+
+fn give(s: String) -> *mut c_char {
+    CString::new(s).unwrap().into_raw()
+}
+
+#[no_mangle]
+pub extern "C" fn gdtree_plugin() -> *const PluginV1 {
+    &TABLE
+}
+
+unsafe extern "C" fn render(scene: *const c_char) -> *mut c_char {
+    give(format!("{}\n", text(scene).to_uppercase()))
+}
+
+unsafe extern "C" fn decode(_type: *const c_char, _resource: *const c_char) -> *mut c_char {
+    match text(_type).as_str() {
+        "Curve" => give("a curve, decoded".to_string()),
+        _ => std::ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn lint(_scene: *const c_char) -> *mut c_char {
+    give(r#"[{"node": ".", "message": "too quiet"}]"#.to_string())
+}
+
+unsafe extern "C" fn free(s: *mut c_char) {
+    drop(CString::from_raw(s));
+}
@@ -0,0 +1 @@
+extends CharacterBody2D
@@ -0,0 +1,4 @@
+using Godot;
+
+[Tool]
+public partial class Gizmo : Node3D {}
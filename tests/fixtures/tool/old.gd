@@ -0,0 +1,2 @@
+tool
+extends Node2D
@@ -0,0 +1,2 @@
+extends Node
+# @tool is only mentioned
@@ -0,0 +1,179 @@
+//! The library's parser, run on the scenes in tests/fixtures.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+use gdtree::scene::{self, Scene};
+use gdtree::version::GodotVersion;
+use gdtree::Project;
+
+fn fixture(path: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures").join(path)
+}
+
+fn parse(path: &str, project: Option<&Project>) -> std::io::Result<Scene> {
+    scene::parse_scene(BufReader::new(File::open(fixture(path))?), project)
+}
+
+#[test]
+fn format_3_scene() {
+    let scene = parse("apply/main.tscn", None).unwrap();
+    let paths: Vec<(String, &str)> = scene.root.paths().into_iter().map(|(path, node)| (path, node._type.as_str())).collect();
+    let expected = [(".", "Node"), ("Panel", "Control"), ("Panel/OK", "Button"), ("Panel/OK/Icon", "Sprite2D"), ("Other", "Node"), ("Menu", "")];
+    assert_eq!(paths, expected.map(|(path, _type)| (path.to_string(), _type)));
+    assert_eq!(scene.root.name, "Main");
+    assert_eq!(scene.ext_resources.len(), 2);
+    assert_eq!(scene.root.children["Menu"].instance.as_ref().unwrap().path, "res://ui/menu.tscn");
+    assert_eq!(scene.detected.version, GodotVersion::Godot4);
+}
+
+#[test]
+fn parse_without_a_project() {
+    let text = "[gd_scene format=3]\n\n[ext_resource type=\"PackedScene\" uid=\"uid://bmenu\" path=\"res://ui/menu.tscn\" id=\"1\"]\n\n[node name=\"Main\" type=\"Node\"]\n\n[node name=\"Menu\" parent=\".\" instance=ExtResource(\"1\")]\n";
+    let scene = gdtree::parse_scene(text.as_bytes()).unwrap();
+    assert_eq!(scene.root.children["Menu"].instance.as_ref().unwrap().path, "res://ui/menu.tscn");
+}
+
+#[test]
+fn res_paths_in_a_project() {
+    let project = Project::find(&fixture("apply")).unwrap();
+    let path = project.resolve("res://ui/menu.tscn");
+    assert_eq!(path, fixture("apply/ui/menu.tscn").canonicalize().unwrap());
+    assert_eq!(project.res_path(&path).as_deref(), Some("res://ui/menu.tscn"));
+}
+
+#[test]
+fn resolve() {
+    let scene = parse("apply/main.tscn", None).unwrap();
+    let root = &scene.root;
+    let resolve = |from: &str, path: &str| root.resolve(from, path);
+    assert_eq!(resolve(".", "Panel/OK").as_deref(), Some("Panel/OK"));
+    assert_eq!(resolve(".", ".").as_deref(), Some("."));
+    assert_eq!(resolve("Panel/OK/Icon", "..").as_deref(), Some("Panel/OK"));
+    assert_eq!(resolve("Panel/OK/Icon", "../../..").as_deref(), Some("."));
+    assert_eq!(resolve("Other", "%OK").as_deref(), Some("Panel/OK"));
+    assert_eq!(resolve("Other", "%OK/Icon").as_deref(), Some("Panel/OK/Icon"));
+    // a property path after the colon doesn't change the node
+    assert_eq!(resolve("Panel/OK/Icon", "..:text").as_deref(), Some("Panel/OK"));
+    // names match case-sensitively
+    assert_eq!(resolve(".", "panel"), None);
+    // only nodes marked unique_name_in_owner can be reached with %
+    assert_eq!(resolve(".", "%Icon"), None);
+    // climbing above the root, and absolute paths, lead outside the scene
+    assert_eq!(resolve(".", ".."), None);
+    assert_eq!(resolve(".", "/root/Main/Panel"), None);
+    assert_eq!(resolve(".", "Panel/Cancel"), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    let scene = parse("apply/main.tscn", None).unwrap();
+    let json = serde_json::to_value(&scene).unwrap();
+    let back: Scene = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(serde_json::to_value(&back).unwrap(), json);
+    assert_eq!(back.root.children["Panel"].children["OK"]._type, "Button");
+}
+
+#[test]
+fn values_over_several_lines() {
+    let scene = parse("values/shader.tscn", None).unwrap();
+    let code = &scene.sub_resources[0].parameters[0];
+    assert_eq!(code.key, "code");
+    assert_eq!(code.val, "\"shader_type canvas_item;\nvoid fragment() {\n\tCOLOR = vec4(1.0);\n}\n\"");
+    let params = &scene.root.parameters;
+    assert_eq!(params[0].val, "{\n\"a\": [1, 2],\n\"b\": \"x\\\"]y\"\n}");
+    assert_eq!(params[1].val, "\"say \\\"hi\\\" [ok]\"");
+}
+
+#[test]
+fn unclosed_header() {
+    let err = parse("values/unclosed.tscn", None).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), "line 5, column 1: the node section header is never closed");
+}
+
+#[test]
+fn syntax_errors() {
+    for (text, error) in [
+        ("[node name=\"Main\" type=\"Node\"]\ntext = \"open\n", "line 2, column 8: string is never closed"),
+        ("[node name=\"Main\" type=\"Node\"]\ntext \"x\"\n", "line 2, column 6: expected `=` after the property name"),
+        ("[gd_scene format=3]\n, = 1\n", "line 2, column 1: expected a section or a property"),
+    ] {
+        let err = gdtree::parser::parse(text).unwrap_err();
+        assert_eq!(err.to_string(), error, "{:?}", text);
+    }
+}
+
+#[test]
+fn unescape() {
+    assert_eq!(gdtree::lexer::unescape("\"a\\\"b\\n\\tc\""), "a\"b\n\tc");
+    assert_eq!(gdtree::lexer::unescape("&\"name\""), "name");
+    assert_eq!(gdtree::lexer::unescape("^\"Panel/OK\""), "Panel/OK");
+    assert_eq!(gdtree::lexer::unescape("\"\\u00e9\""), "é");
+}
+
+#[test]
+fn format_2_scene() {
+    let scene = parse("godot3/hud.tscn", None).unwrap();
+    assert_eq!(scene.detected.version, GodotVersion::Godot3);
+    assert_eq!(scene.detected.evidence[0], "format=2");
+    let icon = &scene.root.children["Icon"];
+    assert_eq!(icon._type, "TextureRect");
+    // the Godot 3 `ExtResource( 1 )` spelling, with spaces and a bare id
+    let texture = icon.parameters.iter().find(|p| p.key == "texture").unwrap();
+    assert_eq!(texture.resource.as_ref().unwrap().path, "res://icon.png");
+    assert_eq!(scene.root.connections.len(), 1);
+}
+
+#[test]
+fn format_1_scene() {
+    let text = "[gd_scene load_steps=2 format=1]\n\n[ext_resource path=\"res://icon.png\" type=\"Texture\" id=1]\n\n[node name=\"Main\" type=\"Sprite\"]\n\ntexture = ExtResource( 1 )\n";
+    let scene = gdtree::parse_scene(text.as_bytes()).unwrap();
+    assert_eq!(scene.detected.version, GodotVersion::Godot2);
+    assert_eq!(scene.root.parameters[0].resource.as_ref().unwrap().path, "res://icon.png");
+}
+
+#[test]
+fn structure_only() {
+    let scene = scene::parse_structure(BufReader::new(File::open(fixture("apply/main.tscn")).unwrap())).unwrap();
+    let full = parse("apply/main.tscn", None).unwrap();
+    let paths = |scene: &Scene| scene.root.paths().into_iter().map(|(path, node)| (path, node._type.clone())).collect::<Vec<_>>();
+    assert_eq!(paths(&scene), paths(&full));
+    assert!(scene.root.parameters.is_empty() && scene.ext_resources.is_empty());
+    assert!(scene.root.children["Menu"].instance.is_none());
+    assert_eq!(scene.detected.version, GodotVersion::Godot4);
+
+    // a header inside a multi-line string is text, not a node
+    let text = "[gd_scene format=3]\n\n[node name=\"Main\" type=\"Node\"]\ntext = \"a \\\"quoted\\\" line\n[node name=\\\"Fake\\\" parent=\\\".\\\"]\n\"\n\n[node name=\"Real\" type=\"Node\" parent=\".\"]\n";
+    let scene = scene::parse_structure(text.as_bytes()).unwrap();
+    assert_eq!(scene.root.children.keys().collect::<Vec<_>>(), ["Real"]);
+}
+
+#[test]
+fn connections_to_missing_nodes() {
+    let scene = parse("apply/main.tscn", None).unwrap();
+    let all: Vec<_> = scene.root.paths().into_iter().flat_map(|(_, node)| node.connections.iter()).collect();
+    let missing = |from: &str| all.iter().find(|c| c.from == from).unwrap().missing.clone();
+    assert_eq!(missing("Panel/Cancel").as_deref(), Some("Panel/Cancel"));
+    assert_eq!(missing("Panel/OK"), None);
+    // a node of an instanced scene may be connected without being listed
+    assert_eq!(missing("Menu/Sound"), None);
+    // the connection from a node that isn't there is handed to the root
+    assert!(scene.root.connections.iter().any(|c| c.from == "Panel/Cancel"));
+}
+
+#[test]
+fn connections_to_missing_methods() {
+    let project = Project::find(&fixture("apply")).unwrap();
+    let ok = &parse("apply/main.tscn", Some(&project)).unwrap().root.children["Panel"].children["OK"];
+    let method = |name: &str| ok.connections.iter().find(|c| c.method == name).unwrap();
+    assert_eq!(method("_on_ok_pressed").missing_method, None);
+    assert_eq!(method("_on_gone").missing_method.as_deref(), Some("res://main.gd"));
+    assert!(method("_on_gone").tags().ends_with(" [no method in res://main.gd]"));
+
+    // scripts are only read within a project
+    let ok = &parse("apply/main.tscn", None).unwrap().root.children["Panel"].children["OK"];
+    assert!(ok.connections.iter().all(|c| c.missing_method.is_none()));
+}